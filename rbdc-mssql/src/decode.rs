@@ -1,10 +1,11 @@
+use crate::geography::SqlGeography;
 use chrono::{FixedOffset, NaiveDateTime};
 use fastdate::offset_sec;
 use rbdc::datetime::DateTime;
 use rbdc::Error;
 use rbs::{to_value, Value};
 use tiberius::numeric::BigDecimal;
-use tiberius::ColumnData;
+use tiberius::{Column, ColumnData, ColumnType};
 
 pub trait Decode {
     fn decode(row: &ColumnData<'static>) -> Result<Value, Error>;
@@ -47,7 +48,7 @@ impl Decode for Value {
             },
             ColumnData::Guid(v) => match v {
                 None => Value::Null,
-                Some(v) => Value::String(v.to_string()).into_ext("Uuid"),
+                Some(v) => Value::from(rbdc::uuid::Uuid::from(*v)),
             },
             ColumnData::Binary(v) => match v {
                 None => Value::Null,
@@ -158,31 +159,54 @@ impl Decode for Value {
                     }
                 }
             },
-            ColumnData::DateTimeOffset(v) => match v {
-                None => Value::Null,
-                Some(_) => {
-                    let v: tiberius::Result<Option<chrono::DateTime<FixedOffset>>> =
-                        tiberius::FromSql::from_sql(row);
-                    match v {
-                        Ok(v) => match v {
-                            None => Value::Null,
-                            Some(v) => {
-                                let dt = DateTime(fastdate::DateTime::from_timestamp_nano(
-                                    v.timestamp_nanos_opt()
-                                        .expect("value can not be represented in a timestamp with nanosecond precision.") as i128 - (v.offset().utc_minus_local() * 60) as i128).set_offset(v.offset().utc_minus_local() * 60));
-                                to_value!(dt)
+            ColumnData::DateTimeOffset(v) => {
+                match v {
+                    None => Value::Null,
+                    Some(_) => {
+                        let v: tiberius::Result<Option<chrono::DateTime<FixedOffset>>> =
+                            tiberius::FromSql::from_sql(row);
+                        match v {
+                            Ok(v) => match v {
+                                None => Value::Null,
+                                Some(v) => {
+                                    let dt = DateTime(
+                                    <fastdate::DateTime as DateTimeFromDateTimeFixedOffset>::from(v),
+                                );
+                                    rbs::value!(dt)
+                                }
+                            },
+                            Err(e) => {
+                                return Err(Error::from(e.to_string()));
                             }
-                        },
-                        Err(e) => {
-                            return Err(Error::from(e.to_string()));
                         }
                     }
                 }
-            },
+            }
         })
     }
 }
 
+/// decode `data` of `column`. a CLR user defined type column holding a `geography` or
+/// `geometry` decodes to `Value::Ext("Geography", Binary)`, read it with
+/// [`crate::geography::SqlGeography`]. the column does not say which of the two it is,
+/// use [`SqlGeography::from_geometry`] on the bytes of a `geometry` column.
+/// other UDTs (`hierarchyid`, ...) decode to `Value::Ext("Udt", Binary)`.
+/// tiberius 0.12 can not read UDT column metadata yet, until it does select a
+/// `geography`/`geometry` as `varbinary(max)`
+pub fn decode_column(column: &Column, data: &ColumnData<'static>) -> Result<Value, Error> {
+    match data {
+        ColumnData::Binary(Some(v)) if column.column_type() == ColumnType::Udt => {
+            let tag = if SqlGeography::from_geography(v).is_ok() {
+                "Geography"
+            } else {
+                "Udt"
+            };
+            Ok(Value::Ext(tag, Box::new(Value::Binary(v.to_vec()))))
+        }
+        data => Value::decode(data),
+    }
+}
+
 pub trait DateTimeFromNativeDatetime {
     fn from(arg: chrono::NaiveDateTime) -> Self;
 }
@@ -191,35 +215,40 @@ pub trait DateTimeFromDateTimeFixedOffset {
     fn from(arg: chrono::DateTime<FixedOffset>) -> Self;
 }
 
+// nanoseconds since the epoch, `timestamp_nanos_opt` overflows after the year 2262
+fn timestamp_nano<Tz: chrono::TimeZone>(v: &chrono::DateTime<Tz>) -> i128 {
+    v.timestamp() as i128 * 1_000_000_000 + v.timestamp_subsec_nanos() as i128
+}
+
 impl DateTimeFromNativeDatetime for fastdate::DateTime {
     fn from(arg: NaiveDateTime) -> Self {
-        fastdate::DateTime::from_timestamp_nano(
-            arg.and_utc()
-                .timestamp_nanos_opt()
-                .expect("value can not be represented in a timestamp with nanosecond precision.")
-                as i128,
-        )
-        .set_offset(offset_sec())
-        .add_sub_sec(-offset_sec() as i64)
+        // the value has no zone, keep its wall clock in the local zone
+        DateTime::from_timestamp_nano(timestamp_nano(&arg.and_utc()))
+            .to_offset(offset_sec())
+            .add_seconds(-offset_sec() as i64)
+            .0
     }
 }
 
 impl DateTimeFromDateTimeFixedOffset for fastdate::DateTime {
     fn from(arg: chrono::DateTime<FixedOffset>) -> Self {
-        fastdate::DateTime::from_timestamp_nano(
-            arg.timestamp_nanos_opt()
-                .expect("value can not be represented in a timestamp with nanosecond precision.")
-                as i128,
-        )
-        .set_offset(arg.offset().local_minus_utc())
+        DateTime::from_timestamp_nano(timestamp_nano(&arg))
+            .to_offset(arg.offset().local_minus_utc())
+            .0
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::decode::{DateTimeFromDateTimeFixedOffset, DateTimeFromNativeDatetime};
+    use crate::decode::{
+        decode_column, DateTimeFromDateTimeFixedOffset, DateTimeFromNativeDatetime, Decode,
+    };
+    use crate::encode::Encode;
+    use crate::geography::SqlGeography;
     use chrono::{FixedOffset, NaiveDateTime};
     use fastdate::DateTime;
+    use rbs::Value;
+    use tiberius::{Column, ColumnData, ColumnType};
 
     #[test]
     fn test_decode_time_zone() {
@@ -237,6 +266,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_decode_far_future() {
+        let naive =
+            NaiveDateTime::parse_from_str("2500-12-31 23:59:59.123", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap();
+        let de = <DateTime as DateTimeFromNativeDatetime>::from(naive);
+        assert_eq!(de.display_stand(), "2500-12-31 23:59:59.123");
+
+        let offset = FixedOffset::west_opt(5 * 60 * 60).unwrap();
+        let dt = chrono::DateTime::<FixedOffset>::from_naive_utc_and_offset(naive, offset);
+        let de = <DateTime as DateTimeFromDateTimeFixedOffset>::from(dt);
+        assert_eq!(de.display(true), "2500-12-31T18:59:59.123-05:00");
+    }
+
+    #[test]
+    fn test_decode_guid() {
+        let guid = tiberius::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let v = <Value as Decode>::decode(&ColumnData::Guid(Some(guid))).unwrap();
+        assert_eq!(
+            v,
+            Value::from(
+                rbdc::uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_udt() {
+        let data = ColumnData::Binary(Some(vec![0xE6, 0x10, 0, 0, 1, 0x0C].into()));
+        let column = Column::new("geo".to_string(), ColumnType::Udt);
+        let v = decode_column(&column, &data).unwrap();
+        assert_eq!(
+            v,
+            Value::Binary(vec![0xE6, 0x10, 0, 0, 1, 0x0C]).into_ext("Udt")
+        );
+        let column = Column::new("bytes".to_string(), ColumnType::BigVarBin);
+        let v = decode_column(&column, &data).unwrap();
+        assert_eq!(v, Value::Binary(vec![0xE6, 0x10, 0, 0, 1, 0x0C]));
+    }
+
+    #[test]
+    fn test_decode_geography() {
+        // geography::STGeomFromText('POINT(-122.349 47.651)', 4326)
+        let mut bytes = vec![0xE6, 0x10, 0, 0, 1, 0x0C];
+        bytes.extend(47.651f64.to_le_bytes());
+        bytes.extend((-122.349f64).to_le_bytes());
+        let data = ColumnData::Binary(Some(bytes.clone().into()));
+        let column = Column::new("geo".to_string(), ColumnType::Udt);
+        let v = decode_column(&column, &data).unwrap();
+        assert_eq!(v, Value::Binary(bytes.clone()).into_ext("Geography"));
+        let g = SqlGeography::try_from(v.clone()).unwrap();
+        assert_eq!(g.srid, 4326);
+        assert_eq!(g.wkt, "POINT (-122.349 47.651)");
+        // and back as a parameter
+        let mut q = tiberius::Query::new("INSERT INTO t (geo) VALUES (@P1)");
+        assert!(v.encode(&mut q).is_ok());
+    }
+
     #[test]
     fn test_decode_zone_native() {
         let dt = NaiveDateTime::from_timestamp_opt(1698039464, 0).unwrap();
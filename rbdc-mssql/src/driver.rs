@@ -18,10 +18,10 @@ impl Driver for MssqlDriver {
             let mut opt = self.default_option();
             opt.set_uri(&url)?;
             if let Some(opt) = opt.downcast_ref::<MssqlConnectOptions>() {
-                let conn = MssqlConnection::establish(&opt.0).await?;
+                let conn = MssqlConnection::connect_with_retry(opt).await?;
                 Ok(Box::new(conn) as Box<dyn Connection>)
             } else {
-                Err(Error::from("downcast_ref failure"))
+                Err(Error::from("expected MssqlConnectOptions"))
             }
         })
     }
@@ -30,9 +30,10 @@ impl Driver for MssqlDriver {
         &'a self,
         opt: &'a dyn ConnectOptions,
     ) -> BoxFuture<'a, Result<Box<dyn Connection>, Error>> {
-        let opt = opt.downcast_ref::<MssqlConnectOptions>().unwrap();
+        let opt = opt.downcast_ref::<MssqlConnectOptions>().cloned();
         Box::pin(async move {
-            let conn = MssqlConnection::establish(&opt.0).await?;
+            let opt = opt.ok_or_else(|| Error::from("expected MssqlConnectOptions"))?;
+            let conn = MssqlConnection::connect_with_retry(&opt).await?;
             Ok(Box::new(conn) as Box<dyn Connection>)
         })
     }
@@ -40,7 +41,7 @@ impl Driver for MssqlDriver {
     fn default_option(&self) -> Box<dyn ConnectOptions> {
         let mut config = Config::new();
         config.trust_cert();
-        Box::new(MssqlConnectOptions(config))
+        Box::new(MssqlConnectOptions::from(config))
     }
 }
 
@@ -64,6 +65,168 @@ mod test {
         assert_eq!("insert into biz_activity (id,name,pc_link,h5_link,pc_banner_img,h5_banner_img,sort,status,remark,create_time,version,delete_flag) VALUES (@P1,@P2,@P3,@P4,@P5,@P6,@P7,@P8,@P9,@P10,@P11,@P12)", sql);
     }
 
+    #[test]
+    fn test_to_url() {
+        use crate::MssqlConnectOptions;
+        use rbdc::db::{ConnectOptions, Driver};
+
+        let uri =
+            "jdbc:sqlserver://localhost:1433;User=SA;Password={TestPass!123456};Database=master;";
+        let mut opt = MssqlConnectOptions::from(tiberius::Config::new());
+        assert!(opt.to_url().is_err());
+        opt.set_uri(uri).unwrap();
+        assert_eq!(opt.to_url().unwrap(), uri);
+        assert!(!format!("{:?}", opt).contains("TestPass!123456"));
+        assert!(!opt.redact_uri().contains("TestPass!123456"));
+        let mut parsed = MssqlDriver {}.default_option();
+        parsed.set_uri(&opt.to_url().unwrap()).unwrap();
+        assert_eq!(
+            format!(
+                "{:?}",
+                parsed.downcast_ref::<MssqlConnectOptions>().unwrap().config()
+            ),
+            format!("{:?}", opt.config())
+        );
+    }
+
+    #[test]
+    fn test_default_option() {
+        use crate::MssqlConnectOptions;
+        use rbdc::db::{ConnectOptions, Driver};
+
+        let mut opt = MssqlDriver {}.default_option();
+        opt.set_uri("jdbc:sqlserver://localhost:1433;User=SA;Password={TestPass!123456};")
+            .unwrap();
+        let opt = opt.downcast_ref::<MssqlConnectOptions>().unwrap();
+        assert_eq!(opt.config.get_addr(), "localhost:1433");
+        assert!(!opt.redact_uri().contains("TestPass!123456"));
+    }
+
+    #[test]
+    fn test_parse_timeouts() {
+        use crate::{MssqlConnectOptions, MssqlTimeouts};
+        use rbdc::db::{ConnectOptions, Driver};
+        use std::time::Duration;
+
+        let mut opt = MssqlDriver {}.default_option();
+        opt.set_uri("jdbc:sqlserver://localhost:1433;User=SA;Password={TestPass!123456};loginTimeout=5;queryTimeout=30;")
+            .unwrap();
+        let opt = opt.downcast_ref::<MssqlConnectOptions>().unwrap();
+        assert_eq!(
+            opt.timeouts,
+            MssqlTimeouts {
+                connect_timeout: Some(Duration::from_secs(5)),
+                command_timeout: Some(Duration::from_secs(30)),
+            }
+        );
+
+        let mut opt = MssqlDriver {}.default_option();
+        opt.set_uri(
+            "server=tcp:localhost,1433;user=SA;password=a;Connect Timeout=3;Command Timeout=10",
+        )
+        .unwrap();
+        let opt = opt.downcast_ref::<MssqlConnectOptions>().unwrap();
+        assert_eq!(opt.timeouts.connect_timeout, Some(Duration::from_secs(3)));
+        assert_eq!(opt.timeouts.command_timeout, Some(Duration::from_secs(10)));
+
+        let mut opt = MssqlDriver {}.default_option();
+        assert!(opt
+            .set_uri("server=tcp:localhost,1433;Command Timeout=soon")
+            .is_err());
+
+        let mut opt =
+            MssqlConnectOptions::from(tiberius::Config::new()).connect_timeout(Duration::from_secs(1));
+        let other =
+            MssqlConnectOptions::from(tiberius::Config::new()).command_timeout(Duration::from_secs(2));
+        opt.merge(&other);
+        assert_eq!(opt.timeouts.connect_timeout, Some(Duration::from_secs(1)));
+        assert_eq!(opt.timeouts.command_timeout, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_parse_reconnect() {
+        use crate::{MssqlConnectOptions, MssqlReconnect};
+        use rbdc::db::{ConnectOptions, Driver};
+        use std::time::Duration;
+
+        let mut opt = MssqlDriver {}.default_option();
+        opt.set_uri(
+            "jdbc:sqlserver://localhost:1433;User=SA;connectRetryCount=3;connectRetryInterval=2;",
+        )
+        .unwrap();
+        let opt = opt.downcast_ref::<MssqlConnectOptions>().unwrap();
+        assert_eq!(
+            opt.reconnect,
+            MssqlReconnect {
+                max_attempts: 3,
+                base_delay: Duration::from_secs(2),
+            }
+        );
+
+        let mut opt = MssqlDriver {}.default_option();
+        assert!(opt
+            .set_uri("server=tcp:localhost,1433;ConnectRetryCount=many")
+            .is_err());
+
+        let mut opt = MssqlConnectOptions::from(tiberius::Config::new());
+        let other = MssqlConnectOptions::from(tiberius::Config::new())
+            .max_reconnect_attempts(2)
+            .reconnect_base_delay(Duration::from_millis(50));
+        opt.merge(&other);
+        assert_eq!(opt.reconnect.max_attempts, 2);
+        assert_eq!(opt.reconnect.base_delay, Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_connect_retry() {
+        use crate::{MssqlConnection, MssqlConnectOptions};
+        use rbdc::{ErrorKind, ErrorKindExt};
+        use std::time::{Duration, Instant};
+
+        // nothing listens on the port, every attempt is refused
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let mut config = tiberius::Config::new();
+        config.host("127.0.0.1");
+        config.port(port);
+        let opt = MssqlConnectOptions::from(config)
+            .max_reconnect_attempts(2)
+            .reconnect_base_delay(Duration::from_millis(20));
+        let start = Instant::now();
+        let e = MssqlConnection::connect_with_retry(&opt)
+            .await
+            .err()
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(60));
+        assert_eq!(e.kind(), ErrorKind::Connection, "{}", e);
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        use crate::{MssqlConnection, MssqlConnectOptions};
+        use rbdc::{ErrorKind, ErrorKindExt};
+        use std::time::{Duration, Instant};
+        use tokio::io::AsyncReadExt;
+
+        // accepts the connection but never answers the prelogin
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let _ = socket.read_to_end(&mut vec![]).await;
+        });
+        let mut config = tiberius::Config::new();
+        config.host("127.0.0.1");
+        config.port(port);
+        let opt = MssqlConnectOptions::from(config).connect_timeout(Duration::from_millis(100));
+        let start = Instant::now();
+        let e = MssqlConnection::establish_with(&opt).await.err().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(e.kind(), ErrorKind::Timeout, "{}", e);
+    }
+
     // #[tokio::test]
     // async fn test_mssql_pool() {
     //     use rbdc::pool::Pool;
@@ -1,3 +1,4 @@
+use rbdc::timestamp::Timestamp;
 use rbdc::Error;
 use rbs::Value;
 use std::str::FromStr;
@@ -85,7 +86,7 @@ impl Encode for Value {
                 }
                 "Json" => Err(Error::from("unimplemented")),
                 "Timestamp" => {
-                    q.bind(v.as_i64().unwrap_or_default());
+                    q.bind(Timestamp::from_ext_value(&v).unwrap_or_default().0);
                     Ok(())
                 }
                 "Uuid" => {
@@ -94,6 +95,14 @@ impl Encode for Value {
                     );
                     Ok(())
                 }
+                // the CLR serialization, SQL Server converts `varbinary` to the column type
+                "Geography" | "Geometry" | "Udt" => match *v {
+                    Value::Binary(v) => {
+                        q.bind(v);
+                        Ok(())
+                    }
+                    v => Err(Error::from(format!("warn type encode {}:{}", t, v))),
+                },
                 _ => Err(Error::from("unimplemented")),
             },
         }
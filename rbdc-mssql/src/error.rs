@@ -0,0 +1,69 @@
+use rbdc::{Error, ErrorKind, ErrorKindExt};
+use tiberius::error::IoErrorKind;
+
+/// a tiberius error as an [`Error`], a server error gets the kind of its error number
+pub fn from_tiberius(e: tiberius::error::Error) -> Error {
+    let kind = match &e {
+        tiberius::error::Error::Io { kind, .. } => match kind {
+            IoErrorKind::TimedOut => ErrorKind::Timeout,
+            _ => ErrorKind::Connection,
+        },
+        tiberius::error::Error::Tls(_) | tiberius::error::Error::Routing { .. } => {
+            ErrorKind::Connection
+        }
+        tiberius::error::Error::Conversion(_)
+        | tiberius::error::Error::Utf8
+        | tiberius::error::Error::Utf16
+        | tiberius::error::Error::ParseInt(_) => ErrorKind::DataType,
+        tiberius::error::Error::Server(e) => kind_of_number(e.code()),
+        _ => ErrorKind::Unknown,
+    };
+    Error::with_kind(e.to_string(), kind)
+}
+
+/// the kind of a [database engine error](https://learn.microsoft.com/sql/relational-databases/errors-events/database-engine-events-and-errors)
+pub fn kind_of_number(number: u32) -> ErrorKind {
+    match number {
+        // login failed, password expired, password must be changed
+        18456 | 18487 | 18488 => ErrorKind::Authentication,
+        // duplicate key in a unique index, unique/primary key constraint,
+        // foreign key/check constraint, NULL into a NOT NULL column
+        2601 | 2627 | 547 | 515 => ErrorKind::ConstraintViolation,
+        // lock request time out period exceeded
+        1222 => ErrorKind::Timeout,
+        // arithmetic overflow, conversion failed, value out of range, data would be truncated
+        220 | 232 | 241 | 242 | 245 | 8114 | 8115 | 8152 | 2628 => ErrorKind::DataType,
+        // syntax error, invalid column/object name, ambiguous column, unknown procedure
+        102 | 156 | 207 | 208 | 209 | 2812 | 4104 => ErrorKind::Sql,
+        _ => ErrorKind::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{from_tiberius, kind_of_number};
+    use rbdc::{ErrorKind, ErrorKindExt};
+
+    #[test]
+    fn test_kind_of_number() {
+        assert_eq!(kind_of_number(2627), ErrorKind::ConstraintViolation);
+        assert_eq!(kind_of_number(208), ErrorKind::Sql);
+        assert_eq!(kind_of_number(18456), ErrorKind::Authentication);
+        assert_eq!(kind_of_number(50000), ErrorKind::Unknown);
+    }
+
+    #[test]
+    fn test_from_tiberius() {
+        let e = from_tiberius(tiberius::error::Error::Io {
+            kind: tiberius::error::IoErrorKind::ConnectionReset,
+            message: "reset by peer".to_string(),
+        });
+        assert_eq!(e.kind(), ErrorKind::Connection);
+        assert!(!e.to_string().contains("SQLSTATE"));
+        let e = from_tiberius(tiberius::error::Error::Conversion("bad value".into()));
+        assert_eq!(e.kind(), ErrorKind::DataType);
+        // a protocol error has no kind of its own, the text decides
+        let e = from_tiberius(tiberius::error::Error::Protocol("unexpected token".into()));
+        assert_eq!(e.kind(), ErrorKind::Unknown);
+    }
+}
@@ -0,0 +1,368 @@
+use byteorder::{ByteOrder, LittleEndian};
+use rbdc::Error;
+use rbs::Value;
+use std::fmt::Write;
+
+const HAS_Z: u8 = 0x01;
+const HAS_M: u8 = 0x02;
+const SINGLE_POINT: u8 = 0x08;
+const SINGLE_LINE_SEGMENT: u8 = 0x10;
+
+/// a `geography` or `geometry` value, parsed from the SQL Server CLR serialization
+/// (the bytes of `CAST(geo AS varbinary(max))`, or a `Value::Ext("Geography" | "Geometry", Binary)`)
+///
+/// only the OGC types `Point`..`GeometryCollection` are supported, circular arcs and
+/// `FULLGLOBE` return an error
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlGeography {
+    pub srid: i32,
+    /// e.g. `POINT (-122.349 47.651)`, `x y` is `longitude latitude` for a `geography`
+    pub wkt: String,
+}
+
+impl SqlGeography {
+    /// parse a `geography`, points are stored `latitude longitude`
+    pub fn from_geography(bytes: &[u8]) -> Result<Self, Error> {
+        Parser::parse(bytes, true)
+    }
+
+    /// parse a `geometry`, points are stored `x y`
+    pub fn from_geometry(bytes: &[u8]) -> Result<Self, Error> {
+        Parser::parse(bytes, false)
+    }
+}
+
+impl TryFrom<Value> for SqlGeography {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Ext("Geography", v) => match *v {
+                Value::Binary(v) => SqlGeography::from_geography(&v),
+                v => Err(Error::from(format!("warn type decode Geography:{}", v))),
+            },
+            Value::Ext("Geometry", v) => match *v {
+                Value::Binary(v) => SqlGeography::from_geometry(&v),
+                v => Err(Error::from(format!("warn type decode Geometry:{}", v))),
+            },
+            // plain bytes (or an untyped `Ext("Udt")`) do not say whether the points are
+            // planar, use `from_geography`/`from_geometry`
+            v => Err(Error::from(format!("warn type decode Geography:{}", v))),
+        }
+    }
+}
+
+struct Figure {
+    point_offset: usize,
+}
+
+struct Shape {
+    parent_offset: i32,
+    figure_offset: i32,
+    kind: u8,
+}
+
+struct Parser<'a> {
+    buf: &'a [u8],
+    geography: bool,
+    points: Vec<(f64, f64)>,
+    z: Option<Vec<f64>>,
+    m: Option<Vec<f64>>,
+    figures: Vec<Figure>,
+    shapes: Vec<Shape>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(buf: &'a [u8], geography: bool) -> Result<SqlGeography, Error> {
+        let mut p = Parser {
+            buf,
+            geography,
+            points: vec![],
+            z: None,
+            m: None,
+            figures: vec![],
+            shapes: vec![],
+        };
+        let srid = p.i32()?;
+        let version = p.u8()?;
+        if version != 1 && version != 2 {
+            return Err(Error::from(format!(
+                "geography: unknown version {}",
+                version
+            )));
+        }
+        let flags = p.u8()?;
+        let num_points = if flags & SINGLE_POINT != 0 {
+            1
+        } else if flags & SINGLE_LINE_SEGMENT != 0 {
+            2
+        } else {
+            p.u32()? as usize
+        };
+        for _ in 0..num_points {
+            let a = p.f64()?;
+            let b = p.f64()?;
+            p.points.push(if geography { (b, a) } else { (a, b) });
+        }
+        if flags & HAS_Z != 0 {
+            p.z = Some(p.f64s(num_points)?);
+        }
+        if flags & HAS_M != 0 {
+            p.m = Some(p.f64s(num_points)?);
+        }
+        if flags & (SINGLE_POINT | SINGLE_LINE_SEGMENT) != 0 {
+            p.figures.push(Figure { point_offset: 0 });
+            p.shapes.push(Shape {
+                parent_offset: -1,
+                figure_offset: 0,
+                kind: if flags & SINGLE_POINT != 0 { 1 } else { 2 },
+            });
+        } else {
+            for _ in 0..p.u32()? {
+                let _attribute = p.u8()?;
+                let point_offset = p.i32()? as usize;
+                p.figures.push(Figure { point_offset });
+            }
+            for _ in 0..p.u32()? {
+                let parent_offset = p.i32()?;
+                let figure_offset = p.i32()?;
+                let kind = p.u8()?;
+                p.shapes.push(Shape {
+                    parent_offset,
+                    figure_offset,
+                    kind,
+                });
+            }
+        }
+        if p.shapes.is_empty() {
+            return Err(Error::from("geography: no shape"));
+        }
+        let mut wkt = String::new();
+        p.write_shape(0, true, &mut wkt)?;
+        Ok(SqlGeography { srid, wkt })
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() < n {
+            return Err(Error::from("geography: buffer too short"));
+        }
+        let (v, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(v)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        Ok(LittleEndian::read_i32(self.take(4)?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(LittleEndian::read_u32(self.take(4)?))
+    }
+
+    fn f64(&mut self) -> Result<f64, Error> {
+        Ok(LittleEndian::read_f64(self.take(8)?))
+    }
+
+    fn f64s(&mut self, n: usize) -> Result<Vec<f64>, Error> {
+        (0..n).map(|_| self.f64()).collect()
+    }
+
+    // the figures of a leaf shape run up to the figures of the next shape that has any
+    fn figures_of(&self, shape: usize) -> std::ops::Range<usize> {
+        let start = self.shapes[shape].figure_offset;
+        if start < 0 {
+            return 0..0;
+        }
+        let end = self.shapes[shape + 1..]
+            .iter()
+            .find(|s| s.figure_offset >= 0)
+            .map(|s| s.figure_offset as usize)
+            .unwrap_or(self.figures.len());
+        start as usize..end.min(self.figures.len())
+    }
+
+    fn points_of(&self, figure: usize) -> std::ops::Range<usize> {
+        let end = self
+            .figures
+            .get(figure + 1)
+            .map(|f| f.point_offset)
+            .unwrap_or(self.points.len());
+        self.figures[figure].point_offset..end.min(self.points.len())
+    }
+
+    fn children(&self, shape: usize) -> Vec<usize> {
+        (shape + 1..self.shapes.len())
+            .filter(|i| self.shapes[*i].parent_offset == shape as i32)
+            .collect()
+    }
+
+    // `(x y, x y)`
+    fn write_figure(&self, figure: usize, out: &mut String) {
+        out.push('(');
+        for (n, i) in self.points_of(figure).enumerate() {
+            if n > 0 {
+                out.push_str(", ");
+            }
+            let (x, y) = self.points[i];
+            let _ = write!(out, "{} {}", x, y);
+            // `x y NULL m` when there are only measures
+            let z = match (&self.z, &self.m) {
+                (None, None) => None,
+                (z, _) => Some(z.as_ref().map(|z| z[i]).unwrap_or(f64::NAN)),
+            };
+            for v in [z, self.m.as_ref().map(|m| m[i])].into_iter().flatten() {
+                match v {
+                    v if v.is_nan() => out.push_str(" NULL"),
+                    v => {
+                        let _ = write!(out, " {}", v);
+                    }
+                }
+            }
+        }
+        out.push(')');
+    }
+
+    // `((x y, ...), (x y, ...))`
+    fn write_figures(&self, shape: usize, out: &mut String) {
+        out.push('(');
+        for (n, f) in self.figures_of(shape).enumerate() {
+            if n > 0 {
+                out.push_str(", ");
+            }
+            self.write_figure(f, out);
+        }
+        out.push(')');
+    }
+
+    // the body of a shape, prefixed with its name when `tagged`
+    // (members of a `MULTI*` are written without it)
+    fn write_shape(&self, shape: usize, tagged: bool, out: &mut String) -> Result<(), Error> {
+        let kind = self.shapes[shape].kind;
+        let name = match kind {
+            1 => "POINT",
+            2 => "LINESTRING",
+            3 => "POLYGON",
+            4 => "MULTIPOINT",
+            5 => "MULTILINESTRING",
+            6 => "MULTIPOLYGON",
+            7 => "GEOMETRYCOLLECTION",
+            _ => {
+                let ty = if self.geography {
+                    "geography"
+                } else {
+                    "geometry"
+                };
+                return Err(Error::from(format!(
+                    "{}: unsupported shape type {}",
+                    ty, kind
+                )));
+            }
+        };
+        if tagged {
+            out.push_str(name);
+            out.push(' ');
+        }
+        match kind {
+            1 | 2 => match self.figures_of(shape).next() {
+                Some(f) => self.write_figure(f, out),
+                None => out.push_str("EMPTY"),
+            },
+            3 if self.figures_of(shape).is_empty() => out.push_str("EMPTY"),
+            3 => self.write_figures(shape, out),
+            _ => {
+                let children = self.children(shape);
+                if children.is_empty() {
+                    out.push_str("EMPTY");
+                    return Ok(());
+                }
+                out.push('(');
+                for (n, child) in children.into_iter().enumerate() {
+                    if n > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write_shape(child, kind == 7, out)?;
+                }
+                out.push(')');
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geography::SqlGeography;
+    use rbs::Value;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // geography::Point(47.651, -122.349, 4326)
+    fn point() -> Vec<u8> {
+        let mut v = vec![0xE6, 0x10, 0, 0, 1, 0x0C];
+        v.extend(47.651f64.to_le_bytes());
+        v.extend((-122.349f64).to_le_bytes());
+        v
+    }
+
+    #[test]
+    fn test_point() {
+        let g = SqlGeography::from_geography(&point()).unwrap();
+        assert_eq!(g.srid, 4326);
+        assert_eq!(g.wkt, "POINT (-122.349 47.651)");
+        let g = SqlGeography::from_geometry(&point()).unwrap();
+        assert_eq!(g.wkt, "POINT (47.651 -122.349)");
+    }
+
+    #[test]
+    fn test_line_segment() {
+        // geography::STGeomFromText('LINESTRING(-122.360 47.656, -122.343 47.656)', 4326)
+        let v = hex("E610000001148716D9CEF7D34740D7A3703D0A975EC08716D9CEF7D34740CBA145B6F3955EC0");
+        let g = SqlGeography::from_geography(&v).unwrap();
+        assert_eq!(g.wkt, "LINESTRING (-122.36 47.656, -122.343 47.656)");
+    }
+
+    #[test]
+    fn test_multi() {
+        // geometry::STGeomFromText('MULTIPOINT((1 2), (3 4))', 0)
+        let mut v = vec![0, 0, 0, 0, 1, 0x04];
+        v.extend(2u32.to_le_bytes());
+        for x in [1f64, 2.0, 3.0, 4.0] {
+            v.extend(x.to_le_bytes());
+        }
+        v.extend(2u32.to_le_bytes());
+        for offset in [0i32, 1] {
+            v.push(1);
+            v.extend(offset.to_le_bytes());
+        }
+        v.extend(3u32.to_le_bytes());
+        for (parent, figure, kind) in [(-1i32, 0i32, 4u8), (0, 0, 1), (0, 1, 1)] {
+            v.extend(parent.to_le_bytes());
+            v.extend(figure.to_le_bytes());
+            v.push(kind);
+        }
+        let g = SqlGeography::from_geometry(&v).unwrap();
+        assert_eq!(g.wkt, "MULTIPOINT ((1 2), (3 4))");
+        assert!(SqlGeography::from_geometry(&v[..v.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_value() {
+        let v = Value::Binary(point()).into_ext("Geography");
+        let g = SqlGeography::try_from(v).unwrap();
+        assert_eq!(g.wkt, "POINT (-122.349 47.651)");
+        let v = Value::Binary(point()).into_ext("Geometry");
+        let g = SqlGeography::try_from(v).unwrap();
+        assert_eq!(g.wkt, "POINT (47.651 -122.349)");
+        assert!(SqlGeography::try_from(Value::Binary(point()).into_ext("Udt")).is_err());
+        assert!(SqlGeography::try_from(Value::Binary(point())).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! tiberius reads the INFO tokens of a response (`PRINT`, `RAISERROR` below severity 11,
+//! `Changed database context to ...`) and the ENVCHANGE tokens, but only reports them as
+//! `INFO` tracing events. [`collect`] polls a future with a dispatcher recording those events,
+//! every event is still passed on to the dispatcher that was current before
+//! (or to `log` when the process has no tracing subscriber, as tracing itself does)
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::OnceLock;
+use tracing::field::{Field, Visit};
+use tracing::subscriber::{Interest, NoSubscriber};
+use tracing::{dispatcher, span, Dispatch, Event, Level, Metadata, Subscriber};
+
+const TARGET: &str = "tiberius::tds::stream::token";
+
+struct Scope {
+    prev: Dispatch,
+    messages: Vec<String>,
+}
+
+thread_local! {
+    // set while `collect` polls its future on this thread
+    static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+/// the output of `f` and the info messages the server sent while it ran
+pub(crate) async fn collect<F: Future>(f: F) -> (F::Output, Vec<String>) {
+    static CAPTURE: OnceLock<Dispatch> = OnceLock::new();
+    let capture = CAPTURE.get_or_init(|| Dispatch::new(Capture));
+    let prev = dispatcher::get_default(|d| d.clone());
+    let mut messages = vec![];
+    let mut f = std::pin::pin!(f);
+    let output = std::future::poll_fn(|cx| {
+        let scope = Scope {
+            prev: prev.clone(),
+            messages: std::mem::take(&mut messages),
+        };
+        let outer = SCOPE.with(|v| v.replace(Some(scope)));
+        let poll = dispatcher::with_default(capture, || f.as_mut().poll(cx));
+        if let Some(scope) = SCOPE.with(|v| v.replace(outer)) {
+            messages = scope.messages;
+        }
+        poll
+    })
+    .await;
+    (output, messages)
+}
+
+fn prev() -> Dispatch {
+    SCOPE
+        .with(|v| v.borrow().as_ref().map(|v| v.prev.clone()))
+        .unwrap_or_else(Dispatch::none)
+}
+
+fn is_info(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == TARGET && *metadata.level() == Level::INFO
+}
+
+#[derive(Default)]
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+struct Capture;
+
+impl Subscriber for Capture {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        // the dispatchers events are passed on to are registered on their own
+        if is_info(metadata) {
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_info(metadata) || prev().enabled(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        Some(tracing::level_filters::LevelFilter::INFO)
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        prev().new_span(span)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        prev().record(span, values)
+    }
+
+    fn record_follows_from(&self, span: &span::Id, follows: &span::Id) {
+        prev().record_follows_from(span, follows)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let prev = prev();
+        let metadata = event.metadata();
+        if is_info(metadata) {
+            let mut message = Message::default();
+            event.record(&mut message);
+            if prev.is::<NoSubscriber>() {
+                let log = log::Metadata::builder()
+                    .level(log::Level::Info)
+                    .target(metadata.target())
+                    .build();
+                if log::Level::Info <= log::max_level() && log::logger().enabled(&log) {
+                    log::logger().log(
+                        &log::Record::builder()
+                            .metadata(log)
+                            .args(format_args!("{}", message.0))
+                            .module_path(metadata.module_path())
+                            .file(metadata.file())
+                            .line(metadata.line())
+                            .build(),
+                    );
+                }
+            }
+            SCOPE.with(|v| {
+                if let Some(scope) = v.borrow_mut().as_mut() {
+                    scope.messages.push(message.0);
+                }
+            });
+        }
+        if prev.enabled(metadata) {
+            prev.event(event);
+        }
+    }
+
+    fn enter(&self, span: &span::Id) {
+        prev().enter(span)
+    }
+
+    fn exit(&self, span: &span::Id) {
+        prev().exit(span)
+    }
+
+    fn clone_span(&self, id: &span::Id) -> span::Id {
+        prev().clone_span(id)
+    }
+
+    fn try_close(&self, id: span::Id) -> bool {
+        prev().try_close(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::info::{collect, TARGET};
+    use std::sync::{Arc, Mutex};
+    use tracing::subscriber::Interest;
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    fn server_info(message: &str) {
+        tracing::event!(target: TARGET, tracing::Level::INFO, "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_collect() {
+        server_info("before");
+        let (v, messages) = collect(async {
+            server_info("Changed database context to 'test'.");
+            tokio::task::yield_now().await;
+            tracing::event!(target: TARGET, tracing::Level::TRACE, "done");
+            server_info("deprecated");
+            1
+        })
+        .await;
+        server_info("after");
+        assert_eq!(v, 1);
+        assert_eq!(
+            messages,
+            vec!["Changed database context to 'test'.", "deprecated"]
+        );
+    }
+
+    // counts the events it is given
+    struct Count(Arc<Mutex<usize>>);
+
+    impl Subscriber for Count {
+        fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+            Interest::always()
+        }
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, _: &Event<'_>) {
+            *self.0.lock().unwrap() += 1;
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_collect_passes_events_on() {
+        let count = Arc::new(Mutex::new(0));
+        let _guard = tracing::subscriber::set_default(Count(count.clone()));
+        let (_, messages) = collect(async {
+            server_info("deprecated");
+            tracing::event!(tracing::Level::DEBUG, "other");
+        })
+        .await;
+        assert_eq!(messages, vec!["deprecated"]);
+        assert_eq!(*count.lock().unwrap(), 2);
+    }
+}
@@ -3,24 +3,36 @@ pub extern crate tiberius;
 pub mod decode;
 pub mod driver;
 pub mod encode;
+pub mod error;
+pub mod geography;
+mod info;
 
 pub use crate::driver::MssqlDriver;
 pub use crate::driver::MssqlDriver as Driver;
 
-use crate::decode::Decode;
 use crate::encode::Encode;
 use futures_core::future::BoxFuture;
-use futures_core::Stream;
-use rbdc::db::{ConnectOptions, Connection, ExecResult, MetaData, Placeholder, Row};
-use rbdc::Error;
+use futures_core::stream::BoxStream;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use rbdc::db::{statement_timeout, ColumnInfo, ConnectOptions, Connection, ExecResult, MetaData, Placeholder, Row};
+use rbdc::{explain_text, normalize_server_version, redact_password, Error, ErrorKind, ErrorKindExt, QueryLog};
 use rbs::Value;
+use std::fmt::Debug;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tiberius::{Client, Column, ColumnData, Config, Query};
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
 pub struct MssqlConnection {
     inner: Option<Client<Compat<TcpStream>>>,
+    // `SELECT @@VERSION`, queried once
+    server_version: Option<String>,
+    command_timeout: Option<Duration>,
+    // a `fetch_stream` timed out in the middle of a response, the client is unusable
+    // (it can not be dropped there, the stream borrows it)
+    broken: bool,
 }
 
 impl MssqlConnection {
@@ -29,40 +41,439 @@ impl MssqlConnection {
         // let cfg = Config::from_jdbc_string(url).map_err(|e| Error::from(e.to_owned()))?;
         let tcp = TcpStream::connect(cfg.get_addr())
             .await
-            .map_err(|e| Error::from(e.to_string()))?;
+            .map_err(|e| Error::with_kind(e.to_string(), ErrorKind::Connection))?;
         tcp.set_nodelay(true)?;
         let c = Client::connect(cfg.clone(), tcp.compat_write())
             .await
-            .map_err(|e| Error::from(e.to_string()))?;
-        Ok(Self { inner: Some(c) })
+            .map_err(error::from_tiberius)?;
+        Ok(Self {
+            inner: Some(c),
+            server_version: None,
+            command_timeout: None,
+            broken: false,
+        })
+    }
+
+    /// like `establish`, applying the timeouts of `opt`
+    pub async fn establish_with(opt: &MssqlConnectOptions) -> Result<Self, Error> {
+        let mut conn = match opt.timeouts.connect_timeout {
+            None => Self::establish(opt.config()).await?,
+            Some(timeout) => rbdc::rt::timeout(timeout, Self::establish(opt.config()))
+                .await
+                .map_err(|_| {
+                    Error::with_kind(
+                        format!("connect timed out after {:?}", timeout),
+                        ErrorKind::Timeout,
+                    )
+                })??,
+        };
+        conn.command_timeout = opt.timeouts.command_timeout;
+        Ok(conn)
+    }
+
+    /// like `establish_with`, retrying a connect that failed with `ErrorKind::Connection`
+    /// or `ErrorKind::Timeout` (refused or reset TCP connection, login timed out) as
+    /// configured by [`MssqlReconnect`]. returns the error of the last attempt
+    pub async fn connect_with_retry(opt: &MssqlConnectOptions) -> Result<Self, Error> {
+        retry(&opt.reconnect, || Self::establish_with(opt)).await
+    }
+
+    fn client(&mut self) -> Result<&mut Client<Compat<TcpStream>>, Error> {
+        self.inner
+            .as_mut()
+            .filter(|_| !self.broken)
+            .ok_or_else(|| Error::with_kind("MssqlConnection is close".to_string(), ErrorKind::Connection))
+    }
+
+    /// run a command on the client within `command_timeout`.
+    /// a timed out command leaves the TDS stream in the middle of a response,
+    /// so the connection is closed and every later call fails (the pool discards it)
+    async fn command<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: for<'c> FnOnce(&'c mut Client<Compat<TcpStream>>) -> BoxFuture<'c, Result<T, Error>>,
+    {
+        let timeout = self.command_timeout;
+        match with_timeout(timeout, f(self.client()?)).await {
+            Ok(v) => v,
+            Err(timeout) => {
+                self.inner = None;
+                Err(statement_timeout(timeout))
+            }
+        }
+    }
+
+    /// like `get_rows`, but keeps every result set of the batch (such as a stored procedure
+    /// returning several tables) separate instead of flattening them
+    pub fn get_result_sets(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> BoxFuture<'_, Result<ResultSets, Error>> {
+        let sql = MssqlDriver {}.exchange(sql);
+        Box::pin(async move {
+            let mut q = Query::new(sql);
+            for x in params {
+                x.encode(&mut q)?;
+            }
+            let s = self
+                .command(move |client| {
+                    Box::pin(async move {
+                        let v = q
+                            .query(client)
+                            .await
+                            .map_err(error::from_tiberius)?;
+                        v.into_results()
+                            .await
+                            .map_err(error::from_tiberius)
+                    })
+                })
+                .await?;
+            let mut sets = Vec::with_capacity(s.len());
+            for item in s {
+                let mut rows = Vec::with_capacity(item.len());
+                // rows of one result set share their columns
+                let columns = Arc::new(item.first().map(|r| r.columns().to_vec()).unwrap_or_default());
+                for r in item {
+                    rows.push(Box::new(MssqlRow {
+                        columns: columns.clone(),
+                        datas: r.into_iter().collect(),
+                    }) as Box<dyn Row>);
+                }
+                sets.push(rows);
+            }
+            Ok(sets)
+        })
+    }
+
+    /// like `get_rows`, but yields rows as they arrive instead of collecting every result set
+    /// into a `Vec` first, so memory stays bounded for large results.
+    /// `command_timeout` bounds the query up to its first response, the query logger
+    /// is called once the stream ends
+    pub fn fetch_stream(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> BoxStream<'_, Result<Box<dyn Row>, Error>> {
+        let log = QueryLog::start(sql, &params);
+        let sql = MssqlDriver {}.exchange(sql);
+        let rows = stream::once(async move {
+            let mut q = Query::new(sql);
+            for x in params {
+                x.encode(&mut q)?;
+            }
+            // not `self.client()`, `broken` is set below while the client is borrowed
+            let client = self
+                .inner
+                .as_mut()
+                .filter(|_| !self.broken)
+                .ok_or_else(|| Error::with_kind("MssqlConnection is close".to_string(), ErrorKind::Connection))?;
+            let v = match with_timeout(self.command_timeout, q.query(client)).await {
+                Ok(v) => v.map_err(error::from_tiberius)?,
+                Err(timeout) => {
+                    self.broken = true;
+                    return Err(statement_timeout(timeout));
+                }
+            };
+            // rows of one result set share their columns
+            let mut columns: Arc<Vec<Column>> = Arc::new(vec![]);
+            Ok::<_, Error>(v.into_row_stream().map(move |r| {
+                let r = r.map_err(error::from_tiberius)?;
+                let same = columns.len() == r.columns().len()
+                    && columns.iter().zip(r.columns()).all(|(a, b)| {
+                        a.name() == b.name() && a.column_type() == b.column_type()
+                    });
+                if !same {
+                    columns = Arc::new(r.columns().to_vec());
+                }
+                Ok(Box::new(MssqlRow {
+                    columns: columns.clone(),
+                    datas: r.into_iter().collect(),
+                }) as Box<dyn Row>)
+            }))
+        });
+        QueryLog::wrap_stream(log, Box::pin(rows.try_flatten()))
+    }
+
+    async fn set_showplan_text(&mut self, on: bool) -> Result<(), Error> {
+        self.client()?
+            .simple_query(if on {
+                "SET SHOWPLAN_TEXT ON"
+            } else {
+                "SET SHOWPLAN_TEXT OFF"
+            })
+            .await
+            .map_err(error::from_tiberius)?
+            .into_results()
+            .await
+            .map_err(error::from_tiberius)?;
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub struct MssqlConnectOptions(pub Config);
+/// the rows of every result set of a batch, see [`MssqlConnection::get_result_sets`]
+pub type ResultSets = Vec<Vec<Box<dyn Row>>>;
+
+// `Err(timeout)` when `f` did not finish within `timeout`
+async fn with_timeout<T>(timeout: Option<Duration>, f: impl Future<Output = T>) -> Result<T, Duration> {
+    match timeout {
+        None => Ok(f.await),
+        Some(timeout) => rbdc::rt::timeout(timeout, f).await.map_err(|_| timeout),
+    }
+}
+
+async fn retry<T, F, Fut>(reconnect: &MssqlReconnect, mut f: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Err(e)
+                if attempt < reconnect.max_attempts
+                    && matches!(e.kind(), ErrorKind::Connection | ErrorKind::Timeout) =>
+            {
+                let delay = reconnect.delay(attempt);
+                log::warn!("mssql connect failed, retry in {:?}: {}", delay, e);
+                rbdc::rt::sleep(delay).await;
+                attempt += 1;
+            }
+            v => return v,
+        }
+    }
+}
+
+// a JDBC or ADO string, with the server certificate trusted
+fn parse_config(url: &str) -> Result<Config, Error> {
+    let mut config = if url.contains("jdbc") {
+        Config::from_jdbc_string(url).map_err(|e| Error::from(e.to_string()))?
+    } else {
+        Config::from_ado_string(url).map_err(|e| Error::from(e.to_string()))?
+    };
+    config.trust_cert();
+    Ok(config)
+}
+
+// the `key=value` pairs of a JDBC/ADO string, keys lowercase without spaces
+fn url_params(url: &str) -> impl Iterator<Item = (String, &str)> {
+    url.split(';').filter_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        let v = v.trim().trim_start_matches('{').trim_end_matches('}');
+        Some((k.replace(' ', "").to_ascii_lowercase(), v))
+    })
+}
+
+fn parse_secs(k: &str, v: &str) -> Result<Duration, Error> {
+    v.parse::<u64>()
+        .map(Duration::from_secs)
+        .map_err(|_| Error::from(format!("invalid {}: {:?}, expected seconds", k, v)))
+}
+
+/// timeouts tiberius does not handle itself, parsed from the url as whole seconds:
+/// `connectTimeout`/`loginTimeout` (JDBC) or `Connect Timeout`/`Connection Timeout` (ADO),
+/// and `queryTimeout` (JDBC) or `Command Timeout` (ADO)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MssqlTimeouts {
+    /// the TCP connect and the login
+    pub connect_timeout: Option<Duration>,
+    /// every `get_rows`/`exec`, a timed out command closes the connection
+    pub command_timeout: Option<Duration>,
+}
+
+impl MssqlTimeouts {
+    fn from_url(url: &str) -> Result<Self, Error> {
+        let mut timeouts = MssqlTimeouts::default();
+        for (k, v) in url_params(url) {
+            let secs = || parse_secs(&k, v);
+            match k.as_str() {
+                "connecttimeout" | "connectiontimeout" | "logintimeout" => {
+                    timeouts.connect_timeout = Some(secs()?)
+                }
+                "commandtimeout" | "querytimeout" => timeouts.command_timeout = Some(secs()?),
+                _ => {}
+            }
+        }
+        Ok(timeouts)
+    }
+}
+
+/// retry of a connect failing with a connection error or timeout,
+/// waiting `base_delay * 2^attempt` plus a random jitter of up to `base_delay` before each retry.
+/// parsed from the url as `connectRetryCount` and `connectRetryInterval` (whole seconds)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MssqlReconnect {
+    /// the retries after the first failed connect, `0` does not retry
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for MssqlReconnect {
+    fn default() -> Self {
+        Self {
+            max_attempts: 0,
+            base_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+impl MssqlReconnect {
+    fn from_url(url: &str) -> Result<Self, Error> {
+        let mut reconnect = MssqlReconnect::default();
+        for (k, v) in url_params(url) {
+            match k.as_str() {
+                "connectretrycount" => {
+                    reconnect.max_attempts = v.parse().map_err(|_| {
+                        Error::from(format!("invalid {}: {:?}, expected a count", k, v))
+                    })?
+                }
+                "connectretryinterval" => reconnect.base_delay = parse_secs(&k, v)?,
+                _ => {}
+            }
+        }
+        Ok(reconnect)
+    }
+
+    // the wait before retry `attempt` (0 based)
+    fn delay(&self, attempt: u32) -> Duration {
+        use std::hash::{BuildHasher, Hasher};
+        let backoff = self.base_delay.saturating_mul(1 << attempt.min(16));
+        // `RandomState` is randomly seeded, which is enough for a jitter
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let jitter = random % (self.base_delay.as_nanos() as u64 + 1);
+        backoff.saturating_add(Duration::from_nanos(jitter))
+    }
+}
+
+/// the option of [`MssqlDriver`]: a tiberius `Config` with the JDBC/ADO string it was
+/// parsed from, the timeouts and the reconnects. `Config` does not expose its fields,
+/// so the string is kept for [`ConnectOptions::to_url`]
+#[derive(Clone)]
+pub struct MssqlConnectOptions {
+    pub config: Config,
+    /// the url passed to `set_uri`, `None` when created from a `Config`
+    pub url: Option<String>,
+    pub timeouts: MssqlTimeouts,
+    pub reconnect: MssqlReconnect,
+}
+
+impl MssqlConnectOptions {
+    /// options without timeouts or reconnects
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            url: None,
+            timeouts: MssqlTimeouts::default(),
+            reconnect: MssqlReconnect::default(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// bound the TCP connect and the login
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// bound every `get_rows`/`exec`, a timed out command fails with
+    /// `ErrorKind::Timeout` and closes the connection
+    pub fn command_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.command_timeout = Some(timeout);
+        self
+    }
+
+    /// retry a connect failing with a connection error or timeout up to `n` times
+    pub fn max_reconnect_attempts(mut self, n: u32) -> Self {
+        self.reconnect.max_attempts = n;
+        self
+    }
+
+    /// the wait before the first retry, doubled for every further retry
+    pub fn reconnect_base_delay(mut self, delay: Duration) -> Self {
+        self.reconnect.base_delay = delay;
+        self
+    }
+}
+
+impl Debug for MssqlConnectOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MssqlConnectOptions")
+            .field("config", &self.config)
+            .field("url", &self.url.as_deref().map(redact_password))
+            .field("timeouts", &self.timeouts)
+            .field("reconnect", &self.reconnect)
+            .finish()
+    }
+}
+
+impl From<Config> for MssqlConnectOptions {
+    fn from(config: Config) -> Self {
+        Self::new(config)
+    }
+}
 
 impl ConnectOptions for MssqlConnectOptions {
-    fn connect(&self) -> BoxFuture<Result<Box<dyn Connection>, Error>> {
+    fn connect(&self) -> BoxFuture<'_, Result<Box<dyn Connection>, Error>> {
         Box::pin(async move {
-            let v = MssqlConnection::establish(&self.0)
-                .await
-                .map_err(|e| Error::from(e.to_string()))?;
+            let v = MssqlConnection::connect_with_retry(self).await?;
             Ok(Box::new(v) as Box<dyn Connection>)
         })
     }
 
+    fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+        Ok(Box::new(self.clone()))
+    }
+
     fn set_uri(&mut self, url: &str) -> Result<(), Error> {
-        if url.contains("jdbc"){
-            let mut config = Config::from_jdbc_string(url).map_err(|e| Error::from(e.to_string()))?;
-            config.trust_cert();
-            *self = MssqlConnectOptions(config);   
-        }else{
-            let mut config = Config::from_ado_string(url).map_err(|e| Error::from(e.to_string()))?;
-            config.trust_cert();
-            *self = MssqlConnectOptions(config);
-        }
+        *self = MssqlConnectOptions {
+            config: parse_config(url)?,
+            url: Some(url.to_string()),
+            timeouts: MssqlTimeouts::from_url(url)?,
+            reconnect: MssqlReconnect::from_url(url)?,
+        };
         Ok(())
     }
+
+    fn to_url(&self) -> Result<String, Error> {
+        self.url.clone().ok_or_else(|| {
+            Error::from(
+                "MssqlConnectOptions was not created from an url, \
+                 tiberius Config can not be turned back into one",
+            )
+        })
+    }
+
+    fn redact_uri(&self) -> String {
+        match &self.url {
+            Some(v) => redact_password(v),
+            // Config hides the password in its Debug
+            None => format!("{:?}", self.config),
+        }
+    }
+
+    /// tiberius `Config` can not be compared field by field,
+    /// so an `other` created from an url replaces `self` as a whole
+    fn merge(&mut self, other: &dyn ConnectOptions) {
+        if let Some(other) = other.downcast_ref::<MssqlConnectOptions>() {
+            if other.url.is_some() {
+                self.config = other.config.clone();
+                self.url = other.url.clone();
+            }
+            if other.timeouts.connect_timeout.is_some() {
+                self.timeouts.connect_timeout = other.timeouts.connect_timeout;
+            }
+            if other.timeouts.command_timeout.is_some() {
+                self.timeouts.command_timeout = other.timeouts.command_timeout;
+            }
+            if other.reconnect != MssqlReconnect::default() {
+                self.reconnect = other.reconnect.clone();
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -86,6 +497,17 @@ impl MetaData for MssqlMetaData {
     fn column_type(&self, i: usize) -> String {
         format!("{:?}", self.0[i].column_type())
     }
+
+    fn columns(&self) -> Vec<ColumnInfo> {
+        self.0
+            .iter()
+            .map(|c| ColumnInfo {
+                name: c.name().to_string(),
+                column_type: format!("{:?}", c.column_type()),
+                nullable: None,
+            })
+            .collect()
+    }
 }
 
 impl Row for MssqlRow {
@@ -94,7 +516,7 @@ impl Row for MssqlRow {
     }
 
     fn get(&mut self, i: usize) -> Result<Value, Error> {
-        Value::decode(&self.datas[i])
+        decode::decode_column(&self.columns[i], &self.datas[i])
     }
 }
 
@@ -104,80 +526,95 @@ impl Connection for MssqlConnection {
         sql: &str,
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Box<dyn Row>>, Error>> {
+        let log = QueryLog::start(sql, &params);
+        let sets = self.get_result_sets(sql, params);
+        let f = Box::pin(async move { Ok(sets.await?.into_iter().flatten().collect()) });
+        QueryLog::wrap(log, f, |v: &Vec<Box<dyn Row>>| v.len() as u64)
+    }
+
+    fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = MssqlDriver {}.exchange(sql);
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let mut q = Query::new(sql);
             for x in params {
                 x.encode(&mut q)?;
             }
-            let v = q
-                .query(
-                    self.inner
-                        .as_mut()
-                        .ok_or_else(|| Error::from("MssqlConnection is close"))?,
-                )
-                .await
-                .map_err(|e| Error::from(e.to_string()))?;
-            let mut results = Vec::with_capacity(v.size_hint().0);
-            let s = v
-                .into_results()
-                .await
-                .map_err(|e| Error::from(e.to_string()))?;
-            for item in s {
-                for r in item {
-                    let mut columns = Vec::with_capacity(r.columns().len());
-                    let mut row = MssqlRow {
-                        columns: Arc::new(vec![]),
-                        datas: Vec::with_capacity(r.columns().len()),
-                    };
-                    for x in r.columns() {
-                        columns.push(x.clone());
-                    }
-                    row.columns = Arc::new(columns);
-                    for x in r {
-                        row.datas.push(x);
-                    }
-                    results.push(Box::new(row) as Box<dyn Row>);
-                }
+            let (v, messages) = info::collect(self.command(move |client| {
+                Box::pin(async move {
+                    q.execute(client)
+                        .await
+                        .map_err(error::from_tiberius)
+                })
+            }))
+            .await;
+            let v = v?;
+            let mut rows_affected = 0;
+            for x in v.rows_affected() {
+                rows_affected += x.clone();
+            }
+            Ok(ExecResult::new(rows_affected, Value::Null).with_messages(messages))
+        });
+        QueryLog::wrap(log, f, |v| v.rows_affected)
+    }
+
+    fn close(&mut self) -> BoxFuture<Result<(), Error>> {
+        Box::pin(async move {
+            //inner must be Option,so we can take owner and call close(self) method.
+            if let Some(v) = self.inner.take() {
+                v.close().await.map_err(error::from_tiberius)?;
             }
-            Ok(results)
+            Ok(())
         })
     }
 
-    fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
-        let sql = MssqlDriver {}.exchange(sql);
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
         Box::pin(async move {
-            let mut q = Query::new(sql);
-            for x in params {
-                x.encode(&mut q)?;
+            if self.server_version.is_none() {
+                let mut rows = self.get_rows("SELECT @@VERSION", vec![]).await?;
+                let version = match rows.first_mut() {
+                    Some(row) => row.get(0)?.into_string(),
+                    None => None,
+                };
+                self.server_version = version.map(|v| normalize_server_version(&v));
             }
-            let v = q
-                .execute(
-                    self.inner
-                        .as_mut()
-                        .ok_or_else(|| Error::from("MssqlConnection is close"))?,
+            Ok(self.server_version.clone())
+        })
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self
+                .get_rows(
+                    "SELECT 1 FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_NAME = ?",
+                    params,
                 )
-                .await
-                .map_err(|e| Error::from(e.to_string()))?;
-            Ok(ExecResult {
-                rows_affected: {
-                    let mut rows_affected = 0;
-                    for x in v.rows_affected() {
-                        rows_affected += x.clone();
-                    }
-                    rows_affected
-                },
-                last_insert_id: Value::Null,
-            })
+                .await?;
+            Ok(!rows.is_empty())
         })
     }
 
-    fn close(&mut self) -> BoxFuture<Result<(), Error>> {
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        let sql = sql.to_string();
         Box::pin(async move {
-            //inner must be Option,so we can take owner and call close(self) method.
-            if let Some(v) = self.inner.take() {
-                v.close().await.map_err(|e| Error::from(e.to_string()))?;
-            }
+            // SET SHOWPLAN_TEXT must be the only statement of its batch
+            self.set_showplan_text(true).await?;
+            let rows = self.get_rows(&sql, params).await;
+            self.set_showplan_text(false).await?;
+            explain_text(rows?)
+        })
+    }
+
+    /// rolls back an open transaction.
+    /// `sp_reset_connection` is only reachable through the TDS reset-connection packet flag,
+    /// which tiberius does not expose
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.client()?
+                .simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRAN")
+                .await
+                .map_err(error::from_tiberius)?;
             Ok(())
         })
     }
@@ -185,48 +622,40 @@ impl Connection for MssqlConnection {
     fn ping(&mut self) -> BoxFuture<Result<(), rbdc::Error>> {
         //TODO While 'select 1' can temporarily solve the problem of checking that the connection is valid, it looks ugly.Better replace it with something better way
         Box::pin(async move {
-            self.inner
-                .as_mut()
-                .ok_or_else(|| Error::from("MssqlConnection is close"))?
+            self.client()?
                 .query("select 1", &[])
                 .await
-                .map_err(|e| Error::from(e.to_string()))?;
+                .map_err(error::from_tiberius)?;
             Ok(())
         })
     }
 
     fn begin(&mut self) -> BoxFuture<Result<(), Error>> {
         Box::pin(async move {
-            self.inner
-                .as_mut()
-                .ok_or_else(|| Error::from("MssqlConnection is close"))?
+            self.client()?
                 .simple_query("begin tran")
                 .await
-                .map_err(|e| Error::from(e.to_string()))?;
+                .map_err(error::from_tiberius)?;
             Ok(())
         })
     }
 
     fn commit(&mut self) -> BoxFuture<Result<(), Error>> {
         Box::pin(async move {
-            self.inner
-                .as_mut()
-                .ok_or_else(|| Error::from("MssqlConnection is close"))?
+            self.client()?
                 .simple_query("commit")
                 .await
-                .map_err(|e| Error::from(e.to_string()))?;
+                .map_err(error::from_tiberius)?;
             Ok(())
         })
     }
 
     fn rollback(&mut self) -> BoxFuture<Result<(), Error>> {
         Box::pin(async move {
-            self.inner
-                .as_mut()
-                .ok_or_else(|| Error::from("MssqlConnection is close"))?
+            self.client()?
                 .simple_query("rollback")
                 .await
-                .map_err(|e| Error::from(e.to_string()))?;
+                .map_err(error::from_tiberius)?;
             Ok(())
         })
     }
@@ -234,6 +663,85 @@ impl Connection for MssqlConnection {
 
 #[cfg(test)]
 mod test {
+    use crate::{retry, with_timeout, MssqlReconnect};
+    use rbdc::{ErrorKind, ErrorKindExt};
+    use rbdc::Error;
+    use std::time::{Duration, Instant};
+
     #[test]
     fn test_datetime() {}
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        assert_eq!(with_timeout(None, async { 1 }).await, Ok(1));
+        let timeout = Duration::from_millis(10);
+        assert_eq!(with_timeout(Some(timeout), async { 1 }).await, Ok(1));
+        let slow = tokio::time::sleep(Duration::from_secs(5));
+        assert_eq!(with_timeout(Some(timeout), slow).await, Err(timeout));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let reconnect = MssqlReconnect {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+        };
+        // fails twice, then connects
+        let mut calls = 0;
+        let start = Instant::now();
+        let v = retry(&reconnect, || {
+            calls += 1;
+            let n = calls;
+            async move {
+                match n {
+                    1 | 2 => Err(Error::from("Connection refused (os error 111)")),
+                    n => Ok(n),
+                }
+            }
+        })
+        .await;
+        assert_eq!(v.unwrap(), 3);
+        // 10ms + 20ms of backoff at least
+        assert!(start.elapsed() >= Duration::from_millis(30));
+
+        // the error of the last attempt
+        let mut calls = 0;
+        let e = retry(&reconnect, || {
+            calls += 1;
+            let n = calls;
+            async move {
+                Err::<(), _>(Error::with_kind(
+                    format!("connection reset {}", n),
+                    ErrorKind::Connection,
+                ))
+            }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(e.to_string(), "connection reset 4");
+
+        // a failed login is not retried
+        let mut calls = 0;
+        let e = retry(&reconnect, || {
+            calls += 1;
+            async { Err::<(), _>(Error::from("Login failed for user 'sa'")) }
+        })
+        .await;
+        assert!(e.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let reconnect = MssqlReconnect {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+        };
+        for (attempt, backoff) in [(0, 100), (1, 200), (3, 800)] {
+            let delay = reconnect.delay(attempt);
+            assert!(delay >= Duration::from_millis(backoff), "{:?}", delay);
+            assert!(delay <= Duration::from_millis(backoff + 100), "{:?}", delay);
+        }
+        assert!(reconnect.delay(u32::MAX) > Duration::from_secs(1));
+    }
 }
@@ -0,0 +1,113 @@
+use crate::connection::MySqlConnection;
+use crate::query::MysqlQuery;
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_util::TryStreamExt;
+use rbdc::db::ExecResult;
+use rbdc::{Error, QueryLog};
+
+impl MySqlConnection {
+    /// run several `;` separated statements in one round trip, such as a migration script,
+    /// returning one [`ExecResult`] per statement in order.
+    ///
+    /// needs the `multi-statements` option (on by default), the batch stops at the first
+    /// failing statement with its error
+    pub fn exec_batch(&mut self, sql: &str) -> BoxFuture<'_, Result<Vec<ExecResult>, Error>> {
+        let log = QueryLog::start(sql, &[]);
+        let sql = sql.to_owned();
+        let f = Box::pin(async move {
+            if !self.option.multi_statements {
+                return Err(Error::from(
+                    "exec_batch needs the multi-statements option enabled",
+                ));
+            }
+            let mut results = vec![];
+            let mut many = self.fetch_many(MysqlQuery {
+                statement: Either::Left(sql),
+                arguments: vec![],
+                persistent: false,
+            });
+            // every statement ends with an OK (or EOF after its rows)
+            while let Some(step) = many.try_next().await? {
+                if let Either::Left(v) = step {
+                    results.push(
+                        ExecResult::new(v.rows_affected, v.last_insert_id.into())
+                            .with_warnings(v.warnings),
+                    );
+                }
+            }
+            Ok(results)
+        });
+        QueryLog::wrap(log, f, |v| v.iter().map(|v| v.rows_affected).sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::MySqlConnection;
+    use crate::options::MySqlConnectOptions;
+    use rbs::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // OK packet: header, 0x00, affected rows, last insert id, status, warnings
+    fn ok_packet(sequence_id: u8, affected_rows: u8, last_insert_id: u8, more: bool) -> Vec<u8> {
+        let status: u16 = if more { 0x0008 } else { 0x0002 };
+        let mut payload = vec![0x00, affected_rows, last_insert_id];
+        payload.extend(status.to_le_bytes());
+        payload.extend(0u16.to_le_bytes());
+        let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+        packet.push(sequence_id);
+        packet.extend(payload);
+        packet
+    }
+
+    #[tokio::test]
+    async fn test_exec_batch() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sql = "CREATE TABLE t (id INT AUTO_INCREMENT PRIMARY KEY, a INT); \
+                   INSERT INTO t (a) VALUES (1); \
+                   INSERT INTO t (a) VALUES (2); \
+                   UPDATE t SET a = 3";
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut header = [0u8; 4];
+            socket.read_exact(&mut header).await.unwrap();
+            let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            let mut payload = vec![0u8; len];
+            socket.read_exact(&mut payload).await.unwrap();
+            assert_eq!(payload[0], 0x03); // COM_QUERY
+            let mut reply = vec![];
+            reply.extend(ok_packet(1, 0, 0, true));
+            reply.extend(ok_packet(2, 1, 1, true));
+            reply.extend(ok_packet(3, 1, 2, true));
+            reply.extend(ok_packet(4, 2, 0, false));
+            socket.write_all(&reply).await.unwrap();
+            socket.flush().await.unwrap();
+        });
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let results = conn.exec_batch(sql).await.unwrap();
+        assert_eq!(results.len(), 4);
+        let affected: Vec<u64> = results.iter().map(|v| v.rows_affected).collect();
+        assert_eq!(affected, vec![0, 1, 1, 2]);
+        assert_eq!(results[1].last_insert_id, Value::U64(1));
+        assert_eq!(results[2].last_insert_id, Value::U64(2));
+    }
+
+    #[tokio::test]
+    async fn test_exec_batch_disabled() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _ = listener.accept().await;
+        });
+        let options = MySqlConnectOptions::new()
+            .host("127.0.0.1")
+            .port(port)
+            .multi_statements(false);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        assert!(conn.exec_batch("SELECT 1; SELECT 2").await.is_err());
+    }
+}
@@ -137,6 +137,10 @@ impl MySqlConnection {
                 }
             }
         }
+
+        // the packets after the handshake are compressed
+        stream.compress = stream.capabilities.contains(Capabilities::COMPRESS);
+
         Ok(Self {
             stream: DropBox {
                 inner: Some(stream),
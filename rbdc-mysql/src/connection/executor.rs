@@ -143,6 +143,7 @@ impl MySqlConnection {
                     let done = MySqlQueryResult {
                         rows_affected,
                         last_insert_id: ok.last_insert_id,
+                        warnings: ok.warnings as u64,
                     };
 
                     r#yield!(Either::Left(done));
@@ -181,6 +182,7 @@ impl MySqlConnection {
                         r#yield!(Either::Left(MySqlQueryResult {
                             rows_affected: 0,
                             last_insert_id: 0,
+                            warnings: eof.warnings as u64,
                         }));
 
                         if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
@@ -0,0 +1,152 @@
+use crate::connection::MySqlConnection;
+use crate::protocol::text::Query;
+use rbdc::rt::{AsyncRead, AsyncReadExt};
+use rbdc::{err_protocol, Error};
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.30/page_protocol_com_query_response_local_infile_request.html
+
+/// max payload of one data packet
+const LOCAL_INFILE_CHUNK: usize = 16 * 1024;
+
+impl MySqlConnection {
+    /// run `LOAD DATA LOCAL INFILE` and feed the file content from `reader`
+    /// instead of a file on disk, return rows_affected.
+    ///
+    /// the file name in the sql is ignored, any name works.
+    /// when `reader` fails, the data read so far is still loaded (the protocol can not abort
+    /// a transfer) and the reader error is returned, run it in a transaction to roll it back.
+    /// both sides must allow local infile:
+    /// the client with [`MySqlConnectOptions::local_infile`](crate::options::MySqlConnectOptions::local_infile)
+    /// and the server with `SET GLOBAL local_infile = ON`.
+    ///
+    /// ```rust,ignore
+    /// let csv = "1,tom\n2,jerry\n";
+    /// let rows = conn
+    ///     .load_data(
+    ///         "LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE user FIELDS TERMINATED BY ','",
+    ///         csv.as_bytes(),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn load_data<R>(&mut self, sql: &str, mut reader: R) -> Result<u64, Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        self.stream.wait_until_ready().await?;
+        self.stream.send_packet(Query(sql)).await?;
+
+        // `recv_packet` already turns an ERR packet into an error
+        let packet = self.stream.recv_packet().await?;
+        match packet[0] {
+            // LOCAL INFILE Request
+            0xfb => {}
+            // the server did not ask for data
+            0x00 => return Ok(packet.ok()?.affected_rows),
+            id => {
+                return Err(err_protocol!(
+                    "expected LOCAL INFILE request but found 0x{:02x}",
+                    id
+                ));
+            }
+        }
+
+        let mut buf = vec![0u8; LOCAL_INFILE_CHUNK];
+        let read = loop {
+            let n = match reader.read(&mut buf).await {
+                Ok(0) => break Ok(()),
+                Ok(n) => n,
+                Err(e) => break Err(e),
+            };
+            self.stream.write_packet(&buf[..n]);
+            self.stream.flush().await?;
+        };
+        // an empty packet marks the end of the data, it is sent even when the reader failed
+        // so the server replies and the connection stays usable
+        self.stream.write_packet(&[][..]);
+        self.stream.flush().await?;
+
+        let ok = self.stream.recv_ok().await;
+        read?;
+        Ok(ok?.affected_rows)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::MySqlConnection;
+    use crate::options::MySqlConnectOptions;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn packet(sequence_id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+        packet.push(sequence_id);
+        packet.extend(payload);
+        packet
+    }
+
+    async fn read_packet(socket: &mut TcpStream) -> Vec<u8> {
+        let mut header = [0u8; 4];
+        socket.read_exact(&mut header).await.unwrap();
+        let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+        let mut payload = vec![0u8; len];
+        socket.read_exact(&mut payload).await.unwrap();
+        payload
+    }
+
+    // a reader that always fails, chained after some data
+    struct Failing;
+
+    impl AsyncRead for Failing {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Err(std::io::Error::other("disk gone")))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_data_reader_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            // LOAD DATA: ask for the file, read it up to the empty packet, load 1 row
+            read_packet(&mut socket).await;
+            socket.write_all(&packet(1, b"\xfbdata.csv")).await.unwrap();
+            let mut data = vec![];
+            loop {
+                let payload = read_packet(&mut socket).await;
+                if payload.is_empty() {
+                    break;
+                }
+                data.extend(payload);
+            }
+            socket
+                .write_all(&packet(4, &[0x00, 1, 0, 0x02, 0, 0, 0]))
+                .await
+                .unwrap();
+            // the next command on the same connection is rejected by the server
+            read_packet(&mut socket).await;
+            let err = b"\xff\x15\x04#42000Access denied";
+            socket.write_all(&packet(1, err)).await.unwrap();
+            data
+        });
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let sql = "LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE t";
+        let e = conn
+            .load_data(sql, (&b"1,a\n"[..]).chain(Failing))
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("disk gone"), "{}", e);
+        // the connection is still in sync: the reply to the next command is read
+        let e = conn.load_data(sql, &b""[..]).await.unwrap_err();
+        assert!(e.to_string().contains("Access denied"), "{}", e);
+        assert_eq!(server.await.unwrap(), b"1,a\n");
+    }
+}
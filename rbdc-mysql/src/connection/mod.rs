@@ -1,5 +1,5 @@
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
 use crate::stmt::MySqlStatementMetadata;
 use either::Either;
 use futures_core::future::BoxFuture;
@@ -7,7 +7,7 @@ use futures_core::stream::BoxStream;
 use futures_util::{FutureExt, StreamExt, TryStreamExt};
 use rbdc::common::StatementCache;
 use rbdc::db::{Connection, ExecResult, Row};
-use rbdc::Error;
+use rbdc::{Error, QueryLog};
 use rbs::Value;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -15,8 +15,11 @@ use std::sync::Arc;
 
 
 mod auth;
+mod batch;
 mod establish;
 mod executor;
+mod local_infile;
+mod rows;
 mod stream;
 mod tls;
 
@@ -24,7 +27,7 @@ use crate::query::MysqlQuery;
 use crate::query_result::MySqlQueryResult;
 use crate::row::MySqlRow;
 pub(crate) use stream::MySqlStream;
-use crate::options::MySqlConnectOptions;
+use crate::options::{format_time_zone, MySqlConnectOptions};
 
 const MAX_PACKET_SIZE: u32 = 1024;
 
@@ -82,6 +85,46 @@ impl MySqlConnection {
         })
     }
 
+    // `SHOW WARNINGS` of the last statement, each row as `Level Code: Message`
+    async fn show_warnings(&mut self) -> Result<Vec<String>, Error> {
+        fn text(v: Value) -> String {
+            match v {
+                Value::String(s) => s,
+                v => v.to_string(),
+            }
+        }
+        let rows = self.get_rows("SHOW WARNINGS", vec![]).await?;
+        let mut messages = Vec::with_capacity(rows.len());
+        for mut row in rows {
+            messages.push(format!(
+                "{} {}: {}",
+                text(row.get(0)?),
+                text(row.get(1)?),
+                text(row.get(2)?)
+            ));
+        }
+        Ok(messages)
+    }
+
+    fn do_reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+            self.stream.send_packet(ResetConnection).await?;
+            self.stream.recv_ok().await?;
+            // the server deallocated every prepared statement
+            self.cache_statement.clear();
+            // and reset the session variables set on connect
+            if let Some(time_zone) = self.option.time_zone {
+                self.exec(
+                    &format!("SET time_zone = '{}'", format_time_zone(time_zone)),
+                    vec![],
+                )
+                .await?;
+            }
+            Ok(())
+        })
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
@@ -111,14 +154,30 @@ impl MySqlConnection {
     }
 }
 
+#[cfg(test)]
+impl MySqlConnection {
+    /// a connection that skips the handshake, for tests against a fake server
+    pub(crate) async fn connect_raw(options: MySqlConnectOptions) -> Result<Self, Error> {
+        let stream = MySqlStream::connect(&options).await?;
+        Ok(MySqlConnection {
+            stream: DropBox {
+                inner: Some(stream),
+            },
+            cache_statement: StatementCache::new(options.statement_cache_capacity),
+            option: Arc::new(options),
+        })
+    }
+}
+
 impl Connection for MySqlConnection {
     fn get_rows(
         &mut self,
         sql: &str,
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Box<dyn Row>>, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = sql.to_owned();
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
                     self.fetch_many(MysqlQuery {
@@ -150,12 +209,14 @@ impl Connection for MySqlConnection {
                 data.push(Box::new(x));
             }
             Ok(data)
-        })
+        });
+        QueryLog::wrap(log, f, |v| v.len() as u64)
     }
 
     fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = sql.to_owned();
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
                     self.fetch_many(MysqlQuery {
@@ -181,11 +242,16 @@ impl Connection for MySqlConnection {
                 })
                 .boxed();
             let v: MySqlQueryResult = v.try_collect().boxed().await?;
-            return Ok(ExecResult {
-                rows_affected: v.rows_affected,
-                last_insert_id: v.last_insert_id.into(),
-            });
-        })
+            let messages = if v.warnings > 0 {
+                self.show_warnings().await?
+            } else {
+                vec![]
+            };
+            Ok(ExecResult::new(v.rows_affected, v.last_insert_id.into())
+                .with_warnings(v.warnings)
+                .with_messages(messages))
+        });
+        QueryLog::wrap(log, f, |v| v.rows_affected)
     }
 
     fn close(&mut self) -> BoxFuture<Result<(), Error>> {
@@ -193,8 +259,164 @@ impl Connection for MySqlConnection {
         Box::pin(async { c.await })
     }
 
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        // reported by the server in the initial handshake
+        let (major, minor, patch) = self.stream.server_version;
+        Box::pin(async move { Ok(Some(format!("{}.{}.{}", major, minor, patch))) })
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self
+                .get_rows(
+                    "SELECT 1 FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?",
+                    params,
+                )
+                .await?;
+            Ok(!rows.is_empty())
+        })
+    }
+
+    /// `COM_RESET_CONNECTION`: rolls back the transaction, drops temp tables,
+    /// deallocates prepared statements and resets session variables (MySQL 5.7.3+, MariaDB 10.2.4+)
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.do_reset()
+    }
+
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         let c = self.do_ping();
         Box::pin(async move { c.await })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::connection::MySqlConnection;
+    use crate::options::MySqlConnectOptions;
+    use rbdc::db::Connection;
+    use rbs::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // answers every COM_QUERY with the next (affected rows, last insert id) OK packet
+    async fn serve(replies: Vec<(u8, u8)>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            for (affected_rows, last_insert_id) in replies {
+                let mut header = [0u8; 4];
+                socket.read_exact(&mut header).await.unwrap();
+                let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+                socket.read_exact(&mut vec![0u8; len]).await.unwrap();
+                let payload = [0x00, affected_rows, last_insert_id, 0x02, 0, 0, 0];
+                let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+                packet.push(1);
+                packet.extend(payload);
+                socket.write_all(&packet).await.unwrap();
+            }
+            let _ = socket.read(&mut [0u8; 1]).await;
+        });
+        port
+    }
+
+    fn packet(buf: &mut Vec<u8>, sequence_id: &mut u8, payload: &[u8]) {
+        buf.extend(&(payload.len() as u32).to_le_bytes()[..3]);
+        buf.push(*sequence_id);
+        buf.extend(payload);
+        *sequence_id = sequence_id.wrapping_add(1);
+    }
+
+    fn lenenc_str(buf: &mut Vec<u8>, s: &str) {
+        buf.push(s.len() as u8);
+        buf.extend(s.as_bytes());
+    }
+
+    // answers the first query with an OK packet holding one warning
+    // and the following `SHOW WARNINGS` with a single row
+    async fn serve_warning() -> u16 {
+        async fn read_query(socket: &mut tokio::net::TcpStream) -> String {
+            let mut header = [0u8; 4];
+            socket.read_exact(&mut header).await.unwrap();
+            let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            let mut body = vec![0u8; len];
+            socket.read_exact(&mut body).await.unwrap();
+            String::from_utf8_lossy(&body[1..]).to_string()
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            read_query(&mut socket).await;
+            let mut buf = vec![];
+            packet(&mut buf, &mut 1, &[0x00, 1, 0, 0x02, 0, 1, 0]);
+            socket.write_all(&buf).await.unwrap();
+            assert_eq!(read_query(&mut socket).await, "SHOW WARNINGS");
+            let mut seq = 1;
+            let mut buf = vec![];
+            packet(&mut buf, &mut seq, &[3]); // column count
+            for (name, ty, charset, flags) in [
+                ("Level", 0xfd, 33u16, 0u16),
+                ("Code", 0x03, 63, 0x20),
+                ("Message", 0xfd, 33, 0),
+            ] {
+                let mut column = vec![];
+                for s in ["def", "", "", "", name, name] {
+                    lenenc_str(&mut column, s);
+                }
+                column.push(0x0c);
+                column.extend(charset.to_le_bytes());
+                column.extend(512u32.to_le_bytes()); // max size
+                column.push(ty);
+                column.extend(flags.to_le_bytes());
+                column.push(0); // decimals
+                column.extend([0, 0]);
+                packet(&mut buf, &mut seq, &column);
+            }
+            let mut row = vec![];
+            lenenc_str(&mut row, "Warning");
+            lenenc_str(&mut row, "1265");
+            lenenc_str(&mut row, "Data truncated for column 'a' at row 1");
+            packet(&mut buf, &mut seq, &row);
+            packet(&mut buf, &mut seq, &[0xfe, 0, 0, 0x02, 0, 0, 0]);
+            socket.write_all(&buf).await.unwrap();
+            let _ = socket.read(&mut [0u8; 1]).await;
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_exec_warnings() {
+        let port = serve_warning().await;
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let v = conn
+            .exec("INSERT INTO t (a) VALUES ('abc')", vec![])
+            .await
+            .unwrap();
+        assert_eq!(v.warnings, 1);
+        assert_eq!(
+            v.messages,
+            vec!["Warning 1265: Data truncated for column 'a' at row 1".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_last_insert_id() {
+        let port = serve(vec![(1, 1), (1, 2), (3, 3)]).await;
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let sql = "INSERT INTO t (a) VALUES (1)";
+        let v = conn.exec(sql, vec![]).await.unwrap();
+        assert_eq!((v.rows_affected, v.last_insert_id), (1, Value::U64(1)));
+        let v = conn.exec(sql, vec![]).await.unwrap();
+        assert_eq!((v.rows_affected, v.last_insert_id), (1, Value::U64(2)));
+        // a multi-row insert reports the id of its first row
+        let v = conn
+            .exec("INSERT INTO t (a) VALUES (1), (2), (3)", vec![])
+            .await
+            .unwrap();
+        assert_eq!((v.rows_affected, v.last_insert_id), (3, Value::U64(3)));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::connection::MySqlConnection;
+use crate::query::MysqlQuery;
+use either::Either;
+use futures_core::stream::BoxStream;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use rbdc::db::{Connection, Row};
+use rbdc::Error;
+use rbs::Value;
+
+impl MySqlConnection {
+    /// like `get_rows`, but yields rows one at a time.
+    ///
+    /// with the `streaming` option each row is decoded from its packet as it arrives,
+    /// so memory stays bounded for large scans, without it every row is read first
+    /// (see [`MySqlConnectOptions::streaming`](crate::options::MySqlConnectOptions::streaming))
+    pub fn get_rows_stream<'a>(
+        &'a mut self,
+        sql: &'a str,
+        params: Vec<Value>,
+    ) -> BoxStream<'a, Result<Box<dyn Row>, Error>> {
+        if !self.option.streaming {
+            let rows = stream::once(self.get_rows(sql, params))
+                .map_ok(|rows| stream::iter(rows.into_iter().map(Ok)));
+            return Box::pin(rows.try_flatten());
+        }
+        let rows = stream::once(async move {
+            let many = if params.is_empty() {
+                self.fetch_many(MysqlQuery {
+                    statement: Either::Left(sql.to_owned()),
+                    arguments: params,
+                    persistent: false,
+                })
+            } else {
+                let stmt = self.prepare_with(sql, &[]).await?;
+                self.fetch_many(MysqlQuery {
+                    statement: Either::Right(stmt),
+                    arguments: params,
+                    persistent: true,
+                })
+            };
+            Ok::<_, Error>(many.try_filter_map(|step| async move {
+                Ok(match step {
+                    Either::Left(_) => None,
+                    Either::Right(row) => Some(Box::new(row) as Box<dyn Row>),
+                })
+            }))
+        });
+        rows.try_flatten().boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::MySqlConnection;
+    use crate::options::MySqlConnectOptions;
+    use futures_util::TryStreamExt;
+    use rbs::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn packet(buf: &mut Vec<u8>, sequence_id: &mut u8, payload: &[u8]) {
+        buf.extend(&(payload.len() as u32).to_le_bytes()[..3]);
+        buf.push(*sequence_id);
+        buf.extend(payload);
+        *sequence_id = sequence_id.wrapping_add(1);
+    }
+
+    fn lenenc_str(buf: &mut Vec<u8>, s: &str) {
+        buf.push(s.len() as u8);
+        buf.extend(s.as_bytes());
+    }
+
+    // answers the first query with a single BIGINT column `id` holding 1..=rows
+    async fn serve(rows: usize) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut header = [0u8; 4];
+            socket.read_exact(&mut header).await.unwrap();
+            let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            socket.read_exact(&mut vec![0u8; len]).await.unwrap();
+            let mut seq = 1;
+            let mut buf = vec![];
+            packet(&mut buf, &mut seq, &[1]); // column count
+            let mut column = vec![];
+            for s in ["def", "test", "t", "t", "id", "id"] {
+                lenenc_str(&mut column, s);
+            }
+            column.push(0x0c);
+            column.extend(63u16.to_le_bytes()); // binary charset
+            column.extend(20u32.to_le_bytes()); // max size
+            column.push(0x08); // LONGLONG
+            column.extend(0u16.to_le_bytes()); // flags
+            column.push(0); // decimals
+            column.extend([0, 0]);
+            packet(&mut buf, &mut seq, &column);
+            for i in 1..=rows {
+                let mut row = vec![];
+                lenenc_str(&mut row, &i.to_string());
+                packet(&mut buf, &mut seq, &row);
+                if buf.len() > 64 * 1024 {
+                    socket.write_all(&buf).await.unwrap();
+                    buf.clear();
+                }
+            }
+            // OK packet with the 0xfe header ends the rows
+            packet(&mut buf, &mut seq, &[0xfe, 0, 0, 0x02, 0, 0, 0]);
+            socket.write_all(&buf).await.unwrap();
+            socket.flush().await.unwrap();
+            // keep the socket open until the client is done
+            let _ = socket.read(&mut [0u8; 1]).await;
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_get_rows_stream() {
+        let port = serve(100_000).await;
+        let options = MySqlConnectOptions::new()
+            .host("127.0.0.1")
+            .port(port)
+            .streaming(true);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let mut rows = conn.get_rows_stream("SELECT id FROM t", vec![]);
+        let mut count = 0;
+        while let Some(mut row) = rows.try_next().await.unwrap() {
+            count += 1;
+            assert_eq!(row.get(0).unwrap(), Value::I64(count));
+        }
+        assert_eq!(count, 100_000);
+    }
+
+    #[tokio::test]
+    async fn test_get_rows_stream_buffered() {
+        let port = serve(10).await;
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut conn = MySqlConnection::connect_raw(options).await.unwrap();
+        let rows: Vec<_> = conn
+            .get_rows_stream("SELECT id FROM t", vec![])
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 10);
+    }
+}
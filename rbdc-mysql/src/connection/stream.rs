@@ -1,10 +1,11 @@
 use std::collections::VecDeque;
 use std::ops::{Deref, DerefMut};
 
-use bytes::{Buf, Bytes};
+use bytes::{Buf, Bytes, BytesMut};
 
 use crate::collation::{CharSet, Collation};
 use crate::error::MySqlDatabaseError;
+use crate::io::compress::{compress_packets, decompress_payload};
 use crate::io::MySqlBufExt;
 use crate::options::MySqlConnectOptions;
 use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
@@ -21,6 +22,11 @@ pub struct MySqlStream {
     pub(crate) waiting: VecDeque<Waiting>,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
+    // the compressed protocol is used once the handshake is done
+    pub(crate) compress: bool,
+    compressed_sequence_id: u8,
+    // plain packet bytes read from compressed packets, not yet consumed
+    decompressed: BytesMut,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -54,16 +60,27 @@ impl MySqlStream {
             | Capabilities::TRANSACTIONS
             | Capabilities::SECURE_CONNECTION
             | Capabilities::PLUGIN_AUTH_LENENC_DATA
-            | Capabilities::MULTI_STATEMENTS
             | Capabilities::MULTI_RESULTS
             | Capabilities::PLUGIN_AUTH
             | Capabilities::PS_MULTI_RESULTS
             | Capabilities::SSL;
 
+        if options.multi_statements {
+            capabilities |= Capabilities::MULTI_STATEMENTS;
+        }
+
         if options.database.is_some() {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.local_infile {
+            capabilities |= Capabilities::LOCAL_FILES;
+        }
+
+        if options.compress {
+            capabilities |= Capabilities::COMPRESS;
+        }
+
         Ok(Self {
             waiting: VecDeque::new(),
             capabilities,
@@ -71,13 +88,16 @@ impl MySqlStream {
             sequence_id: 0,
             collation,
             charset,
+            compress: false,
+            compressed_sequence_id: 0,
+            decompressed: BytesMut::new(),
             stream: BufStream::new(MaybeTlsStream::Raw(socket)),
         })
     }
 
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.wbuf.is_empty() {
-            self.stream.flush().await?;
+            self.flush().await?;
         }
 
         while !self.waiting.is_empty() {
@@ -119,6 +139,7 @@ impl MySqlStream {
         T: Encode<'en, Capabilities>,
     {
         self.sequence_id = 0;
+        self.compressed_sequence_id = 0;
         self.write_packet(payload);
         self.flush().await
     }
@@ -131,22 +152,49 @@ impl MySqlStream {
             .write_with(Packet(payload), (self.capabilities, &mut self.sequence_id));
     }
 
+    /// write the buffered packets to the server,
+    /// wrapped into compressed packets when the compressed protocol is used
+    pub(crate) async fn flush(&mut self) -> Result<(), Error> {
+        if self.compress && !self.stream.wbuf.is_empty() {
+            let buf = compress_packets(&self.stream.wbuf, &mut self.compressed_sequence_id)?;
+            self.stream.wbuf = buf;
+        }
+        self.stream.flush().await
+    }
+
+    // read `cnt` bytes of plain packet data
+    async fn read_bytes(&mut self, cnt: usize) -> Result<Bytes, Error> {
+        if !self.compress {
+            return self.stream.read(cnt).await;
+        }
+        while self.decompressed.len() < cnt {
+            let mut header: Bytes = self.stream.read(7).await?;
+            let compressed_len = header.get_uint_le(3) as usize;
+            let sequence_id = header.get_u8();
+            let uncompressed_len = header.get_uint_le(3) as usize;
+            self.compressed_sequence_id = sequence_id.wrapping_add(1);
+            let payload = self.stream.read_raw(compressed_len).await?;
+            self.decompressed
+                .extend_from_slice(&decompress_payload(&payload, uncompressed_len)?);
+        }
+        Ok(self.decompressed.split_to(cnt).freeze())
+    }
+
     // receive the next packet from the database server
     // may block (async) on more data from the server
     pub(crate) async fn recv_packet(&mut self) -> Result<Packet<Bytes>, Error> {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_packets.html
         // https://mariadb.com/kb/en/library/0-packet/#standard-packet
 
-        let mut header: Bytes = self.stream.read(4).await?;
+        let mut header: Bytes = self.read_bytes(4).await?;
 
         let packet_size = header.get_uint_le(3) as usize;
         let sequence_id = header.get_u8();
 
         self.sequence_id = sequence_id.wrapping_add(1);
 
-        let payload: Bytes = self.stream.read(packet_size).await?;
+        let payload: Bytes = self.read_bytes(packet_size).await?;
 
-        // TODO: packet compression
         // TODO: packet joining
 
         if payload
@@ -211,3 +259,37 @@ impl DerefMut for MySqlStream {
         &mut self.stream
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::connection::MySqlStream;
+    use crate::options::MySqlConnectOptions;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_compressed_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // echo server, the client reads back the compressed packets it wrote
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let (mut r, mut w) = socket.split();
+            let _ = tokio::io::copy(&mut r, &mut w).await;
+        });
+        let options = MySqlConnectOptions::new().host("127.0.0.1").port(port);
+        let mut stream = MySqlStream::connect(&options).await.unwrap();
+        stream.compress = true;
+        // two 10MB packets, the second one crosses the 16MB compressed packet boundary
+        let blobs: Vec<Vec<u8>> = (1..3u32)
+            .map(|n| (0..10_000_000u32).map(|i| (i * n % 251) as u8).collect())
+            .collect();
+        for blob in &blobs {
+            stream.write_packet(&blob[..]);
+        }
+        stream.flush().await.unwrap();
+        for blob in &blobs {
+            let packet = stream.recv_packet().await.unwrap();
+            assert_eq!(&packet[..], &blob[..]);
+        }
+    }
+}
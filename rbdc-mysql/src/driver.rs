@@ -20,7 +20,7 @@ impl Driver for MysqlDriver {
                 let conn = opt.connect().await?;
                 Ok(Box::new(conn) as Box<dyn Connection>)
             } else {
-                Err(Error::from("downcast_ref failure"))
+                Err(Error::from("expected MySqlConnectOptions"))
             }
         })
     }
@@ -29,8 +29,9 @@ impl Driver for MysqlDriver {
         &'a self,
         opt: &'a dyn ConnectOptions,
     ) -> BoxFuture<'a,Result<Box<dyn Connection>, Error>> {
-        let opt: &MySqlConnectOptions = opt.downcast_ref().unwrap();
+        let opt = opt.downcast_ref::<MySqlConnectOptions>();
         Box::pin(async move {
+            let opt = opt.ok_or_else(|| Error::from("expected MySqlConnectOptions"))?;
             let conn = opt.connect().await?;
             Ok(conn)
         })
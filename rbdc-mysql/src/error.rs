@@ -1,4 +1,5 @@
 use crate::protocol::response::ErrPacket;
+use rbdc::{ErrorKind, ErrorKindExt};
 use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 
@@ -67,6 +68,7 @@ impl MySqlDatabaseError {
 
 impl From<MySqlDatabaseError> for rbdc::Error {
     fn from(arg: MySqlDatabaseError) -> Self {
-        rbdc::Error::from(arg.to_string())
+        let kind = arg.code().map(ErrorKind::from_sql_state).unwrap_or(ErrorKind::Unknown);
+        rbdc::Error::with_kind(arg.to_string(), kind)
     }
 }
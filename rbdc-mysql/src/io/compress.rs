@@ -0,0 +1,106 @@
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use rbdc::{err_protocol, Error};
+use std::io::{Read, Write};
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.30/page_protocol_basic_compression.html
+
+/// a compressed packet carries at most 16MB of uncompressed data
+pub(crate) const MAX_COMPRESSED_PAYLOAD: usize = 0xFF_FF_FF;
+
+// smaller payloads are sent as is, compressing them does not pay off
+const MIN_COMPRESS_LENGTH: usize = 50;
+
+/// wrap `data` (one or more plain packets, headers included) into compressed packets.
+///
+/// each compressed packet has a 7 byte header: compressed payload length (3 bytes),
+/// compressed sequence id (1 byte) and uncompressed payload length (3 bytes),
+/// where `0` means the payload was not compressed
+pub(crate) fn compress_packets(data: &[u8], sequence_id: &mut u8) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::with_capacity(data.len() / 2 + 7);
+    for chunk in data.chunks(MAX_COMPRESSED_PAYLOAD) {
+        let mut compressed = None;
+        if chunk.len() >= MIN_COMPRESS_LENGTH {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(chunk)?;
+            let v = encoder.finish()?;
+            if v.len() < chunk.len() {
+                compressed = Some(v);
+            }
+        }
+        let (payload, uncompressed_len) = match &compressed {
+            Some(v) => (v.as_slice(), chunk.len()),
+            None => (chunk, 0),
+        };
+        buf.extend_from_slice(&(payload.len() as u32).to_le_bytes()[..3]);
+        buf.push(*sequence_id);
+        buf.extend_from_slice(&(uncompressed_len as u32).to_le_bytes()[..3]);
+        buf.extend_from_slice(payload);
+        *sequence_id = sequence_id.wrapping_add(1);
+    }
+    Ok(buf)
+}
+
+/// the plain packet bytes of a compressed packet payload
+pub(crate) fn decompress_payload(
+    payload: &[u8],
+    uncompressed_len: usize,
+) -> Result<Vec<u8>, Error> {
+    if uncompressed_len == 0 {
+        return Ok(payload.to_vec());
+    }
+    let mut buf = Vec::with_capacity(uncompressed_len);
+    ZlibDecoder::new(payload).read_to_end(&mut buf)?;
+    if buf.len() != uncompressed_len {
+        return Err(err_protocol!(
+            "compressed packet expected {} bytes but got {}",
+            uncompressed_len,
+            buf.len()
+        ));
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::io::compress::{compress_packets, decompress_payload};
+
+    // read back the compressed packets written by `compress_packets`
+    fn decompress_all(mut buf: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        while !buf.is_empty() {
+            let len = u32::from_le_bytes([buf[0], buf[1], buf[2], 0]) as usize;
+            let uncompressed_len = u32::from_le_bytes([buf[4], buf[5], buf[6], 0]) as usize;
+            data.extend(decompress_payload(&buf[7..7 + len], uncompressed_len).unwrap());
+            buf = &buf[7 + len..];
+        }
+        data
+    }
+
+    #[test]
+    fn test_small_payload_is_not_compressed() {
+        let mut seq = 0;
+        let buf = compress_packets(b"\x01\x00\x00\x00\x0e", &mut seq).unwrap();
+        assert_eq!(buf, b"\x05\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x0e");
+        assert_eq!(seq, 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data: Vec<u8> = (0..20_000_000u32).map(|i| (i % 251) as u8).collect();
+        let mut seq = 3;
+        let buf = compress_packets(&data, &mut seq).unwrap();
+        // split at 16MB
+        assert_eq!(seq, 5);
+        assert!(buf.len() < data.len() / 10);
+        assert_eq!(decompress_all(&buf), data);
+    }
+
+    #[test]
+    fn test_decompress_wrong_length() {
+        let mut seq = 0;
+        let buf = compress_packets(&[7u8; 100], &mut seq).unwrap();
+        assert!(decompress_payload(&buf[7..], 99).is_err());
+    }
+}
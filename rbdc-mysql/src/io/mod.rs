@@ -1,5 +1,6 @@
 mod buf;
 mod buf_mut;
+pub(crate) mod compress;
 
 pub use buf::MySqlBufExt;
 pub use buf_mut::MySqlBufMutExt;
@@ -1,5 +1,6 @@
+use crate::protocol::text::ColumnFlags;
 use crate::result_set::MySqlTypeInfo;
-use rbdc::db::MetaData;
+use rbdc::db::{ColumnInfo, MetaData};
 use rbdc::ext::ustr::UStr;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
@@ -37,4 +38,25 @@ impl MetaData for MysqlMetaData {
         }
         return String::new();
     }
+
+    fn column_ordinal(&self, name: &str) -> Option<usize> {
+        self.inner.get(name).map(|(idx, _)| *idx)
+    }
+
+    fn columns(&self) -> Vec<ColumnInfo> {
+        let mut columns: Vec<(usize, ColumnInfo)> = self
+            .inner
+            .iter()
+            .map(|(s, (idx, type_info))| {
+                let column = ColumnInfo {
+                    name: s.to_string(),
+                    column_type: format!("{:?}", type_info.r#type),
+                    nullable: Some(!type_info.flags.contains(ColumnFlags::NOT_NULL)),
+                };
+                (*idx, column)
+            })
+            .collect();
+        columns.sort_by_key(|(idx, _)| *idx);
+        columns.into_iter().map(|(_, v)| v).collect()
+    }
 }
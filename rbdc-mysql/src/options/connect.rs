@@ -1,14 +1,16 @@
 use crate::connection::MySqlConnection;
-use crate::options::MySqlConnectOptions;
+use crate::options::{format_time_zone, MySqlConnectOptions};
 use futures_core::future::BoxFuture;
 use rbdc::db::{ConnectOptions, Connection};
+use rbdc::net::CertificateInput;
 use rbdc::Error;
 use std::str::FromStr;
+use url::Url;
 
 impl ConnectOptions for MySqlConnectOptions {
     fn connect(&self) -> BoxFuture<Result<Box<dyn Connection>, Error>> {
         Box::pin(async move {
-            let conn = MySqlConnection::establish(self).await?;
+            let mut conn = MySqlConnection::establish(self).await?;
 
             // After the connection is established, we initialize by configuring a few
             // connection parameters
@@ -46,13 +48,143 @@ impl ConnectOptions for MySqlConnectOptions {
             //
             // conn.execute(&*options).await?;
 
+            if let Some(time_zone) = self.time_zone {
+                conn.exec(
+                    &format!("SET time_zone = '{}'", format_time_zone(time_zone)),
+                    vec![],
+                )
+                .await?;
+            }
+
             let r: Box<dyn Connection> = Box::new(conn);
             Ok(r)
         })
     }
 
+    fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+        Ok(Box::new(self.clone()))
+    }
+
     fn set_uri(&mut self, uri: &str) -> Result<(), Error> {
         *self = MySqlConnectOptions::from_str(uri).map_err(|e| Error::from(e.to_string()))?;
         Ok(())
     }
+
+    fn to_url(&self) -> Result<String, Error> {
+        let mut url = Url::parse("mysql://localhost").map_err(|e| Error::from(e.to_string()))?;
+        let err = |_| Error::from("invalid host/username/password for url");
+        url.set_host(Some(&self.host))
+            .map_err(|e| Error::from(e.to_string()))?;
+        url.set_port(Some(self.port)).map_err(err)?;
+        url.set_username(&self.username).map_err(err)?;
+        url.set_password(self.password.as_deref()).map_err(err)?;
+        if let Some(database) = &self.database {
+            url.set_path(database);
+        }
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("ssl-mode", self.ssl_mode.as_str());
+            if let Some(cert) = &self.ssl_ca {
+                query.append_pair(
+                    "ssl-ca",
+                    &match cert {
+                        CertificateInput::Inline(v) => String::from_utf8_lossy(v).to_string(),
+                        CertificateInput::File(v) => v.to_string_lossy().to_string(),
+                    },
+                );
+            }
+            query.append_pair("charset", &self.charset);
+            if let Some(collation) = &self.collation {
+                query.append_pair("collation", collation);
+            }
+            query.append_pair(
+                "statement-cache-capacity",
+                &self.statement_cache_capacity.to_string(),
+            );
+            if let Some(socket) = &self.socket {
+                query.append_pair("socket", &socket.to_string_lossy());
+            }
+            if self.local_infile {
+                query.append_pair("local-infile", "true");
+            }
+            if let Some(time_zone) = self.time_zone {
+                query.append_pair("time-zone", &format_time_zone(time_zone));
+            }
+            if self.compress {
+                query.append_pair("compress", "true");
+            }
+            if !self.multi_statements {
+                query.append_pair("multi-statements", "false");
+            }
+            if self.streaming {
+                query.append_pair("streaming", "true");
+            }
+            if !self.parse_json {
+                query.append_pair("parse-json", "false");
+            }
+        }
+        Ok(url.to_string())
+    }
+
+    fn merge(&mut self, other: &dyn ConnectOptions) {
+        let other = match other.downcast_ref::<MySqlConnectOptions>() {
+            Some(v) => v,
+            None => return,
+        };
+        let default = MySqlConnectOptions::new();
+        if other.host != default.host {
+            self.host = other.host.clone();
+        }
+        if other.port != default.port {
+            self.port = other.port;
+        }
+        if other.socket.is_some() {
+            self.socket = other.socket.clone();
+        }
+        if other.username != default.username {
+            self.username = other.username.clone();
+        }
+        if other.password.is_some() {
+            self.password = other.password.clone();
+        }
+        if other.database.is_some() {
+            self.database = other.database.clone();
+        }
+        if other.ssl_mode.as_str() != default.ssl_mode.as_str() {
+            self.ssl_mode = other.ssl_mode;
+        }
+        if other.ssl_ca.is_some() {
+            self.ssl_ca = other.ssl_ca.clone();
+        }
+        if other.statement_cache_capacity != default.statement_cache_capacity {
+            self.statement_cache_capacity = other.statement_cache_capacity;
+        }
+        if other.charset != default.charset {
+            self.charset = other.charset.clone();
+        }
+        if other.collation.is_some() {
+            self.collation = other.collation.clone();
+        }
+        if other.offset_sec != default.offset_sec {
+            self.offset_sec = other.offset_sec;
+        }
+        if other.time_zone.is_some() {
+            self.time_zone = other.time_zone;
+        }
+        if other.local_infile {
+            self.local_infile = true;
+        }
+        if other.compress {
+            self.compress = true;
+        }
+        if !other.parse_json {
+            self.parse_json = false;
+        }
+        if !other.multi_statements {
+            self.multi_statements = false;
+        }
+        if other.streaming {
+            self.streaming = true;
+        }
+    }
 }
@@ -6,6 +6,7 @@ mod parse;
 mod ssl_mode;
 
 use rbdc::net::CertificateInput;
+use rbdc::Error;
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -27,6 +28,12 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
+/// | `local-infile` | `false` | Allows `LOAD DATA LOCAL INFILE`, see [`MySqlConnection::load_data`](crate::connection::MySqlConnection::load_data). |
+/// | `time-zone` | `None` | Session time zone offset such as `+08:00`, sent as `SET time_zone` on connect. |
+/// | `compress` | `false` | Use the (zlib) compressed protocol when the server supports it. |
+/// | `multi-statements` | `true` | Allow several `;` separated statements in one query (`CLIENT_MULTI_STATEMENTS`). |
+/// | `streaming` | `false` | `get_rows_stream` yields rows as they arrive instead of reading them all first. |
+/// | `parse-json` | `true` | Decode `JSON` columns into structured values instead of the raw text. |
 ///
 /// ## offset_sec
 /// DateTime timezone offset sec
@@ -44,6 +51,12 @@ pub struct MySqlConnectOptions {
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) offset_sec: i32,
+    pub(crate) time_zone: Option<i32>,
+    pub(crate) local_infile: bool,
+    pub(crate) compress: bool,
+    pub(crate) parse_json: bool,
+    pub(crate) multi_statements: bool,
+    pub(crate) streaming: bool,
 }
 
 impl Default for MySqlConnectOptions {
@@ -68,6 +81,12 @@ impl MySqlConnectOptions {
             ssl_ca: None,
             statement_cache_capacity: 100,
             offset_sec: offset_sec(),
+            time_zone: None,
+            local_infile: false,
+            compress: false,
+            parse_json: true,
+            multi_statements: true,
+            streaming: false,
         }
     }
 
@@ -205,4 +224,98 @@ impl MySqlConnectOptions {
         self.offset_sec = offset_sec;
         self
     }
+
+    /// set session time zone offset sec, the connection runs `SET time_zone = '+08:00'` on connect.
+    /// MySQL converts TIMESTAMP columns into the session time zone,
+    /// so decoded TIMESTAMP values are shifted back by this offset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    ///  use rbdc_mysql::options::MySqlConnectOptions;
+    ///  let options = MySqlConnectOptions::new()
+    ///     .time_zone(8 * 3600);
+    /// ```
+    pub fn time_zone(mut self, offset_sec: i32) -> Self {
+        self.time_zone = Some(offset_sec);
+        self
+    }
+
+    /// allow `LOAD DATA LOCAL INFILE`, the client announces the `CLIENT_LOCAL_FILES` capability.
+    /// The server must also enable it with `local_infile=ON`.
+    ///
+    /// The default is `false`.
+    pub fn local_infile(mut self, enable: bool) -> Self {
+        self.local_infile = enable;
+        self
+    }
+
+    /// use the zlib compressed protocol (`CLIENT_COMPRESS`) after the handshake,
+    /// when the server supports it. This trades cpu for much smaller transfers of
+    /// large results over slow links, zstd compression is not supported.
+    ///
+    /// The default is `false`.
+    pub fn compress(mut self, enable: bool) -> Self {
+        self.compress = enable;
+        self
+    }
+
+    /// allow several `;` separated statements in one text query (`CLIENT_MULTI_STATEMENTS`),
+    /// required by [`MySqlConnection::exec_batch`](crate::connection::MySqlConnection::exec_batch).
+    /// Disable it to make the server reject stacked queries.
+    ///
+    /// The default is `true`.
+    pub fn multi_statements(mut self, enable: bool) -> Self {
+        self.multi_statements = enable;
+        self
+    }
+
+    /// let [`MySqlConnection::get_rows_stream`](crate::connection::MySqlConnection::get_rows_stream)
+    /// read one row packet at a time, so memory stays bounded for large scans.
+    /// The connection can not run another query until the stream is drained or dropped.
+    /// When `false`, every row is read before the first one is yielded.
+    ///
+    /// The default is `false`.
+    pub fn streaming(mut self, enable: bool) -> Self {
+        self.streaming = enable;
+        self
+    }
+
+    /// decode `JSON` columns into structured values (`Value::Map`, `Value::Array`, ...),
+    /// text that is not valid json is returned as `Value::String`.
+    /// When `false`, the column is always returned as the raw `Value::String`.
+    ///
+    /// The default is `true`.
+    pub fn parse_json(mut self, enable: bool) -> Self {
+        self.parse_json = enable;
+        self
+    }
+}
+
+/// `+08:00` -> 28800
+pub(crate) fn parse_time_zone(arg: &str) -> Result<i32, Error> {
+    let arg = arg.trim();
+    if arg.eq_ignore_ascii_case("UTC") || arg == "Z" {
+        return Ok(0);
+    }
+    let err = || Error::from(format!("invalid time-zone '{}', expect like '+08:00'", arg));
+    let (sign, hm) = match arg.as_bytes().first() {
+        Some(b'+') => (1, &arg[1..]),
+        Some(b'-') => (-1, &arg[1..]),
+        _ => return Err(err()),
+    };
+    let (h, m) = hm.split_once(':').ok_or_else(err)?;
+    let h: i32 = h.parse().map_err(|_| err())?;
+    let m: i32 = m.parse().map_err(|_| err())?;
+    if h > 14 || m > 59 {
+        return Err(err());
+    }
+    Ok(sign * (h * 3600 + m * 60))
+}
+
+/// 28800 -> `+08:00`
+pub(crate) fn format_time_zone(offset_sec: i32) -> String {
+    let sign = if offset_sec < 0 { '-' } else { '+' };
+    let abs = offset_sec.abs();
+    format!("{}{:02}:{:02}", sign, abs / 3600, abs % 3600 / 60)
 }
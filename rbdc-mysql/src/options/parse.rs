@@ -1,8 +1,9 @@
-use crate::options::MySqlConnectOptions;
+use crate::options::{parse_time_zone, MySqlConnectOptions};
 use percent_encoding::percent_decode_str;
 use rbdc::Error;
 use std::num::ParseIntError;
 use std::str::FromStr;
+use std::str::ParseBoolError;
 use url::{ParseError, Url};
 
 impl FromStr for MySqlConnectOptions {
@@ -66,6 +67,50 @@ impl FromStr for MySqlConnectOptions {
                     options = options.socket(&*value);
                 }
 
+                "local-infile" => {
+                    options = options.local_infile(
+                        value
+                            .parse()
+                            .map_err(|e: ParseBoolError| Error::from(e.to_string()))?,
+                    );
+                }
+
+                "time-zone" => {
+                    options = options.time_zone(parse_time_zone(&value)?);
+                }
+
+                "multi-statements" => {
+                    options = options.multi_statements(
+                        value
+                            .parse()
+                            .map_err(|e: ParseBoolError| Error::from(e.to_string()))?,
+                    );
+                }
+
+                "streaming" => {
+                    options = options.streaming(
+                        value
+                            .parse()
+                            .map_err(|e: ParseBoolError| Error::from(e.to_string()))?,
+                    );
+                }
+
+                "parse-json" => {
+                    options = options.parse_json(
+                        value
+                            .parse()
+                            .map_err(|e: ParseBoolError| Error::from(e.to_string()))?,
+                    );
+                }
+
+                "compress" => {
+                    options = options.compress(
+                        value
+                            .parse()
+                            .map_err(|e: ParseBoolError| Error::from(e.to_string()))?,
+                    );
+                }
+
                 _ => {}
             }
         }
@@ -89,3 +134,101 @@ fn it_parses_password_with_non_ascii_chars_correctly() {
 
     assert_eq!(Some("p@ssw0rd".into()), opts.password);
 }
+
+#[test]
+fn it_parses_local_infile() {
+    let uri = "mysql://root@localhost:3306/test?local-infile=true";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert!(opts.local_infile);
+}
+
+#[test]
+fn it_parses_compress() {
+    let uri = "mysql://root@localhost:3306/test?compress=true";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert!(opts.compress);
+    assert!(!MySqlConnectOptions::new().compress);
+}
+
+#[test]
+fn it_parses_multi_statements() {
+    let uri = "mysql://root@localhost:3306/test?multi-statements=false";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert!(!opts.multi_statements);
+    assert!(MySqlConnectOptions::new().multi_statements);
+}
+
+#[test]
+fn it_parses_streaming() {
+    let uri = "mysql://root@localhost:3306/test?streaming=true";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert!(opts.streaming);
+    assert!(!MySqlConnectOptions::new().streaming);
+}
+
+#[test]
+fn it_parses_parse_json() {
+    let uri = "mysql://root@localhost:3306/test?parse-json=false";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert!(!opts.parse_json);
+    assert!(MySqlConnectOptions::new().parse_json);
+}
+
+#[test]
+fn it_parses_time_zone() {
+    let uri = "mysql://root@localhost:3306/test?time-zone=%2B08:00";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert_eq!(Some(8 * 3600), opts.time_zone);
+
+    let uri = "mysql://root@localhost:3306/test?time-zone=-05:30";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+    assert_eq!(Some(-(5 * 3600 + 30 * 60)), opts.time_zone);
+
+    let uri = "mysql://root@localhost:3306/test?time-zone=Asia/Shanghai";
+    assert!(MySqlConnectOptions::from_str(uri).is_err());
+}
+
+#[test]
+fn it_round_trips_to_url() {
+    use crate::options::MySqlSslMode;
+    use rbdc::db::ConnectOptions;
+
+    let opts = MySqlConnectOptions::new()
+        .host("db.example.com")
+        .port(3307)
+        .username("app")
+        .password("p@ss:word")
+        .database("orders")
+        .ssl_mode(MySqlSslMode::Required)
+        .ssl_ca("/etc/ssl/ca.pem")
+        .charset("utf8mb4")
+        .collation("utf8mb4_bin")
+        .statement_cache_capacity(7)
+        .socket("/tmp/mysql.sock")
+        .local_infile(true)
+        .compress(true)
+        .parse_json(false)
+        .multi_statements(false)
+        .streaming(true)
+        .time_zone(8 * 3600);
+    let url = opts.to_url().unwrap();
+    let parsed = MySqlConnectOptions::from_str(&url).unwrap();
+    assert_eq!(format!("{:?}", opts), format!("{:?}", parsed), "{}", url);
+}
+
+#[test]
+fn it_merges_options() {
+    use rbdc::db::ConnectOptions;
+
+    let mut base = MySqlConnectOptions::new()
+        .host("db.example.com")
+        .username("app")
+        .password("secret")
+        .database("main");
+    let tenant = MySqlConnectOptions::new().database("tenant_42");
+    base.merge(&tenant);
+    assert_eq!("db.example.com", &base.host);
+    assert_eq!("app", &base.username);
+    assert_eq!(Some("secret".into()), base.password);
+    assert_eq!(Some("tenant_42".into()), base.database);
+}
@@ -37,6 +37,18 @@ impl Default for MySqlSslMode {
     }
 }
 
+impl MySqlSslMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            MySqlSslMode::Disabled => "disabled",
+            MySqlSslMode::Preferred => "preferred",
+            MySqlSslMode::Required => "required",
+            MySqlSslMode::VerifyCa => "verify_ca",
+            MySqlSslMode::VerifyIdentity => "verify_identity",
+        }
+    }
+}
+
 impl FromStr for MySqlSslMode {
     type Err = Error;
 
@@ -69,3 +69,16 @@ fn test_decode_err_packet_unknown_database() {
     assert_eq!(p.sql_state.as_deref(), Some("42000"));
     assert_eq!(&p.error_message, "Unknown database \'unknown\'");
 }
+
+#[test]
+fn test_err_packet_kind() {
+    use crate::error::MySqlDatabaseError;
+    use rbdc::{ErrorKind, ErrorKindExt};
+
+    const ERR_DUPLICATE_ENTRY: &[u8] = b"\xff\x26\x04#23000Duplicate entry '1' for key 'PRIMARY'";
+
+    let p = ErrPacket::decode_with(ERR_DUPLICATE_ENTRY.into(), Capabilities::PROTOCOL_41).unwrap();
+    let e = rbdc::Error::from(MySqlDatabaseError(p));
+    assert_eq!(e.kind(), ErrorKind::ConstraintViolation);
+    assert_eq!(e.to_string(), "1062 (23000): Duplicate entry '1' for key 'PRIMARY'");
+}
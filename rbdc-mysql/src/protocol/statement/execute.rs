@@ -1,3 +1,4 @@
+use crate::protocol::text::ColumnFlags;
 use crate::protocol::Capabilities;
 use crate::stmt::MySqlArguments;
 use rbdc::io::Encode;
@@ -39,7 +40,12 @@ impl<'q> Encode<'_, Capabilities> for Execute<'q> {
 
             for ty in &self.arguments.types {
                 buf.push(ty.r#type as u8);
-                buf.push(0);
+                // the high bit of the second byte marks an unsigned integer
+                buf.push(if ty.flags.contains(ColumnFlags::UNSIGNED) {
+                    0x80
+                } else {
+                    0
+                });
             }
 
             buf.extend(&*self.arguments.values);
@@ -11,7 +11,7 @@ use rbdc::{err_protocol, Error};
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/group__group__cs__column__definition__flags.html
 
 bitflags! {
-    //#[derive(serde::Serialize, serde::Deserialize)]
+    #[derive(serde::Serialize, serde::Deserialize)]
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub struct ColumnFlags: u16 {
         /// Field can't be `NULL`.
@@ -115,8 +115,7 @@ pub struct ColumnDefinition {
     pub max_size: u32,
     pub r#type: ColumnType,
     pub flags: ColumnFlags,
-    #[allow(unused)]
-    decimals: u8,
+    pub decimals: u8,
 }
 
 impl ColumnDefinition {
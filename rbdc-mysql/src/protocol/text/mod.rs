@@ -2,10 +2,12 @@ mod column;
 mod ping;
 mod query;
 mod quit;
+mod reset;
 mod row;
 
 pub use column::{ColumnDefinition, ColumnFlags, ColumnType};
 pub use ping::Ping;
 pub use query::Query;
 pub use quit::Quit;
+pub use reset::ResetConnection;
 pub use row::TextRow;
@@ -2,9 +2,12 @@
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
     pub(super) last_insert_id: u64,
+    pub(super) warnings: u64,
 }
 
 impl MySqlQueryResult {
+    /// the LAST_INSERT_ID field of the OK packet, for a multi-row insert
+    /// this is the id generated for the first row
     pub fn last_insert_id(&self) -> u64 {
         self.last_insert_id
     }
@@ -12,13 +15,47 @@ impl MySqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    pub fn warnings(&self) -> u64 {
+        self.warnings
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
     fn extend<T: IntoIterator<Item = MySqlQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
-            self.last_insert_id = elem.last_insert_id;
+            // an EOF or a trailing OK (such as the status of a `CALL`) reports 0,
+            // keep the id of the last statement that generated one
+            if elem.last_insert_id != 0 {
+                self.last_insert_id = elem.last_insert_id;
+            }
+            self.warnings += elem.warnings;
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::query_result::MySqlQueryResult;
+
+    #[test]
+    fn test_extend_keeps_last_insert_id() {
+        let mut v = MySqlQueryResult::default();
+        v.extend([
+            MySqlQueryResult {
+                rows_affected: 3,
+                last_insert_id: 7,
+                warnings: 0,
+            },
+            MySqlQueryResult {
+                rows_affected: 0,
+                last_insert_id: 0,
+                warnings: 1,
+            },
+        ]);
+        assert_eq!(v.rows_affected, 3);
+        assert_eq!(v.last_insert_id, 7);
+        assert_eq!(v.warnings, 1);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::protocol::text::{ColumnDefinition, ColumnType};
+use crate::protocol::text::{ColumnDefinition, ColumnFlags, ColumnType};
 use rbdc::ext::ustr::UStr;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -14,11 +14,14 @@ pub struct MySqlColumn {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MySqlTypeInfo {
     pub r#type: ColumnType,
-    // pub flags: ColumnFlags,
+    pub flags: ColumnFlags,
     pub char_set: u16,
     // [max_size] for integer types, this is (M) in BIT(M) or TINYINT(M)
-    // #[serde(default)]
-    // pub max_size: Option<u32>,
+    #[serde(default)]
+    pub max_size: Option<u32>,
+    // [decimals] the scale, (D) in DECIMAL(M,D)
+    #[serde(default)]
+    pub decimals: Option<u8>,
 }
 impl MySqlTypeInfo {
     fn is_null(&self) -> bool {
@@ -28,8 +31,10 @@ impl MySqlTypeInfo {
     pub const fn binary(ty: ColumnType) -> Self {
         Self {
             r#type: ty,
-            // flags: ColumnFlags::BINARY,
+            flags: ColumnFlags::BINARY,
             char_set: 63,
+            max_size: None,
+            decimals: None,
         }
     }
 
@@ -37,8 +42,10 @@ impl MySqlTypeInfo {
     pub const fn null() -> Self {
         Self {
             r#type: ColumnType::Null,
-            // flags: ColumnFlags::BINARY,
+            flags: ColumnFlags::BINARY,
             char_set: 63,
+            max_size: None,
+            decimals: None,
         }
     }
 
@@ -46,8 +53,10 @@ impl MySqlTypeInfo {
     pub const fn __enum() -> Self {
         Self {
             r#type: ColumnType::Enum,
-            // flags: ColumnFlags::BINARY,
+            flags: ColumnFlags::BINARY,
             char_set: 63,
+            max_size: None,
+            decimals: None,
         }
     }
 
@@ -66,16 +75,27 @@ impl MySqlTypeInfo {
     pub(crate) fn from_column(column: &ColumnDefinition) -> Self {
         Self {
             r#type: column.r#type,
-            // flags: column.flags,
+            flags: column.flags,
             char_set: column.char_set,
+            max_size: Some(column.max_size),
+            decimals: Some(column.decimals),
+        }
+    }
+
+    pub(crate) fn unsigned(ty: ColumnType) -> Self {
+        Self {
+            flags: ColumnFlags::UNSIGNED,
+            ..Self::from_type(ty)
         }
     }
 
     pub(crate) fn from_type(ty: ColumnType) -> Self {
         Self {
             r#type: ty,
-            // flags: column.flags,
+            flags: ColumnFlags::empty(),
             char_set: 63,
+            max_size: None,
+            decimals: None,
         }
     }
 }
@@ -14,6 +14,26 @@ impl Encode for Decimal {
     }
 }
 
+/// the decimal text of the column, with trailing zeros up to the column scale kept,
+/// `1.5` of a `DECIMAL(6,4)` column is `1.5000`
+pub fn decode_decimal(value: MySqlValue) -> String {
+    let mut s = value.as_str().unwrap_or("0").to_string();
+    // 31 (NOT_FIXED_DEC) is sent when the scale is not known
+    if let Some(scale) = value.type_info.decimals.filter(|v| *v < 31) {
+        let scale = scale as usize;
+        let fraction = s.find('.').map(|i| s.len() - i - 1);
+        match fraction {
+            Some(len) if len < scale => s.push_str(&"0".repeat(scale - len)),
+            None if scale > 0 => {
+                s.push('.');
+                s.push_str(&"0".repeat(scale));
+            }
+            _ => {}
+        }
+    }
+    s
+}
+
 impl Decode for Decimal {
     fn decode(value: MySqlValue) -> Result<Self, Error> {
         Decimal::from_str(value.as_str().unwrap_or("0"))
@@ -1,4 +1,6 @@
+use crate::options::format_time_zone;
 use crate::protocol::text::ColumnType;
+use crate::types::set::MySqlSet;
 use crate::value::{MySqlValue, MySqlValueFormat};
 use byteorder::{ByteOrder, LittleEndian};
 use bytes::Buf;
@@ -61,12 +63,14 @@ pub(crate) fn f64_decode(value: MySqlValue) -> Result<f64, Error> {
     })
 }
 
+/// TIMESTAMP is sent in the session time zone, so the result carries that zone
 pub(crate) fn decode_timestamp(value: MySqlValue) -> Result<String, Error> {
+    let zone = session_zone(&value);
     Ok(match value.format() {
         MySqlValueFormat::Text => {
             let mut v = value.as_str()?.to_string();
             if !v.ends_with("Z") {
-                v.push_str("Z");
+                v.push_str(&zone);
             }
             v
         }
@@ -79,11 +83,18 @@ pub(crate) fn decode_timestamp(value: MySqlValue) -> Result<String, Error> {
             } else {
                 "00:00:00".to_string()
             };
-            date + " " + &dt + "Z"
+            date + " " + &dt + &zone
         }
     })
 }
 
+fn session_zone(value: &MySqlValue) -> String {
+    match value.option.time_zone {
+        None | Some(0) => "Z".to_string(),
+        Some(offset_sec) => format_time_zone(offset_sec),
+    }
+}
+
 pub(crate) fn decode_year(value: MySqlValue) -> Result<String, Error> {
     Ok(match value.format() {
         MySqlValueFormat::Text => value.as_str()?.to_string(),
@@ -161,6 +172,16 @@ pub(crate) fn decode_time_buf(_: u8, mut buf: &[u8]) -> Result<String, Error> {
     ))
 }
 
+/// ENUM is sent as the selected label
+pub(crate) fn decode_enum(value: MySqlValue) -> Result<String, Error> {
+    Ok(value.as_str()?.to_string())
+}
+
+/// SET is sent as a comma separated list of the selected members, e.g. `a,c`
+pub(crate) fn decode_set(value: MySqlValue) -> Result<Vec<String>, Error> {
+    Ok(MySqlSet::from(value.as_str()?).0)
+}
+
 pub(crate) fn decode_bool(value: MySqlValue) -> Result<bool, Error> {
     Ok(int_decode(value)? != 0)
 }
@@ -2,6 +2,7 @@ use crate::io::MySqlBufMutExt;
 use crate::types::{Decode, Encode};
 use crate::value::MySqlValue;
 use rbdc::Error;
+use rbs::Value;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
@@ -34,3 +35,42 @@ impl Decode for Enum {
         Ok(Self(value.as_str().unwrap_or_default().to_string()))
     }
 }
+
+/// the selected member of an `ENUM` column, such as `ENUM('small','large')` -> `"small"`.
+/// decoded as `Value::Ext("enum", String)`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename = "enum")]
+pub struct MySqlEnum(pub String);
+
+impl Display for MySqlEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Debug for MySqlEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MySqlEnum({})", self.0)
+    }
+}
+
+impl From<MySqlEnum> for Value {
+    fn from(arg: MySqlEnum) -> Self {
+        Value::Ext("enum", Box::new(Value::String(arg.0)))
+    }
+}
+
+impl Encode for MySqlEnum {
+    fn encode(self, buf: &mut Vec<u8>) -> Result<usize, Error> {
+        let bytes = self.0.into_bytes();
+        let len = bytes.len();
+        buf.put_bytes_lenenc(bytes);
+        Ok(len)
+    }
+}
+
+impl Decode for MySqlEnum {
+    fn decode(value: MySqlValue) -> Result<Self, Error> {
+        Ok(Self(value.as_str()?.to_string()))
+    }
+}
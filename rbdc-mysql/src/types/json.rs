@@ -29,7 +29,12 @@ pub fn encode_json(arg: rbs::Value, buf: &mut Vec<u8>) -> Result<usize, Error> {
     Ok(len)
 }
 
+/// the structured value of a `JSON` column, or the raw text as `Value::String`
+/// when it is not valid json or `parse_json` is disabled
 pub fn decode_json(value: MySqlValue) -> Result<rbs::Value, Error> {
-    let v = value.as_str().unwrap_or("null").to_string();
-    Ok(serde_json::from_str(&v).map_err(|e| Error::from(e.to_string()))?)
+    let v = value.as_str().unwrap_or("null");
+    if !value.option.parse_json {
+        return Ok(rbs::Value::String(v.to_string()));
+    }
+    Ok(serde_json::from_str(v).unwrap_or_else(|_| rbs::Value::String(v.to_string())))
 }
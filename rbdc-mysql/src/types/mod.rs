@@ -32,6 +32,210 @@ pub trait Encode {
 
 #[cfg(test)]
 mod test {
+    use crate::options::MySqlConnectOptions;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::result_set::MySqlTypeInfo;
+    use crate::types::enums::MySqlEnum;
+    use crate::types::set::{MySqlSet, Set};
+    use crate::types::{Decode, Encode, TypeInfo};
+    use crate::value::{MySqlValue, MySqlValueFormat};
+    use rbs::Value;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn text_value(r#type: ColumnType, flags: ColumnFlags, v: &str) -> MySqlValue {
+        MySqlValue {
+            value: Some(v.as_bytes().to_vec()),
+            type_info: MySqlTypeInfo {
+                r#type,
+                flags,
+                char_set: 45,
+                max_size: None,
+                decimals: None,
+            },
+            format: MySqlValueFormat::Text,
+            option: Arc::new(MySqlConnectOptions::new()),
+        }
+    }
+
     #[test]
     fn test_datetime() {}
+
+    #[test]
+    fn test_decode_timestamp_time_zone() {
+        let mut v = text_value(ColumnType::Timestamp, ColumnFlags::empty(), "2024-01-01 08:00:00");
+        v.option = Arc::new(MySqlConnectOptions::new().time_zone(8 * 3600));
+        let utc = fastdate::DateTime::from_str("2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::Ext(
+                "Timestamp",
+                Box::new(Value::I64(utc.unix_timestamp_micros()))
+            )
+        );
+    }
+
+    #[test]
+    fn test_encode_timestamp() {
+        use rbdc::timestamp::Timestamp;
+        let encode = |v: Value| {
+            let mut buf = vec![];
+            v.into_ext("Timestamp").encode(&mut buf).unwrap();
+            buf
+        };
+        let expect = |t: Timestamp| {
+            let mut buf = vec![];
+            t.encode(&mut buf).unwrap();
+            buf
+        };
+        // micros are never read as millis, 1971-01-01 included
+        let t = Timestamp::from_unix_seconds(31_536_000);
+        assert_eq!(encode(t.clone().into()), expect(t));
+        // the `U64` millis payload of older releases
+        let millis = 1_713_491_896_123i64;
+        assert_eq!(
+            encode(Value::U64(millis as u64)),
+            expect(Timestamp::from_unix_millis(millis))
+        );
+    }
+
+    #[test]
+    fn test_decode_bit() {
+        let bit = |width: u32, bytes: &[u8]| {
+            let mut v = text_value(ColumnType::Bit, ColumnFlags::UNSIGNED, "");
+            v.value = Some(bytes.to_vec());
+            v.type_info.max_size = Some(width);
+            Value::decode(v).unwrap()
+        };
+        assert_eq!(bit(1, &[1]), Value::Bool(true));
+        assert_eq!(bit(1, &[0]), Value::Bool(false));
+        assert_eq!(bit(8, &[0b1010_1010]), Value::U64(0b1010_1010));
+        assert_eq!(bit(16, &[0, 0b1010_1010]), Value::U64(0b1010_1010));
+        assert_eq!(bit(16, &[1, 0b1010_1010]), Value::U64(0x1AA));
+        assert_eq!(
+            bit(64, &[0, 0, 0, 0, 0, 0, 0, 0b1010_1010]),
+            Value::U64(0b1010_1010)
+        );
+    }
+
+    #[test]
+    fn test_decode_bigint_unsigned() {
+        let v = text_value(
+            ColumnType::LongLong,
+            ColumnFlags::UNSIGNED,
+            "18446744073709551615",
+        );
+        assert_eq!(Value::decode(v).unwrap(), Value::U64(u64::MAX));
+        let v = text_value(ColumnType::LongLong, ColumnFlags::empty(), "-1");
+        assert_eq!(Value::decode(v).unwrap(), Value::I64(-1));
+
+        // binary protocol round trip
+        let arg = Value::U64(u64::MAX);
+        let type_info = arg.type_info();
+        assert!(type_info.flags.contains(ColumnFlags::UNSIGNED));
+        let mut buf = vec![];
+        arg.encode(&mut buf).unwrap();
+        let v = MySqlValue {
+            value: Some(buf),
+            type_info,
+            format: MySqlValueFormat::Binary,
+            option: Arc::new(MySqlConnectOptions::new()),
+        };
+        assert_eq!(Value::decode(v).unwrap(), Value::U64(u64::MAX));
+    }
+
+    #[test]
+    fn test_decode_decimal() {
+        let decimal = |scale: Option<u8>, s: &str| {
+            let mut v = text_value(ColumnType::NewDecimal, ColumnFlags::BINARY, s);
+            v.type_info.decimals = scale;
+            Value::decode(v).unwrap()
+        };
+        let ext = |s: &str| Value::Ext("Decimal", Box::new(Value::String(s.to_string())));
+        assert_eq!(decimal(Some(4), "1.5"), ext("1.5000"));
+        assert_eq!(decimal(Some(4), "1.5000"), ext("1.5000"));
+        assert_eq!(decimal(Some(2), "-12"), ext("-12.00"));
+        assert_eq!(decimal(Some(0), "12"), ext("12"));
+        assert_eq!(decimal(None, "1.50"), ext("1.50"));
+        assert_eq!(decimal(Some(31), "1.5"), ext("1.5"));
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let v = text_value(ColumnType::Json, ColumnFlags::BINARY, r#"{"key": 42}"#);
+        let v = Value::decode(v).unwrap();
+        assert!(matches!(v, Value::Map(_)), "{:?}", v);
+        assert_eq!(v["key"].as_i64(), Some(42));
+        let v = text_value(ColumnType::Json, ColumnFlags::BINARY, "{not json");
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::String("{not json".to_string())
+        );
+        let mut v = text_value(ColumnType::Json, ColumnFlags::BINARY, r#"{"key": 42}"#);
+        v.option = Arc::new(MySqlConnectOptions::new().parse_json(false));
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::String(r#"{"key": 42}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_enum() {
+        // ENUM('a','b')
+        let v = text_value(ColumnType::String, ColumnFlags::ENUM, "b");
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::Ext("enum", Box::new(Value::String("b".to_string())))
+        );
+        let v = text_value(ColumnType::String, ColumnFlags::ENUM, "a");
+        assert_eq!(MySqlEnum::decode(v).unwrap(), MySqlEnum("a".to_string()));
+    }
+
+    #[test]
+    fn test_decode_set() {
+        // SET('x','y','z')
+        let v = text_value(ColumnType::String, ColumnFlags::SET, "x,z");
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::Ext(
+                "set",
+                Box::new(Value::Array(vec![
+                    Value::String("x".to_string()),
+                    Value::String("z".to_string())
+                ]))
+            )
+        );
+        let v = text_value(ColumnType::String, ColumnFlags::SET, "");
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::Ext("set", Box::new(Value::Array(vec![])))
+        );
+        let v = text_value(ColumnType::String, ColumnFlags::SET, "x,y,z");
+        assert_eq!(
+            MySqlSet::decode(v).unwrap(),
+            MySqlSet(vec!["x".to_string(), "y".to_string(), "z".to_string()])
+        );
+        // the existing `Set` keeps the text as is
+        let v = text_value(ColumnType::String, ColumnFlags::SET, "x,z");
+        assert_eq!(Set::decode(v).unwrap(), Set("x,z".to_string()));
+    }
+
+    #[test]
+    fn test_encode_set() {
+        let set = Value::Ext(
+            "set",
+            Box::new(Value::Array(vec![
+                Value::String("x".to_string()),
+                Value::String("z".to_string()),
+            ])),
+        );
+        let mut buf = vec![];
+        set.encode(&mut buf).unwrap();
+        assert_eq!(buf, b"\x03x,z");
+        let mut buf = vec![];
+        Value::Ext("Set", Box::new(Value::String("x,z".to_string())))
+            .encode(&mut buf)
+            .unwrap();
+        assert_eq!(buf, b"\x03x,z");
+    }
 }
@@ -2,6 +2,7 @@ use crate::io::MySqlBufMutExt;
 use crate::types::{Decode, Encode};
 use crate::value::MySqlValue;
 use rbdc::Error;
+use rbs::Value;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
@@ -34,3 +35,54 @@ impl Decode for Set {
         Ok(Self(value.as_str().unwrap_or_default().to_string()))
     }
 }
+
+/// the selected members of a `SET` column, such as `SET('x','y','z')` -> `["x", "z"]`.
+/// MySQL sends them as a comma separated string (`x,z`), decoded as `Value::Ext("set", Array)`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename = "set")]
+pub struct MySqlSet(pub Vec<String>);
+
+impl From<&str> for MySqlSet {
+    fn from(arg: &str) -> Self {
+        if arg.is_empty() {
+            return MySqlSet(vec![]);
+        }
+        MySqlSet(arg.split(',').map(|x| x.to_string()).collect())
+    }
+}
+
+impl Display for MySqlSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(","))
+    }
+}
+
+impl Debug for MySqlSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MySqlSet({})", self)
+    }
+}
+
+impl From<MySqlSet> for Value {
+    fn from(arg: MySqlSet) -> Self {
+        Value::Ext(
+            "set",
+            Box::new(Value::Array(arg.0.into_iter().map(Value::String).collect())),
+        )
+    }
+}
+
+impl Encode for MySqlSet {
+    fn encode(self, buf: &mut Vec<u8>) -> Result<usize, Error> {
+        let bytes = self.to_string().into_bytes();
+        let len = bytes.len();
+        buf.put_bytes_lenenc(bytes);
+        Ok(len)
+    }
+}
+
+impl Decode for MySqlSet {
+    fn decode(value: MySqlValue) -> Result<Self, Error> {
+        Ok(Self::from(value.as_str()?))
+    }
+}
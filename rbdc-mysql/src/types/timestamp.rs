@@ -10,7 +10,7 @@ use rbdc::Error;
 
 impl Encode for Timestamp {
     fn encode(self, buf: &mut Vec<u8>) -> Result<usize, Error> {
-        let datetime = fastdate::DateTime::from_timestamp_millis(self.0 as i64);
+        let datetime = fastdate::DateTime::from_timestamp_micros(self.0);
         let size = date_time_size_hint(
             datetime.hour(),
             datetime.minute(),
@@ -42,9 +42,11 @@ impl Encode for Timestamp {
 
 impl Decode for Timestamp {
     fn decode(value: MySqlValue) -> Result<Self, Error> {
+        // TIMESTAMP is sent in the session time zone
+        let offset_us = value.option.time_zone.unwrap_or_default() as i64 * 1_000_000;
         Ok(match value.format() {
             MySqlValueFormat::Text => {
-                Self(fastdate::DateTime::from_str(value.as_str()?).map_err(|e|Error::from(e.to_string()))?.unix_timestamp_millis())
+                Self(fastdate::DateTime::from_str(value.as_str()?).map_err(|e|Error::from(e.to_string()))?.unix_timestamp_micros() - offset_us)
             }
             MySqlValueFormat::Binary => {
                 let buf = value.as_bytes()?;
@@ -60,7 +62,7 @@ impl Decode for Timestamp {
                         hour: 0,
                     }
                 };
-                Self(fastdate::DateTime::from((date, time)).unix_timestamp_millis())
+                Self(fastdate::DateTime::from((date, time)).unix_timestamp_micros() - offset_us)
             }
         })
     }
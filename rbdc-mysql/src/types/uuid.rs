@@ -3,10 +3,11 @@ use crate::types::{Decode, Encode};
 use crate::value::MySqlValue;
 use rbdc::uuid::Uuid;
 use rbdc::Error;
+use std::str::FromStr;
 
 impl Encode for Uuid {
     fn encode(self, buf: &mut Vec<u8>) -> Result<usize, Error> {
-        let bytes = self.0.into_bytes();
+        let bytes = self.to_string().into_bytes();
         let len = bytes.len();
         buf.put_bytes_lenenc(bytes);
         Ok(len)
@@ -15,6 +16,11 @@ impl Encode for Uuid {
 
 impl Decode for Uuid {
     fn decode(value: MySqlValue) -> Result<Self, Error> {
-        Ok(Self(value.as_str().unwrap_or_default().to_string()))
+        let bytes = value.as_bytes()?;
+        if bytes.len() == 16 {
+            // BINARY(16) column
+            return Uuid::from_slice(bytes);
+        }
+        Uuid::from_str(value.as_str()?)
     }
 }
@@ -1,13 +1,14 @@
 use crate::io::MySqlBufMutExt;
-use crate::protocol::text::ColumnType;
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::result_set::MySqlTypeInfo;
 use crate::types::decode::{
-    decode_date, decode_time, decode_timestamp, decode_year, f32_decode, f64_decode, int_decode,
-    uint_decode,
+    decode_date, decode_enum, decode_set, decode_time, decode_timestamp, decode_year, f32_decode,
+    f64_decode, int_decode, uint_decode,
 };
-use crate::types::enums::Enum;
+use crate::types::decimal::decode_decimal;
+use crate::types::enums::MySqlEnum;
 use crate::types::json::{decode_json, encode_json};
-use crate::types::set::Set;
+use crate::types::set::MySqlSet;
 use crate::types::year::Year;
 use crate::types::{Decode, Encode, TypeInfo};
 use crate::value::{MySqlValue};
@@ -28,8 +29,8 @@ impl TypeInfo for Value {
             Value::Bool(_) => MySqlTypeInfo::from_type(ColumnType::Tiny),
             Value::I32(_) => MySqlTypeInfo::from_type(ColumnType::Long),
             Value::I64(_) => MySqlTypeInfo::from_type(ColumnType::LongLong),
-            Value::U32(_) => MySqlTypeInfo::from_type(ColumnType::Long),
-            Value::U64(_) => MySqlTypeInfo::from_type(ColumnType::LongLong),
+            Value::U32(_) => MySqlTypeInfo::unsigned(ColumnType::Long),
+            Value::U64(_) => MySqlTypeInfo::unsigned(ColumnType::LongLong),
             Value::F32(_) => MySqlTypeInfo::from_type(ColumnType::Float),
             Value::F64(_) => MySqlTypeInfo::from_type(ColumnType::Double),
             Value::String(_) => MySqlTypeInfo::from_type(ColumnType::VarChar),
@@ -54,8 +55,8 @@ impl TypeInfo for Value {
                     }
                     "DateTime" => MySqlTypeInfo::from_type(ColumnType::Datetime),
                     "Json" => MySqlTypeInfo::from_type(ColumnType::Json),
-                    "Enum" => MySqlTypeInfo::from_type(ColumnType::Enum),
-                    "Set" => MySqlTypeInfo::from_type(ColumnType::Set),
+                    "enum" | "Enum" => MySqlTypeInfo::from_type(ColumnType::Enum),
+                    "set" | "Set" => MySqlTypeInfo::from_type(ColumnType::Set),
                     _ => MySqlTypeInfo::null(),
                 }
             }
@@ -111,7 +112,7 @@ impl Encode for Value {
                 match ext_type {
                     "Uuid" => {
                         //uuid -> string
-                        Uuid(v.into_string().unwrap_or_default()).encode(buf)
+                        Uuid::from_str(&v.into_string().unwrap_or_default())?.encode(buf)
                     }
                     //decimal = 12345678
                     "Decimal" => Decimal::from_str(v.as_str().unwrap_or_default())
@@ -132,7 +133,7 @@ impl Encode for Value {
                     )
                     .encode(buf),
                     //RFC3339 = "2006-01-02 15:04:05.999999"
-                    "Timestamp" => Timestamp(v.as_i64().unwrap_or_default()).encode(buf),
+                    "Timestamp" => Timestamp::from_ext_value(&v).unwrap_or_default().encode(buf),
                     "DateTime" => DateTime(
                         fastdate::DateTime::from_str(&v.into_string().unwrap_or_default())
                             .map_err(|e| Error::from(e.to_string()))?,
@@ -142,8 +143,18 @@ impl Encode for Value {
                         let json_str = v.into_string().unwrap_or_default();
                         Json(json_str).encode(buf)
                     }
-                    "Enum" => Enum(v.into_string().unwrap_or_default()).encode(buf),
-                    "Set" => Set(v.into_string().unwrap_or_default()).encode(buf),
+                    "enum" | "Enum" => MySqlEnum(v.into_string().unwrap_or_default()).encode(buf),
+                    //set = ["x", "z"] or "x,z"
+                    "set" | "Set" => match *v {
+                        Value::Array(members) => MySqlSet(
+                            members
+                                .into_iter()
+                                .map(|x| x.into_string().unwrap_or_default())
+                                .collect(),
+                        )
+                        .encode(buf),
+                        v => MySqlSet::from(v.as_str().unwrap_or_default()).encode(buf),
+                    },
                     _ => {
                         buf.put_bytes_lenenc(v.into_bytes().unwrap_or_default());
                         Ok(0)
@@ -159,6 +170,13 @@ impl Decode for Value {
     where
         Self: Sized,
     {
+        let flags = v.type_info().flags;
+        if flags.contains(ColumnFlags::ENUM) {
+            return Ok(MySqlEnum(decode_enum(v).unwrap_or_default()).into());
+        }
+        if flags.contains(ColumnFlags::SET) {
+            return Ok(MySqlSet(decode_set(v).unwrap_or_default()).into());
+        }
         let type_info = v.type_info().r#type;
         Ok(match type_info {
             ColumnType::Tiny => Value::I32(int_decode(v).unwrap_or_default() as i32),
@@ -167,10 +185,19 @@ impl Decode for Value {
             ColumnType::Float => Value::F32(f32_decode(v).unwrap_or_default()),
             ColumnType::Double => Value::F64(f64_decode(v).unwrap_or_default()),
             ColumnType::Null => Value::Null,
+            // BIGINT UNSIGNED goes up to u64::MAX
+            ColumnType::LongLong if flags.contains(ColumnFlags::UNSIGNED) => {
+                Value::U64(uint_decode(v).unwrap_or_default())
+            }
             ColumnType::LongLong => Value::I64(int_decode(v).unwrap_or_default()),
             ColumnType::Int24 => Value::I32(int_decode(v).unwrap_or_default() as i32),
             ColumnType::VarChar => Value::String(v.as_str().unwrap_or_default().to_string()),
-            ColumnType::Bit => Value::U64(uint_decode(v).unwrap_or_default()),
+            // BIT(M) is sent as ceil(M/8) bytes, most significant byte first
+            ColumnType::Bit => match v.type_info.max_size {
+                Some(1) => Value::Bool(uint_decode(v).unwrap_or_default() != 0),
+                Some(n) if n > 64 => Value::Binary(v.as_bytes().unwrap_or_default().to_vec()),
+                _ => Value::U64(uint_decode(v).unwrap_or_default()),
+            },
             ColumnType::TinyBlob => Value::Binary(v.as_bytes().unwrap_or_default().to_vec()),
             ColumnType::MediumBlob => Value::Binary(v.as_bytes().unwrap_or_default().to_vec()),
             ColumnType::LongBlob => Value::Binary(v.as_bytes().unwrap_or_default().to_vec()),
@@ -179,16 +206,16 @@ impl Decode for Value {
             ColumnType::String => Value::String(v.as_str().unwrap_or_default().to_string()),
             ColumnType::Timestamp => Value::Ext(
                 "Timestamp",
-                Box::new(Value::U64({
+                Box::new(Value::I64({
                     let s = decode_timestamp(v).unwrap_or_default();
                     let date =
                         fastdate::DateTime::from_str(&s).map_err(|e| Error::from(e.to_string()))?;
-                    date.unix_timestamp_millis() as u64
+                    date.unix_timestamp_micros()
                 })),
             ),
             ColumnType::Decimal => Value::Ext(
                 "Decimal",
-                Box::new(Value::String(v.as_str().unwrap_or("0").to_string())),
+                Box::new(Value::String(decode_decimal(v))),
             ),
             ColumnType::Date => Value::Ext(
                 "Date",
@@ -209,16 +236,10 @@ impl Decode for Value {
             ColumnType::Json => decode_json(v)?,
             ColumnType::NewDecimal => Value::Ext(
                 "Decimal",
-                Box::new(Value::String(v.as_str().unwrap_or("0").to_string())),
-            ),
-            ColumnType::Enum => Value::Ext(
-                "Enum",
-                Box::new(Value::String(v.as_str().unwrap_or("").to_string())),
-            ),
-            ColumnType::Set => Value::Ext(
-                "Set",
-                Box::new(Value::String(v.as_str().unwrap_or("").to_string())),
+                Box::new(Value::String(decode_decimal(v))),
             ),
+            ColumnType::Enum => MySqlEnum(decode_enum(v).unwrap_or_default()).into(),
+            ColumnType::Set => MySqlSet(decode_set(v).unwrap_or_default()).into(),
             //bytes ,see https://dev.mysql.com/doc/internals/en/x-protocol-messages-messages.html
             ColumnType::Geometry => Value::Ext(
                 "Geometry",
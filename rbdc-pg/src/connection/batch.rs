@@ -0,0 +1,105 @@
+// postgres limits a single statement to 65535 bind parameters
+pub(crate) const MAX_BIND_PARAMS: usize = u16::MAX as usize;
+
+/// find the `VALUES (...)` tuple of an `INSERT` statement,
+/// returns the byte range of the tuple (including the parentheses)
+/// when it holds every `?` placeholder of the statement
+pub(crate) fn find_values_tuple(sql: &str) -> Option<(usize, usize)> {
+    let bytes = sql.as_bytes();
+    let lower = sql.to_ascii_lowercase();
+    if !lower.trim_start().starts_with("insert") {
+        return None;
+    }
+    let mut from = 0;
+    let start = loop {
+        let idx = from + lower[from..].find("values")?;
+        let before = idx.checked_sub(1).map(|i| bytes[i]);
+        let after = bytes.get(idx + 6).copied();
+        let is_word = |c: Option<u8>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_');
+        if !is_word(before) && !is_word(after) {
+            break idx + 6;
+        }
+        from = idx + 6;
+    };
+    let open = start + sql[start..].find(|c: char| !c.is_whitespace())?;
+    if bytes[open] != b'(' {
+        return None;
+    }
+    let mut depth = 0;
+    let mut in_str = false;
+    let mut close = None;
+    for (i, &c) in bytes.iter().enumerate().skip(open) {
+        match c {
+            b'\'' => in_str = !in_str,
+            b'(' if !in_str => depth += 1,
+            b')' if !in_str => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    let count = |s: &str| s.bytes().filter(|c| *c == b'?').count();
+    if count(&sql[open..close]) == 0 || count(&sql[open..close]) != count(sql) {
+        return None;
+    }
+    Some((open, close))
+}
+
+/// repeat the `VALUES` tuple `rows` times, `INSERT INTO t (a,b) VALUES (?,?)` -> `INSERT INTO t (a,b) VALUES (?,?), (?,?)`
+pub(crate) fn repeat_values(sql: &str, tuple: (usize, usize), rows: usize) -> String {
+    let (open, close) = tuple;
+    let group = &sql[open..close];
+    let mut s = String::with_capacity(sql.len() + (group.len() + 2) * rows);
+    s.push_str(&sql[..open]);
+    for i in 0..rows {
+        if i != 0 {
+            s.push_str(", ");
+        }
+        s.push_str(group);
+    }
+    s.push_str(&sql[close..]);
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::batch::{find_values_tuple, repeat_values};
+
+    #[test]
+    fn test_repeat_values() {
+        let sql = "INSERT INTO t (a, b) VALUES (?, ?)";
+        let tuple = find_values_tuple(sql).unwrap();
+        assert_eq!(
+            repeat_values(sql, tuple, 3),
+            "INSERT INTO t (a, b) VALUES (?, ?), (?, ?), (?, ?)"
+        );
+        let sql = "insert into t (a, b) values (?, now()) on conflict do nothing";
+        let tuple = find_values_tuple(sql).unwrap();
+        assert_eq!(
+            repeat_values(sql, tuple, 2),
+            "insert into t (a, b) values (?, now()), (?, now()) on conflict do nothing"
+        );
+    }
+
+    #[test]
+    fn test_find_values_tuple_none() {
+        assert_eq!(find_values_tuple("UPDATE t SET a = ?"), None);
+        assert_eq!(
+            find_values_tuple("INSERT INTO t SELECT * FROM s WHERE a = ?"),
+            None
+        );
+        assert_eq!(
+            find_values_tuple("INSERT INTO t (a) VALUES (?) ON CONFLICT (a) DO UPDATE SET b = ?"),
+            None
+        );
+        assert_eq!(
+            find_values_tuple("INSERT INTO my_values (a) VALUES (1)"),
+            None
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::connection::{PgConnection, PgStream};
+use crate::message::CancelRequest;
+use crate::options::PgConnectOptions;
+use futures_core::future::BoxFuture;
+use rbdc::db::statement_timeout;
+use rbdc::Error;
+use std::time::Duration;
+
+/// cancels the statement running on a connection from another socket, see
+/// <https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS>
+///
+/// the token does not borrow the connection, so it can be moved to another task
+/// while the connection runs a statement:
+/// ```rust,ignore
+/// let token = conn.cancel_token();
+/// tokio::spawn(async move {
+///     tokio::time::sleep(Duration::from_secs(1)).await;
+///     token.cancel().await
+/// });
+/// // fails with `canceling statement due to user request`
+/// conn.exec("select pg_sleep(10)", vec![]).await;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PgCancelToken {
+    options: PgConnectOptions,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgConnection {
+    /// a [`PgCancelToken`] for the statements of this connection
+    pub fn cancel_token(&self) -> PgCancelToken {
+        PgCancelToken {
+            options: self.options.clone(),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+}
+
+impl PgCancelToken {
+    /// ask the server to cancel the statement currently running on the connection, if any.
+    /// the server answers nothing and closes the socket, whether or not a statement was cancelled
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+        stream
+            .send(CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .await
+    }
+
+    /// wait for `f` at most `timeout`, then cancel the statement and wait for the backend to
+    /// report it, so the connection stays usable
+    pub(crate) async fn timeout<T>(
+        self,
+        timeout: Duration,
+        mut f: BoxFuture<'_, Result<T, Error>>,
+    ) -> Result<T, Error> {
+        match rbdc::rt::timeout(timeout, &mut f).await {
+            Ok(v) => v,
+            Err(_) => {
+                self.cancel().await?;
+                // fails with `query_canceled`, unless it finished before the cancel arrived
+                f.await.map_err(|_| statement_timeout(timeout))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::cancel::PgCancelToken;
+    use crate::options::{PgConnectOptions, PgSslMode};
+    use rbdc::{ErrorKind, ErrorKindExt};
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            socket.read_to_end(&mut request).await.unwrap();
+            request
+        });
+        let token = PgCancelToken {
+            options: PgConnectOptions::new_without_pgpass()
+                .host("127.0.0.1")
+                .port(port)
+                .ssl_mode(PgSslMode::Disable),
+            process_id: 1234,
+            secret_key: 5678,
+        };
+        token.clone().cancel().await.unwrap();
+        // one message on its own socket, closed right after
+        let request = server.await.unwrap();
+        assert_eq!(request.len(), 16);
+        assert_eq!(&request[..4], &16u32.to_be_bytes());
+        assert_eq!(&request[8..12], &1234u32.to_be_bytes());
+        assert_eq!(&request[12..], &5678u32.to_be_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_cancels() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 16];
+            socket.read_exact(&mut request).await.unwrap();
+            tx.send(()).unwrap();
+            request
+        });
+        let token = PgCancelToken {
+            options: PgConnectOptions::new_without_pgpass()
+                .host("127.0.0.1")
+                .port(port)
+                .ssl_mode(PgSslMode::Disable),
+            process_id: 7,
+            secret_key: 42,
+        };
+        // a statement that only ends once the server received the cancel
+        let f = Box::pin(async move {
+            rx.await.unwrap();
+            Err::<(), _>(rbdc::Error::from("canceling statement due to user request"))
+        });
+        let e = token
+            .timeout(Duration::from_millis(50), f)
+            .await
+            .unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Timeout, "{}", e);
+        let request = server.await.unwrap();
+        assert_eq!(&request[4..8], &80877102u32.to_be_bytes());
+        assert_eq!(&request[8..], &[0, 0, 0, 7, 0, 0, 0, 42]);
+
+        let token = PgCancelToken {
+            options: PgConnectOptions::new_without_pgpass(),
+            process_id: 7,
+            secret_key: 42,
+        };
+        let v = token
+            .timeout(Duration::from_secs(5), Box::pin(async { Ok(1) }))
+            .await;
+        assert_eq!(v.unwrap(), 1);
+    }
+}
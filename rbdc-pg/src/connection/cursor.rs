@@ -0,0 +1,200 @@
+use crate::connection::PgConnection;
+use crate::message::TransactionStatus;
+use rbdc::db::{Connection, Row};
+use rbdc::Error;
+use rbs::Value;
+
+/// server side cursors, to read a huge result set in batches:
+/// ```rust,ignore
+/// conn.begin().await?;
+/// conn.declare_cursor("big", "select * from t where a > ?", vec![Value::I32(1)]).await?;
+/// loop {
+///     let rows = conn.fetch("big", 100).await?;
+///     if rows.is_empty() {
+///         break;
+///     }
+///     // ...
+/// }
+/// conn.commit().await?;
+/// ```
+/// the cursor is declared `WITHOUT HOLD`, so postgres closes it when the transaction ends
+/// (commit or rollback), [`PgConnection::close_cursor`] closes it earlier
+impl PgConnection {
+    /// `DECLARE <name> NO SCROLL CURSOR FOR <sql>`, must be called inside a transaction
+    pub async fn declare_cursor(
+        &mut self,
+        name: &str,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+        if matches!(self.transaction_status, TransactionStatus::Idle) {
+            return Err(Error::from(
+                "declare_cursor must be called inside a transaction, call begin() first",
+            ));
+        }
+        self.exec(&declare_sql(name, sql), params).await?;
+        Ok(())
+    }
+
+    /// `FETCH FORWARD <count> FROM <name>`, the next (at most) `count` rows of the cursor,
+    /// empty once it is exhausted
+    pub async fn fetch(&mut self, name: &str, count: u64) -> Result<Vec<Box<dyn Row>>, Error> {
+        self.get_rows(&fetch_sql(name, count), vec![]).await
+    }
+
+    /// `CLOSE <name>`
+    pub async fn close_cursor(&mut self, name: &str) -> Result<(), Error> {
+        self.exec(&format!("CLOSE {}", quote_ident(name)), vec![])
+            .await?;
+        Ok(())
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn declare_sql(name: &str, sql: &str) -> String {
+    format!("DECLARE {} NO SCROLL CURSOR FOR {}", quote_ident(name), sql)
+}
+
+fn fetch_sql(name: &str, count: u64) -> String {
+    format!("FETCH FORWARD {} FROM {}", count, quote_ident(name))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::cursor::{declare_sql, fetch_sql};
+    use crate::connection::PgConnection;
+    use crate::options::{PgConnectOptions, PgSslMode};
+    use rbdc::db::Connection;
+    use rbs::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_cursor_sql() {
+        assert_eq!(
+            declare_sql("big", "select * from t where a > ?"),
+            "DECLARE \"big\" NO SCROLL CURSOR FOR select * from t where a > ?"
+        );
+        assert_eq!(fetch_sql("big", 100), "FETCH FORWARD 100 FROM \"big\"");
+        assert_eq!(fetch_sql("a\"b", 1), "FETCH FORWARD 1 FROM \"a\"\"b\"");
+    }
+
+    fn message(format: u8, body: &[u8]) -> Vec<u8> {
+        let mut v = vec![format];
+        v.extend(&(body.len() as i32 + 4).to_be_bytes());
+        v.extend(body);
+        v
+    }
+
+    async fn read_message(socket: &mut TcpStream) -> (u8, Vec<u8>) {
+        let format = socket.read_u8().await.unwrap();
+        let len = socket.read_i32().await.unwrap() as usize;
+        let mut body = vec![0u8; len - 4];
+        socket.read_exact(&mut body).await.unwrap();
+        (format, body)
+    }
+
+    // answers the startup, then simple queries: `begin`/`commit`, the `DECLARE` of a cursor
+    // over the int4 rows `0..rows`, and its `FETCH FORWARD n`.
+    // reports the number of rows sent by each `FETCH`
+    async fn server(rows: i32) -> (u16, tokio::task::JoinHandle<Vec<i32>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            socket.read_exact(&mut vec![0u8; len - 4]).await.unwrap();
+            let mut out = message(b'R', &0i32.to_be_bytes());
+            out.extend(message(b'K', &[0, 0, 0, 1, 0, 0, 0, 2]));
+            out.extend(message(b'Z', b"I"));
+            socket.write_all(&out).await.unwrap();
+
+            let mut pages = vec![];
+            let mut next = 0;
+            let mut status = b'I';
+            loop {
+                let sql = match read_message(&mut socket).await {
+                    (b'Q', body) => String::from_utf8(body[..body.len() - 1].to_vec()).unwrap(),
+                    (b'X', _) => break,
+                    (format, _) => panic!("unexpected {}", format as char),
+                };
+                let mut out = vec![];
+                if sql == "begin" {
+                    status = b'T';
+                    out.extend(message(b'C', b"BEGIN\0"));
+                } else if sql == "commit" {
+                    status = b'I';
+                    out.extend(message(b'C', b"COMMIT\0"));
+                } else if sql.starts_with("DECLARE \"big\" NO SCROLL CURSOR FOR ") {
+                    out.extend(message(b'C', b"DECLARE CURSOR\0"));
+                } else if let Some(count) = sql
+                    .strip_prefix("FETCH FORWARD ")
+                    .and_then(|v| v.strip_suffix(" FROM \"big\""))
+                {
+                    let count = count.parse::<i32>().unwrap().min(rows - next);
+                    // one int4 column `id`, text format
+                    let mut description = vec![0, 1];
+                    description.extend(b"id\0");
+                    description.extend([0, 0, 0, 0, 0, 0, 0, 0, 0, 23, 0, 4]);
+                    description.extend([255, 255, 255, 255, 0, 0]);
+                    out.extend(message(b'T', &description));
+                    for id in next..next + count {
+                        let id = id.to_string();
+                        let mut row = vec![0, 1];
+                        row.extend((id.len() as i32).to_be_bytes());
+                        row.extend(id.as_bytes());
+                        out.extend(message(b'D', &row));
+                    }
+                    next += count;
+                    pages.push(count);
+                    out.extend(message(b'C', format!("FETCH {}\0", count).as_bytes()));
+                } else {
+                    // also the `lc_monetary` query of the startup, answered like a server without `MONEY`
+                    out.extend(message(b'E', b"SERROR\0C42704\0Mnot supported\0\0"));
+                }
+                out.extend(message(b'Z', &[status]));
+                socket.write_all(&out).await.unwrap();
+            }
+            pages
+        });
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pages() {
+        let (port, server) = server(250).await;
+        let options = PgConnectOptions::new_without_pgpass()
+            .host("127.0.0.1")
+            .port(port)
+            .username("postgres")
+            .ssl_mode(PgSslMode::Disable);
+        let mut conn = PgConnection::establish(&options).await.unwrap();
+        assert!(conn
+            .declare_cursor("big", "select id from t", vec![])
+            .await
+            .is_err());
+        conn.begin().await.unwrap();
+        conn.declare_cursor("big", "select id from t", vec![])
+            .await
+            .unwrap();
+        let mut ids = vec![];
+        loop {
+            let rows = conn.fetch("big", 100).await.unwrap();
+            if rows.is_empty() {
+                break;
+            }
+            assert!(rows.len() <= 100);
+            for mut row in rows {
+                ids.push(row.get(0).unwrap());
+            }
+        }
+        conn.commit().await.unwrap();
+        conn.do_close().await.unwrap();
+        assert_eq!(ids, (0..250).map(Value::I32).collect::<Vec<_>>());
+        assert_eq!(server.await.unwrap(), vec![100, 100, 50, 0]);
+    }
+}
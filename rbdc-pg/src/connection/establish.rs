@@ -2,11 +2,14 @@ use crate::connection::{sasl, stream::PgStream, tls, PgConnection};
 use crate::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
-use crate::options::PgConnectOptions;
+use crate::options::{PgChannelBinding, PgConnectOptions};
+use crate::query::PgQuery;
 use crate::types::Oid;
+use either::Either;
 use rbdc::common::StatementCache;
+use rbdc::db::Row;
 use rbdc::io::Decode;
-use rbdc::{err_protocol, Error};
+use rbdc::{err_protocol, Error, ErrorKind, ErrorKindExt};
 use std::collections::HashMap;
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
@@ -28,7 +31,7 @@ impl PgConnection {
             ("DateStyle", "ISO, MDY"),
             // Sets the client-side encoding (character set).
             // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
-            ("client_encoding", "UTF8"),
+            ("client_encoding", &options.client_encoding),
             // Sets the time zone for displaying and interpreting time stamps.
             ("TimeZone", "UTC"),
         ];
@@ -45,6 +48,12 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        // in milliseconds
+        let statement_timeout = options.statement_timeout.map(|v| v.as_millis().to_string());
+        if let Some(ref statement_timeout) = statement_timeout {
+            params.push(("statement_timeout", statement_timeout));
+        }
+
         stream
             .send(Startup {
                 username: Some(&options.username),
@@ -61,6 +70,7 @@ impl PgConnection {
         let mut process_id = 0;
         let mut secret_key = 0;
         let transaction_status;
+        let mut channel_bound = false;
 
         loop {
             let message = stream.recv().await?;
@@ -69,9 +79,14 @@ impl PgConnection {
                     Authentication::Ok => {
                         // the authentication exchange is successfully completed
                         // do nothing; no more information is required to continue
+                        if !channel_bound {
+                            check_channel_binding(options)?;
+                        }
                     }
 
                     Authentication::CleartextPassword => {
+                        check_channel_binding(options)?;
+
                         // The frontend must now send a [PasswordMessage] containing the
                         // password in clear-text form.
 
@@ -83,6 +98,8 @@ impl PgConnection {
                     }
 
                     Authentication::Md5Password(body) => {
+                        check_channel_binding(options)?;
+
                         // The frontend must now send a [PasswordMessage] containing the
                         // password (with user name) encrypted via MD5, then encrypted again
                         // using the 4-byte random salt specified in the
@@ -98,7 +115,7 @@ impl PgConnection {
                     }
 
                     Authentication::Sasl(body) => {
-                        sasl::authenticate(&mut stream, options, body).await?;
+                        channel_bound = sasl::authenticate(&mut stream, options, body).await?;
                     }
 
                     method => {
@@ -135,16 +152,111 @@ impl PgConnection {
             }
         }
 
-        Ok(PgConnection {
+        let mut conn = PgConnection {
             stream,
             process_id,
             secret_key,
+            options: options.clone(),
             transaction_status,
             pending_ready_for_query_count: 0,
             next_statement_id: Oid(1),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             cache_type_oid: HashMap::with_capacity(10),
             cache_type_info: HashMap::with_capacity(10),
-        })
+            money_frac_digits: 2,
+        };
+        if let Some(v) = conn.fetch_money_frac_digits().await {
+            conn.money_frac_digits = v;
+        }
+        Ok(conn)
+    }
+
+    // `SHOW lc_monetary` only names the locale, casting a money to numeric gives the
+    // fractional digits the server uses for it.
+    // `None` on servers without `MONEY`
+    async fn fetch_money_frac_digits(&mut self) -> Option<u32> {
+        let row = self
+            .fetch_optional(PgQuery {
+                statement: Either::Left("SELECT scale('0'::money::numeric)".to_string()),
+                arguments: vec![],
+                persistent: false,
+            })
+            .await;
+        let mut row = row.ok()??;
+        self.wait_until_ready().await.ok()?;
+        row.get(0).ok()?.as_u64().map(|v| v as u32)
+    }
+}
+
+// `channel_binding=require` only accepts SCRAM-SHA-256-PLUS
+fn check_channel_binding(options: &PgConnectOptions) -> Result<(), Error> {
+    if options.channel_binding == PgChannelBinding::Require {
+        return Err(Error::with_kind(
+            "channel binding is required, but the server authenticated the client without it"
+                .to_string(),
+            ErrorKind::Authentication,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::options::{PgConnectOptions, PgSslMode};
+    use rbdc::db::ConnectOptions;
+    use rbdc::{ErrorKind, ErrorKindExt};
+    use std::time::{Duration, Instant};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    // accepts one connection and reports the startup message, but never answers it
+    async fn silent_server() -> (u16, oneshot::Receiver<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            let mut startup = vec![0u8; len - 4];
+            socket.read_exact(&mut startup).await.unwrap();
+            let _ = tx.send(startup);
+            let _ = socket.read(&mut [0u8; 1]).await;
+        });
+        (port, rx)
+    }
+
+    fn options(port: u16) -> PgConnectOptions {
+        PgConnectOptions::new_without_pgpass()
+            .host("127.0.0.1")
+            .port(port)
+            .username("postgres")
+            .ssl_mode(PgSslMode::Disable)
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        let (port, _startup) = silent_server().await;
+        let options = options(port).connect_timeout(Duration::from_millis(100));
+        let start = Instant::now();
+        let e = options.connect().await.err().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(e.kind(), ErrorKind::Timeout, "{}", e);
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout_startup_param() {
+        let (port, startup) = silent_server().await;
+        let options = options(port)
+            .connect_timeout(Duration::from_millis(100))
+            .statement_timeout(Duration::from_secs(5));
+        assert!(options.connect().await.is_err());
+        let startup = startup.await.unwrap();
+        let expect = b"statement_timeout\x005000\0";
+        assert!(
+            startup.windows(expect.len()).any(|v| v == expect),
+            "{:?}",
+            String::from_utf8_lossy(&startup)
+        );
     }
 }
@@ -161,7 +161,7 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare<'a>(
+    pub(super) async fn get_or_prepare(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -307,6 +307,7 @@ impl PgConnection {
                             data,
                             format,
                             metadata: Arc::clone(&metadata),
+                            money_frac_digits: self.money_frac_digits,
                         };
 
                         r#yield!(Either::Right(row));
@@ -0,0 +1,181 @@
+use crate::connection::PgConnection;
+use futures_core::future::BoxFuture;
+use rbdc::db::{Connection, Row};
+use rbdc::Error;
+use rbs::Value;
+
+/// a column of a table, see [`PgConnection::list_columns`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// `information_schema.columns.data_type`, e.g. `integer`, `character varying`, `ARRAY`
+    pub data_type: String,
+    pub nullable: bool,
+    /// the default expression as text, e.g. `nextval('t_id_seq'::regclass)`
+    pub default: Option<String>,
+}
+
+/// an index of a table, see [`PgConnection::list_indexes`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexInfo {
+    pub name: String,
+    /// the indexed columns in index order, expression columns are left out
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub primary: bool,
+}
+
+const LIST_TABLES: &str = "SELECT table_name::text FROM information_schema.tables \
+     WHERE table_schema = COALESCE($1::text, current_schema()) AND table_type = 'BASE TABLE' \
+     ORDER BY table_name";
+
+const LIST_COLUMNS: &str = "SELECT column_name::text, data_type::text, is_nullable::text, \
+     column_default::text FROM information_schema.columns \
+     WHERE table_name = $1 AND table_schema = COALESCE($2::text, current_schema()) \
+     ORDER BY ordinal_position";
+
+const LIST_INDEXES: &str = "SELECT i.relname::text, ARRAY(SELECT a.attname::text \
+     FROM unnest(ix.indkey) WITH ORDINALITY AS k(attnum, n) \
+     JOIN pg_attribute a ON a.attrelid = ix.indrelid AND a.attnum = k.attnum \
+     ORDER BY k.n), ix.indisunique, ix.indisprimary \
+     FROM pg_index ix JOIN pg_class i ON i.oid = ix.indexrelid \
+     WHERE ix.indrelid = $1::regclass ORDER BY i.relname";
+
+/// diagnostic helpers reading the catalog, `schema: None` means `current_schema()`
+impl PgConnection {
+    /// the base tables (no views) of `schema`, sorted by name
+    pub fn list_tables(
+        &mut self,
+        schema: Option<&str>,
+    ) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        let params = vec![text_param(schema)];
+        Box::pin(async move {
+            let rows = self.get_rows(LIST_TABLES, params).await?;
+            rows.into_iter()
+                .map(|row| {
+                    let name = row_values(row)?.into_iter().next();
+                    Ok(name.and_then(Value::into_string).unwrap_or_default())
+                })
+                .collect()
+        })
+    }
+
+    /// the columns of `table` in `schema`, in table order
+    pub fn list_columns(
+        &mut self,
+        table: &str,
+        schema: Option<&str>,
+    ) -> BoxFuture<'_, Result<Vec<ColumnInfo>, Error>> {
+        let params = vec![Value::String(table.to_string()), text_param(schema)];
+        Box::pin(async move {
+            let rows = self.get_rows(LIST_COLUMNS, params).await?;
+            rows.into_iter()
+                .map(|row| Ok(column_info(row_values(row)?)))
+                .collect()
+        })
+    }
+
+    /// the indexes of `table` (which may be schema qualified, otherwise it is looked up
+    /// through the `search_path`), sorted by name
+    pub fn list_indexes(&mut self, table: &str) -> BoxFuture<'_, Result<Vec<IndexInfo>, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self.get_rows(LIST_INDEXES, params).await?;
+            rows.into_iter()
+                .map(|row| Ok(index_info(row_values(row)?)))
+                .collect()
+        })
+    }
+}
+
+fn text_param(v: Option<&str>) -> Value {
+    v.map(|v| Value::String(v.to_string()))
+        .unwrap_or(Value::Null)
+}
+
+fn row_values(mut row: Box<dyn Row>) -> Result<Vec<Value>, Error> {
+    let len = row.meta_data().column_len();
+    (0..len).map(|i| row.get(i)).collect()
+}
+
+fn column_info(row: Vec<Value>) -> ColumnInfo {
+    let mut row = row.into_iter();
+    let mut next = || row.next().and_then(Value::into_string);
+    ColumnInfo {
+        name: next().unwrap_or_default(),
+        data_type: next().unwrap_or_default(),
+        nullable: next().as_deref() == Some("YES"),
+        default: next(),
+    }
+}
+
+fn index_info(row: Vec<Value>) -> IndexInfo {
+    let mut row = row.into_iter();
+    IndexInfo {
+        name: row.next().and_then(Value::into_string).unwrap_or_default(),
+        columns: row
+            .next()
+            .and_then(Value::into_array)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(Value::into_string)
+            .collect(),
+        unique: row.next().and_then(|v| v.as_bool()).unwrap_or_default(),
+        primary: row.next().and_then(|v| v.as_bool()).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::introspect::{column_info, index_info, ColumnInfo, IndexInfo};
+    use rbs::Value;
+
+    #[test]
+    fn test_column_info() {
+        let c = column_info(vec![
+            Value::String("id".to_string()),
+            Value::String("integer".to_string()),
+            Value::String("NO".to_string()),
+            Value::String("nextval('t_id_seq'::regclass)".to_string()),
+        ]);
+        assert_eq!(
+            c,
+            ColumnInfo {
+                name: "id".to_string(),
+                data_type: "integer".to_string(),
+                nullable: false,
+                default: Some("nextval('t_id_seq'::regclass)".to_string()),
+            }
+        );
+        let c = column_info(vec![
+            Value::String("name".to_string()),
+            Value::String("text".to_string()),
+            Value::String("YES".to_string()),
+            Value::Null,
+        ]);
+        assert!(c.nullable);
+        assert_eq!(c.default, None);
+    }
+
+    #[test]
+    fn test_index_info() {
+        let i = index_info(vec![
+            Value::String("t_a_b_key".to_string()),
+            Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ]),
+            Value::Bool(true),
+            Value::Bool(false),
+        ]);
+        assert_eq!(
+            i,
+            IndexInfo {
+                name: "t_a_b_key".to_string(),
+                columns: vec!["a".to_string(), "b".to_string()],
+                unique: true,
+                primary: false,
+            }
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::driver::PgDriver;
 use crate::message::{
     Close, Message, MessageFormat, Query, ReadyForQuery, Terminate, TransactionStatus,
 };
+use crate::options::PgConnectOptions;
 use crate::query::PgQuery;
 use crate::query_result::PgQueryResult;
 use crate::row::PgRow;
@@ -16,17 +17,26 @@ use rbdc::common::StatementCache;
 use rbdc::db::{Connection, ExecResult, Placeholder, Row};
 use rbdc::ext::ustr::UStr;
 use rbdc::io::Decode;
-use rbdc::Error;
+use rbdc::{explain_text, normalize_server_version, Error, QueryLog};
 use rbs::Value;
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
+pub use self::introspect::{ColumnInfo, IndexInfo};
+pub use self::pipeline::{PgPipeline, PipelineResult};
 pub use self::stream::PgStream;
 
+mod batch;
+mod cancel;
+mod cursor;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod introspect;
+mod notify;
+mod pipeline;
 mod sasl;
 mod stream;
 mod tls;
@@ -40,14 +50,15 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // where cancel requests are sent
+    options: PgConnectOptions,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: Oid,
@@ -64,6 +75,9 @@ pub struct PgConnection {
 
     // current transaction status
     transaction_status: TransactionStatus,
+
+    // fractional digits of the server `lc_monetary`, the scale of a `MONEY` on the wire
+    money_frac_digits: u32,
 }
 
 impl PgConnection {
@@ -179,6 +193,80 @@ impl Connection for PgConnection {
         Box::pin(async { self.do_close().await })
     }
 
+    /// sends a `CancelRequest` once `timeout` elapses, so the statement stops on the server
+    fn exec_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        let token = self.cancel_token();
+        let f = self.exec(sql, params);
+        Box::pin(token.timeout(timeout, f))
+    }
+
+    /// sends a `CancelRequest` once `timeout` elapses, so the statement stops on the server
+    fn get_rows_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+        let token = self.cancel_token();
+        let f = self.get_rows(sql, params);
+        Box::pin(token.timeout(timeout, f))
+    }
+
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async move {
+            // reported by the server as a ParameterStatus during startup
+            if let Some(v) = self.stream.parameter_statuses.get("server_version") {
+                return Ok(Some(normalize_server_version(v)));
+            }
+            let mut rows = self.get_rows("SELECT version()", vec![]).await?;
+            let version = match rows.first_mut() {
+                Some(row) => row.get(0)?.into_string(),
+                None => None,
+            };
+            Ok(version.map(|v| normalize_server_version(&v)))
+        })
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self
+                .get_rows(
+                    "SELECT 1 FROM pg_tables WHERE schemaname = ANY(current_schemas(false)) AND tablename = ?",
+                    params,
+                )
+                .await?;
+            Ok(!rows.is_empty())
+        })
+    }
+
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        let sql = format!("EXPLAIN (FORMAT TEXT) {}", sql);
+        Box::pin(async move {
+            let rows = self.get_rows(&sql, params).await?;
+            explain_text(rows)
+        })
+    }
+
+    /// `DISCARD ALL`, after rolling back an open transaction (`DISCARD ALL` can not run inside one)
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+            if !matches!(self.transaction_status, TransactionStatus::Idle) {
+                self.exec("ROLLBACK", vec![]).await?;
+            }
+            self.exec("DISCARD ALL", vec![]).await?;
+            // the server deallocated every prepared statement
+            self.cache_statement.clear();
+            Ok(())
+        })
+    }
+
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         // By sending a comment we avoid an error if the connection was in the middle of a rowset
         self.exec("/* RBDC ping */", vec![]).map_ok(|_| ()).boxed()
@@ -189,8 +277,9 @@ impl Connection for PgConnection {
         sql: &str,
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Box<dyn Row>>, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = PgDriver {}.exchange(sql);
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
                     self.fetch_many(PgQuery {
@@ -226,14 +315,17 @@ impl Connection for PgConnection {
                 data.push(Box::new(x));
             }
             Ok(data)
-        })
+        });
+        QueryLog::wrap(log, f, |v| v.len() as u64)
     }
 
     fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = PgDriver {}.exchange(sql);
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
+                    self.stream.notices = Some(vec![]);
                     self.fetch_many(PgQuery {
                         statement: Either::Left(sql),
                         arguments: params,
@@ -245,6 +337,7 @@ impl Connection for PgConnection {
                         type_info.push(x.type_info());
                     }
                     let stmt = self.prepare_with(sql, &type_info).await?;
+                    self.stream.notices = Some(vec![]);
                     self.fetch_many(PgQuery {
                         statement: Either::Right(stmt),
                         arguments: params,
@@ -260,11 +353,58 @@ impl Connection for PgConnection {
                     })
                 })
                 .boxed();
-            let v: PgQueryResult = v.try_collect().boxed().await?;
-            return Ok(ExecResult {
-                rows_affected: v.rows_affected,
-                last_insert_id: Value::Null,
+            let v: Result<PgQueryResult, Error> = v.try_collect().boxed().await;
+            let messages = self.stream.notices.take().unwrap_or_default();
+            let v = v?;
+            return Ok(ExecResult::new(v.rows_affected, Value::Null).with_messages(messages));
+        });
+        QueryLog::wrap(log, f, |v| v.rows_affected)
+    }
+
+    /// `INSERT ... VALUES (?, ?)` is sent as one multi-row `VALUES (...), (...)` statement
+    /// (split when a statement would exceed 65535 bind parameters),
+    /// any other statement falls back to calling `exec` for every parameter set
+    fn execute_many(
+        &mut self,
+        sql: &str,
+        params_sets: Vec<Vec<Value>>,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        let sql = sql.to_string();
+        Box::pin(async move {
+            let mut result = ExecResult::default();
+            let per_row = params_sets.first().map(|v| v.len()).unwrap_or_default();
+            let tuple = batch::find_values_tuple(&sql).filter(|(open, close)| {
+                per_row != 0
+                    && sql[*open..*close].matches('?').count() == per_row
+                    && params_sets.iter().all(|v| v.len() == per_row)
             });
+            let tuple = match tuple {
+                Some(v) => v,
+                None => {
+                    for params in params_sets {
+                        let v = self.exec(&sql, params).await?;
+                        result.rows_affected += v.rows_affected;
+                        result.messages.extend(v.messages);
+                    }
+                    return Ok(result);
+                }
+            };
+            let rows_per_statement = batch::MAX_BIND_PARAMS / per_row;
+            let mut sets = params_sets.into_iter().peekable();
+            while sets.peek().is_some() {
+                let mut params = Vec::with_capacity(rows_per_statement * per_row);
+                let mut rows = 0;
+                for set in sets.by_ref().take(rows_per_statement) {
+                    params.extend(set);
+                    rows += 1;
+                }
+                let v = self
+                    .exec(&batch::repeat_values(&sql, tuple, rows), params)
+                    .await?;
+                result.rows_affected += v.rows_affected;
+                result.messages.extend(v.messages);
+            }
+            Ok(result)
         })
     }
 }
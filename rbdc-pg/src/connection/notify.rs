@@ -0,0 +1,158 @@
+use crate::connection::PgConnection;
+use futures_core::future::BoxFuture;
+use rbdc::db::Connection;
+use rbdc::Error;
+use rbs::Value;
+
+impl PgConnection {
+    /// send a notification on `channel` with `SELECT pg_notify($1, $2)`, every session
+    /// listening on the channel receives it with `payload` (empty when `None`).
+    ///
+    /// inside a transaction the notification is only delivered once it commits
+    pub fn notify(
+        &mut self,
+        channel: &str,
+        payload: Option<&str>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        let params = vec![
+            Value::String(channel.to_string()),
+            Value::String(payload.unwrap_or_default().to_string()),
+        ];
+        Box::pin(async move {
+            self.exec("SELECT pg_notify($1, $2)", params).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::PgConnection;
+    use crate::options::{PgConnectOptions, PgSslMode};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn message(format: u8, body: &[u8]) -> Vec<u8> {
+        let mut v = vec![format];
+        v.extend(&(body.len() as i32 + 4).to_be_bytes());
+        v.extend(body);
+        v
+    }
+
+    async fn read_message(socket: &mut TcpStream) -> (u8, Vec<u8>) {
+        let format = socket.read_u8().await.unwrap();
+        let len = socket.read_i32().await.unwrap() as usize;
+        let mut body = vec![0u8; len - 4];
+        socket.read_exact(&mut body).await.unwrap();
+        (format, body)
+    }
+
+    fn cstr(buf: &mut &[u8]) -> String {
+        let end = buf.iter().position(|v| *v == 0).unwrap();
+        let v = String::from_utf8(buf[..end].to_vec()).unwrap();
+        *buf = &buf[end + 1..];
+        v
+    }
+
+    fn i16(buf: &mut &[u8]) -> i16 {
+        let v = i16::from_be_bytes([buf[0], buf[1]]);
+        *buf = &buf[2..];
+        v
+    }
+
+    // the values of a `Bind` message, every one in binary format
+    fn bind_params(mut body: &[u8]) -> Vec<String> {
+        cstr(&mut body);
+        cstr(&mut body);
+        for _ in 0..i16(&mut body) {
+            assert_eq!(i16(&mut body), 1);
+        }
+        (0..i16(&mut body))
+            .map(|_| {
+                let len = i32::from_be_bytes(body[..4].try_into().unwrap()) as usize;
+                let v = String::from_utf8(body[4..4 + len].to_vec()).unwrap();
+                body = &body[4 + len..];
+                v
+            })
+            .collect()
+    }
+
+    // answers the startup, then a statement with two text parameters and no rows.
+    // reports the sql of every `Parse` and the values of every `Bind`
+    async fn server() -> (
+        u16,
+        tokio::task::JoinHandle<(Vec<String>, Vec<Vec<String>>)>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            socket.read_exact(&mut vec![0u8; len - 4]).await.unwrap();
+            let mut out = message(b'R', &0i32.to_be_bytes());
+            out.extend(message(b'K', &[0, 0, 0, 1, 0, 0, 0, 2]));
+            out.extend(message(b'Z', b"I"));
+            socket.write_all(&out).await.unwrap();
+
+            let mut sqls = vec![];
+            let mut binds = vec![];
+            let mut out = vec![];
+            loop {
+                match read_message(&mut socket).await {
+                    (b'P', body) => {
+                        let mut body = &body[..];
+                        cstr(&mut body);
+                        sqls.push(cstr(&mut body));
+                        out.extend(message(b'1', &[]));
+                        out.extend(message(b't', &[0, 2, 0, 0, 0, 25, 0, 0, 0, 25]));
+                    }
+                    (b'D', _) => out.extend(message(b'n', &[])),
+                    (b'B', body) => {
+                        binds.push(bind_params(&body));
+                        out.extend(message(b'2', &[]));
+                    }
+                    (b'E', _) => out.extend(message(b'C', b"SELECT 1\0")),
+                    (b'S', _) => {
+                        out.extend(message(b'Z', b"I"));
+                        socket.write_all(&out).await.unwrap();
+                        out.clear();
+                    }
+                    // the `lc_monetary` query of the startup, answered like a server without `MONEY`
+                    (b'Q', _) => {
+                        let error = b"SERROR\0C42704\0Mtype \"money\" does not exist\0\0";
+                        let mut out = message(b'E', error);
+                        out.extend(message(b'Z', b"I"));
+                        socket.write_all(&out).await.unwrap();
+                    }
+                    (b'X', _) => break,
+                    (format, _) => panic!("unexpected {}", format as char),
+                }
+            }
+            (sqls, binds)
+        });
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (port, server) = server().await;
+        let options = PgConnectOptions::new_without_pgpass()
+            .host("127.0.0.1")
+            .port(port)
+            .username("postgres")
+            .ssl_mode(PgSslMode::Disable);
+        let mut conn = PgConnection::establish(&options).await.unwrap();
+        conn.notify("chan", Some("hello")).await.unwrap();
+        conn.notify("chan", None).await.unwrap();
+        conn.do_close().await.unwrap();
+        let (sqls, binds) = server.await.unwrap();
+        assert_eq!(sqls, vec!["SELECT pg_notify($1, $2)".to_string()]);
+        assert_eq!(
+            binds,
+            vec![
+                vec!["chan".to_string(), "hello".to_string()],
+                vec!["chan".to_string(), String::new()],
+            ]
+        );
+    }
+}
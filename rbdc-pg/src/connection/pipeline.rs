@@ -0,0 +1,257 @@
+use crate::arguments::PgArguments;
+use crate::connection::PgConnection;
+use crate::driver::PgDriver;
+use crate::message::{self, Bind, CommandComplete, MessageFormat};
+use crate::row::PgRow;
+use crate::statement::PgStatementMetadata;
+use crate::types::{Oid, TypeInfo};
+use crate::value::PgValueFormat;
+use futures_core::future::BoxFuture;
+use rbdc::db::{ExecResult, Placeholder, Row};
+use rbdc::{err_protocol, Error};
+use rbs::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// the result of one query of a [`PgPipeline`]
+#[derive(Debug)]
+pub enum PipelineResult {
+    /// the query returns rows (`SELECT`, `... RETURNING`)
+    Rows(Vec<Box<dyn Row>>),
+    Exec(ExecResult),
+}
+
+/// queries sent to the server in one batch, without waiting for the result of each:
+/// ```rust,ignore
+/// let mut pipeline = conn.pipeline();
+/// for i in 0..50 {
+///     pipeline.add("insert into t (id) values (?)", vec![Value::I32(i)]);
+/// }
+/// let results = pipeline.execute(&mut conn).await?;
+/// ```
+/// every query runs in its own implicit transaction unless the pipeline runs inside `begin()`,
+/// the first failing query fails the pipeline and the server skips the queries after it
+#[derive(Debug, Default)]
+pub struct PgPipeline {
+    queries: Vec<(String, Vec<Value>)>,
+}
+
+impl PgConnection {
+    /// an empty [`PgPipeline`]
+    pub fn pipeline(&self) -> PgPipeline {
+        PgPipeline::default()
+    }
+}
+
+impl PgPipeline {
+    /// queue `sql`, placeholders are `?` or `$1` like [`rbdc::db::Connection::exec`]
+    pub fn add(&mut self, sql: &str, params: Vec<Value>) -> &mut Self {
+        self.queries.push((PgDriver {}.exchange(sql), params));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// prepare every statement that is not cached yet first (one round trip each),
+    /// then send all `Bind`/`Execute` messages with a single `Sync` and read the results in order
+    pub fn execute(
+        self,
+        conn: &mut PgConnection,
+    ) -> BoxFuture<'_, Result<Vec<PipelineResult>, Error>> {
+        Box::pin(async move {
+            if self.queries.is_empty() {
+                return Ok(vec![]);
+            }
+            conn.wait_until_ready().await?;
+
+            let mut prepared: HashMap<String, (Oid, Arc<PgStatementMetadata>)> = HashMap::new();
+            let mut portals = Vec::with_capacity(self.queries.len());
+            for (sql, params) in self.queries {
+                let (statement, metadata) = match prepared.get(&sql) {
+                    Some(v) => v.clone(),
+                    None => {
+                        let types: Vec<_> = params.iter().map(|x| x.type_info()).collect();
+                        // caching must not evict a statement an earlier query of this pipeline binds
+                        let store = conn.cache_statement.contains_key(&sql)
+                            || conn.cache_statement.len() < conn.cache_statement.capacity();
+                        let v = conn.get_or_prepare(&sql, &types, store, None).await?;
+                        prepared.insert(sql, v.clone());
+                        v
+                    }
+                };
+                let mut arguments = PgArguments::from_args(params)?;
+                arguments.apply_patches(conn, &metadata.parameters).await?;
+                portals.push((statement, metadata, arguments));
+            }
+            conn.wait_until_ready().await?;
+
+            for (statement, _, arguments) in &portals {
+                conn.stream.write(Bind {
+                    portal: None,
+                    statement: *statement,
+                    formats: &[PgValueFormat::Binary],
+                    num_params: arguments.types.len() as i16,
+                    params: &arguments.buffer,
+                    result_formats: &[PgValueFormat::Binary],
+                });
+                conn.stream.write(message::Execute {
+                    portal: None,
+                    limit: 0,
+                });
+            }
+            conn.write_sync();
+            conn.stream.flush().await?;
+
+            let mut results = Vec::with_capacity(portals.len());
+            for (i, (_, metadata, _)) in portals.iter().enumerate() {
+                let mut rows: Vec<Box<dyn Row>> = vec![];
+                let rows_affected = loop {
+                    let message = conn
+                        .stream
+                        .recv()
+                        .await
+                        .map_err(|e| Error::from(format!("pipeline query {}: {}", i, e)))?;
+                    match message.format {
+                        MessageFormat::BindComplete => {}
+                        MessageFormat::DataRow => rows.push(Box::new(PgRow {
+                            data: message.decode()?,
+                            format: PgValueFormat::Binary,
+                            metadata: Arc::clone(metadata),
+                            money_frac_digits: conn.money_frac_digits,
+                        })),
+                        MessageFormat::CommandComplete => {
+                            let cc: CommandComplete = message.decode()?;
+                            break cc.rows_affected();
+                        }
+                        MessageFormat::EmptyQueryResponse => break 0,
+                        format => {
+                            return Err(err_protocol!(
+                                "pipeline: unexpected message: {:?}",
+                                format
+                            ));
+                        }
+                    }
+                };
+                results.push(if metadata.columns.is_empty() {
+                    PipelineResult::Exec(ExecResult::new(rows_affected, Value::Null))
+                } else {
+                    PipelineResult::Rows(rows)
+                });
+            }
+            conn.wait_until_ready().await?;
+            Ok(results)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::pipeline::PipelineResult;
+    use crate::connection::PgConnection;
+    use crate::options::{PgConnectOptions, PgSslMode};
+    use rbs::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn message(format: u8, body: &[u8]) -> Vec<u8> {
+        let mut v = vec![format];
+        v.extend(&(body.len() as i32 + 4).to_be_bytes());
+        v.extend(body);
+        v
+    }
+
+    async fn read_message(socket: &mut TcpStream) -> (u8, Vec<u8>) {
+        let format = socket.read_u8().await.unwrap();
+        let len = socket.read_i32().await.unwrap() as usize;
+        let mut body = vec![0u8; len - 4];
+        socket.read_exact(&mut body).await.unwrap();
+        (format, body)
+    }
+
+    // answers the startup, then every batch up to a `Sync`: a `Parse` with a one int4 parameter,
+    // no row statement, and every `Execute` with `INSERT 0 1`.
+    // reports the number of `Execute` of each batch
+    async fn server() -> (u16, tokio::task::JoinHandle<Vec<usize>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            socket.read_exact(&mut vec![0u8; len - 4]).await.unwrap();
+            let mut out = message(b'R', &0i32.to_be_bytes());
+            out.extend(message(b'K', &[0, 0, 0, 1, 0, 0, 0, 2]));
+            out.extend(message(b'Z', b"I"));
+            socket.write_all(&out).await.unwrap();
+
+            let mut batches = vec![];
+            let mut out = vec![];
+            let mut executes = 0;
+            loop {
+                match read_message(&mut socket).await {
+                    (b'P', _) => {
+                        out.extend(message(b'1', &[]));
+                        out.extend(message(b't', &[0, 1, 0, 0, 0, 23]));
+                    }
+                    (b'D', _) => out.extend(message(b'n', &[])),
+                    (b'B', _) => out.extend(message(b'2', &[])),
+                    (b'E', _) => {
+                        executes += 1;
+                        out.extend(message(b'C', b"INSERT 0 1\0"));
+                    }
+                    (b'S', _) => {
+                        out.extend(message(b'Z', b"I"));
+                        socket.write_all(&out).await.unwrap();
+                        out.clear();
+                        batches.push(executes);
+                        executes = 0;
+                    }
+                    // the `lc_monetary` query of the startup, answered like a server without `MONEY`
+                    (b'Q', _) => {
+                        let error = b"SERROR\0C42704\0Mtype \"money\" does not exist\0\0";
+                        let mut out = message(b'E', error);
+                        out.extend(message(b'Z', b"I"));
+                        socket.write_all(&out).await.unwrap();
+                    }
+                    (b'X', _) => break,
+                    (format, _) => panic!("unexpected {}", format as char),
+                }
+            }
+            batches
+        });
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn test_pipeline() {
+        let (port, server) = server().await;
+        let options = PgConnectOptions::new_without_pgpass()
+            .host("127.0.0.1")
+            .port(port)
+            .username("postgres")
+            .ssl_mode(PgSslMode::Disable);
+        let mut conn = PgConnection::establish(&options).await.unwrap();
+        let mut pipeline = conn.pipeline();
+        for i in 0..50 {
+            pipeline.add("insert into t (id) values (?)", vec![Value::I32(i)]);
+        }
+        assert_eq!(pipeline.len(), 50);
+        let results = pipeline.execute(&mut conn).await.unwrap();
+        assert_eq!(results.len(), 50);
+        for v in results {
+            match v {
+                PipelineResult::Exec(v) => assert_eq!(v.rows_affected, 1),
+                v => panic!("{:?}", v),
+            }
+        }
+        assert!(conn.pipeline().execute(&mut conn).await.unwrap().is_empty());
+        conn.do_close().await.unwrap();
+        // one batch to prepare the statement, then all 50 inserts in one batch
+        assert_eq!(server.await.unwrap(), vec![0, 50]);
+    }
+}
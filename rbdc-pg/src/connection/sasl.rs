@@ -2,26 +2,31 @@ use crate::connection::stream::PgStream;
 use crate::message::{
     Authentication, AuthenticationSasl, MessageFormat, SaslInitialResponse, SaslResponse,
 };
-use crate::options::PgConnectOptions;
+use crate::options::{PgChannelBinding, PgConnectOptions};
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use hmac::{Hmac, Mac};
 use rand::Rng;
-use rbdc::{err_protocol, Error};
-use sha2::{Digest, Sha256};
+use rbdc::{err_protocol, Error, ErrorKind, ErrorKindExt};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use stringprep::saslprep;
 
+// client does not support channel binding
 const GS2_HEADER: &str = "n,,";
+// client supports channel binding but thinks the server does not
+const GS2_HEADER_Y: &str = "y,,";
+const GS2_HEADER_PLUS: &str = "p=tls-server-end-point,,";
 const CHANNEL_ATTR: &str = "c";
 const USERNAME_ATTR: &str = "n";
 const CLIENT_PROOF_ATTR: &str = "p";
 const NONCE_ATTR: &str = "r";
 
+/// returns whether channel binding (`SCRAM-SHA-256-PLUS`) was used
 pub(crate) async fn authenticate(
     stream: &mut PgStream,
     options: &PgConnectOptions,
     data: AuthenticationSasl,
-) -> Result<(), Error> {
+) -> Result<bool, Error> {
     let mut has_sasl = false;
     let mut has_sasl_plus = false;
     let mut unknown = Vec::with_capacity(10);
@@ -49,8 +54,18 @@ pub(crate) async fn authenticate(
         ));
     }
 
-    // channel-binding = "c=" base64
-    let channel_binding = format!("{}={}", CHANNEL_ATTR, STANDARD.encode(GS2_HEADER));
+    let (gs2_header, cb_data) = select_channel_binding(
+        options.channel_binding,
+        has_sasl_plus,
+        stream.is_tls(),
+        stream.peer_certificate(),
+    )?;
+    let plus = gs2_header == GS2_HEADER_PLUS;
+
+    // channel-binding = "c=" base64(gs2-header [cbind-data])
+    let mut cbind_input = gs2_header.as_bytes().to_vec();
+    cbind_input.extend_from_slice(&cb_data);
+    let channel_binding = format!("{}={}", CHANNEL_ATTR, STANDARD.encode(&cbind_input));
 
     // "n=" saslname ;; Usernames are prepared using SASLprep.
     let username = format!("{}={}", USERNAME_ATTR, options.username);
@@ -69,14 +84,14 @@ pub(crate) async fn authenticate(
 
     let client_first_message = format!(
         "{gs2_header}{client_first_message_bare}",
-        gs2_header = GS2_HEADER,
+        gs2_header = gs2_header,
         client_first_message_bare = client_first_message_bare
     );
 
     stream
         .send(SaslInitialResponse {
             response: &client_first_message,
-            plus: false,
+            plus,
         })
         .await?;
 
@@ -167,7 +182,86 @@ pub(crate) async fn authenticate(
     // authentication is only considered valid if this verification passes
     mac.verify_slice(&data.verifier).map_err(Error::protocol)?;
 
-    Ok(())
+    Ok(plus)
+}
+
+// the gs2 header and `tls-server-end-point` channel binding data to use
+fn select_channel_binding(
+    mode: PgChannelBinding,
+    has_sasl_plus: bool,
+    is_tls: bool,
+    certificate: Option<Vec<u8>>,
+) -> Result<(&'static str, Vec<u8>), Error> {
+    if mode == PgChannelBinding::Disable {
+        return Ok((GS2_HEADER, vec![]));
+    }
+    match certificate {
+        Some(cert) if has_sasl_plus => Ok((GS2_HEADER_PLUS, tls_server_end_point(&cert))),
+        _ if mode == PgChannelBinding::Require => Err(Error::with_kind(
+            if !is_tls {
+                "channel binding is required, but the connection is not SSL".to_string()
+            } else if !has_sasl_plus {
+                "channel binding is required, but the server does not support SCRAM-SHA-256-PLUS"
+                    .to_string()
+            } else {
+                "channel binding is required, but the server certificate is unavailable".to_string()
+            },
+            ErrorKind::Authentication,
+        )),
+        _ if is_tls => Ok((GS2_HEADER_Y, vec![])),
+        _ => Ok((GS2_HEADER, vec![])),
+    }
+}
+
+// RFC 5929 tls-server-end-point: hash of the DER server certificate, using the hash
+// of its signature algorithm, or SHA-256 when that is MD5/SHA-1 (or unknown)
+fn tls_server_end_point(cert: &[u8]) -> Vec<u8> {
+    // sha384WithRSAEncryption, ecdsa-with-SHA384
+    const SHA384: [&[u8]; 2] = [
+        &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C],
+        &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03],
+    ];
+    // sha512WithRSAEncryption, ecdsa-with-SHA512
+    const SHA512: [&[u8]; 2] = [
+        &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0D],
+        &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x04],
+    ];
+    match signature_algorithm(cert) {
+        Some(oid) if SHA384.contains(&oid) => Sha384::digest(cert).to_vec(),
+        Some(oid) if SHA512.contains(&oid) => Sha512::digest(cert).to_vec(),
+        _ => Sha256::digest(cert).to_vec(),
+    }
+}
+
+// Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm SEQUENCE { OID, .. }, .. }
+fn signature_algorithm(cert: &[u8]) -> Option<&[u8]> {
+    let (_, cert, _) = der_read(cert)?;
+    let (_, _tbs, rest) = der_read(cert)?;
+    let (_, algorithm, _) = der_read(rest)?;
+    let (tag, oid, _) = der_read(algorithm)?;
+    // OBJECT IDENTIFIER
+    (tag == 0x06).then_some(oid)
+}
+
+// (tag, content, rest) of one DER element
+fn der_read(buf: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, buf) = buf.split_first()?;
+    let (&len, mut buf) = buf.split_first()?;
+    let len = if len & 0x80 == 0 {
+        len as usize
+    } else {
+        let n = (len & 0x7F) as usize;
+        if n == 0 || n > 4 || buf.len() < n {
+            return None;
+        }
+        let len = buf[..n].iter().fold(0usize, |v, b| (v << 8) | *b as usize);
+        buf = &buf[n..];
+        len
+    };
+    if buf.len() < len {
+        return None;
+    }
+    Some((tag, &buf[..len], &buf[len..]))
 }
 
 // nonce is a sequence of random printable bytes
@@ -216,3 +310,76 @@ fn hi<'a>(s: &'a str, salt: &'a [u8], iter_count: u32) -> Result<[u8; 32], Error
 
     Ok(hi.into())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::connection::sasl::{
+        select_channel_binding, signature_algorithm, tls_server_end_point, GS2_HEADER,
+        GS2_HEADER_PLUS, GS2_HEADER_Y,
+    };
+    use crate::options::PgChannelBinding;
+    use rbdc::{ErrorKind, ErrorKindExt};
+    use sha2::{Digest, Sha256, Sha384};
+
+    // SEQUENCE { SEQUENCE { tbs }, SEQUENCE { OID, NULL }, BIT STRING }
+    fn cert(oid: &[u8]) -> Vec<u8> {
+        let mut algorithm = vec![0x06, oid.len() as u8];
+        algorithm.extend_from_slice(oid);
+        algorithm.extend_from_slice(&[0x05, 0x00]);
+        let mut body = vec![0x30, 0x03, 0x02, 0x01, 0x02];
+        body.push(0x30);
+        body.push(algorithm.len() as u8);
+        body.extend_from_slice(&algorithm);
+        body.extend_from_slice(&[0x03, 0x02, 0x00, 0xFF]);
+        let mut cert = vec![0x30, 0x81, body.len() as u8];
+        cert.extend_from_slice(&body);
+        cert
+    }
+
+    #[test]
+    fn test_tls_server_end_point() {
+        let sha256_rsa = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+        let sha384_ecdsa = [0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03];
+        let sha1_rsa = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05];
+
+        let c = cert(&sha256_rsa);
+        assert_eq!(signature_algorithm(&c), Some(&sha256_rsa[..]));
+        assert_eq!(tls_server_end_point(&c), Sha256::digest(&c).to_vec());
+
+        let c = cert(&sha384_ecdsa);
+        assert_eq!(tls_server_end_point(&c), Sha384::digest(&c).to_vec());
+
+        let c = cert(&sha1_rsa);
+        assert_eq!(tls_server_end_point(&c), Sha256::digest(&c).to_vec());
+
+        assert_eq!(signature_algorithm(&[0x30, 0x05, 0x30]), None);
+        assert_eq!(tls_server_end_point(b"x"), Sha256::digest(b"x").to_vec());
+    }
+
+    #[test]
+    fn test_select_channel_binding() {
+        let c = cert(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B]);
+        let select = |mode, plus, tls, cert: Option<&Vec<u8>>| {
+            select_channel_binding(mode, plus, tls, cert.cloned())
+        };
+
+        let (header, data) = select(PgChannelBinding::Prefer, true, true, Some(&c)).unwrap();
+        assert_eq!(header, GS2_HEADER_PLUS);
+        assert_eq!(data, Sha256::digest(&c).to_vec());
+
+        let (header, _) = select(PgChannelBinding::Prefer, false, true, Some(&c)).unwrap();
+        assert_eq!(header, GS2_HEADER_Y);
+        let (header, _) = select(PgChannelBinding::Prefer, true, false, None).unwrap();
+        assert_eq!(header, GS2_HEADER);
+        let (header, data) = select(PgChannelBinding::Disable, true, true, Some(&c)).unwrap();
+        assert_eq!((header, data), (GS2_HEADER, vec![]));
+
+        let (header, _) = select(PgChannelBinding::Require, true, true, Some(&c)).unwrap();
+        assert_eq!(header, GS2_HEADER_PLUS);
+        let e = select(PgChannelBinding::Require, true, false, None).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Authentication);
+        assert!(e.to_string().contains("not SSL"));
+        let e = select(PgChannelBinding::Require, false, true, Some(&c)).unwrap_err();
+        assert!(e.to_string().contains("SCRAM-SHA-256-PLUS"));
+    }
+}
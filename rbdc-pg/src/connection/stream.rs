@@ -10,7 +10,7 @@ use rbdc::err_protocol;
 
 use crate::message::*;
 use crate::options::PgConnectOptions;
-use rbdc::error::Error;
+use rbdc::error::{Error, ErrorKind, ErrorKindExt};
 use rbdc::io::{BufStream, Decode, Encode};
 use rbdc::net::{MaybeTlsStream, Socket};
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
@@ -30,6 +30,10 @@ pub struct PgStream {
     // re-used for query execution in-between receiving messages
     pub(crate) notifications: Option<UnboundedSender<Notification>>,
 
+    // buffer of notice messages raised while executing a statement
+    // this is only set for the duration of `Connection::exec`
+    pub(crate) notices: Option<Vec<String>>,
+
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
@@ -42,11 +46,16 @@ impl PgStream {
             None => Socket::connect_tcp(&options.host, options.port).await?,
         };
 
+        if let Socket::Tcp(ref stream) = socket {
+            set_keepalive(stream, options)?;
+        }
+
         let inner = BufStream::new(MaybeTlsStream::Raw(socket));
 
         Ok(Self {
             inner,
             notifications: None,
+            notices: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
         })
@@ -100,7 +109,9 @@ impl PgStream {
             match message.format {
                 MessageFormat::ErrorResponse => {
                     // An error returned from the database server.
-                    return Err(Error::from(format!("db:{:?}", message.decode::<Notice>()?)));
+                    let notice: Notice = message.decode()?;
+                    let kind = ErrorKind::from_sql_state(notice.code());
+                    return Err(Error::with_kind(format!("db:{:?}", notice), kind));
                 }
 
                 MessageFormat::NotificationResponse => {
@@ -137,6 +148,10 @@ impl PgStream {
 
                     let notice: Notice = message.decode()?;
 
+                    if let Some(notices) = &mut self.notices {
+                        notices.push(notice.message().to_owned());
+                    }
+
                     let lvl = match notice.severity() {
                         PgSeverity::Fatal | PgSeverity::Panic | PgSeverity::Error => Level::Error,
                         PgSeverity::Warning => Level::Warn,
@@ -170,6 +185,22 @@ impl PgStream {
     }
 }
 
+// SO_KEEPALIVE with TCP_KEEPIDLE/TCP_KEEPINTVL, when either is configured
+fn set_keepalive(stream: &rbdc::rt::TcpStream, options: &PgConnectOptions) -> Result<(), Error> {
+    if options.keepalive_idle.is_none() && options.keepalive_interval.is_none() {
+        return Ok(());
+    }
+    let mut keepalive = socket2::TcpKeepalive::new();
+    if let Some(idle) = options.keepalive_idle {
+        keepalive = keepalive.with_time(idle);
+    }
+    if let Some(interval) = options.keepalive_interval {
+        keepalive = keepalive.with_interval(interval);
+    }
+    socket2::SockRef::from(stream).set_tcp_keepalive(&keepalive)?;
+    Ok(())
+}
+
 impl Deref for PgStream {
     type Target = BufStream<MaybeTlsStream<Socket>>;
 
@@ -249,4 +280,41 @@ mod tests {
         // unknown
         assert_eq!(parse_server_version("unknown"), None);
     }
+
+    #[tokio::test]
+    async fn test_keepalive() {
+        use super::PgStream;
+        use crate::options::PgConnectOptions;
+        use rbdc::net::Socket;
+        use std::time::Duration;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _socket = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        let options = PgConnectOptions::new_without_pgpass()
+            .host("127.0.0.1")
+            .port(port);
+
+        let stream = PgStream::connect(&options).await.unwrap();
+        let socket: &Socket = &stream;
+        let Socket::Tcp(tcp) = socket else { panic!() };
+        assert!(!socket2::SockRef::from(tcp).keepalive().unwrap());
+
+        let options = options
+            .keepalive_idle(Duration::from_secs(60))
+            .keepalive_interval(Duration::from_secs(10));
+        let stream = PgStream::connect(&options).await.unwrap();
+        let socket: &Socket = &stream;
+        let Socket::Tcp(tcp) = socket else { panic!() };
+        let sock = socket2::SockRef::from(tcp);
+        assert!(sock.keepalive().unwrap());
+        assert_eq!(sock.tcp_keepalive_time().unwrap(), Duration::from_secs(60));
+        assert_eq!(
+            sock.tcp_keepalive_interval().unwrap(),
+            Duration::from_secs(10)
+        );
+    }
 }
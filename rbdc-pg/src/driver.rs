@@ -20,7 +20,7 @@ impl Driver for PgDriver {
                 let conn = opt.connect().await?;
                 Ok(Box::new(conn) as Box<dyn Connection>)
             } else {
-                Err(Error::from("downcast_ref failure"))
+                Err(Error::from("expected PgConnectOptions"))
             }
         })
     }
@@ -28,8 +28,9 @@ impl Driver for PgDriver {
         &'a self,
         opt: &'a dyn ConnectOptions,
     ) -> BoxFuture<'a,Result<Box<dyn Connection>, Error>> {
-        let opt: &PgConnectOptions = opt.downcast_ref().unwrap();
+        let opt = opt.downcast_ref::<PgConnectOptions>();
         Box::pin(async move {
+            let opt = opt.ok_or_else(|| Error::from("expected PgConnectOptions"))?;
             let conn = opt.connect().await?;
             Ok(conn)
         })
@@ -0,0 +1,31 @@
+use rbdc::io::Encode;
+
+/// sent on a new connection to cancel the statement running on the backend `process_id`
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl Encode<'_> for CancelRequest {
+    #[inline]
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.extend(&16_u32.to_be_bytes());
+        buf.extend(&(((1234 << 16) | 5678) as u32).to_be_bytes());
+        buf.extend(&self.process_id.to_be_bytes());
+        buf.extend(&self.secret_key.to_be_bytes());
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    const EXPECTED: &[u8] = b"\x00\x00\x00\x10\x04\xd2\x16\x2e\x00\x00\x00\x07\x00\x00\x00\x2a";
+
+    let mut buf = Vec::new();
+    CancelRequest {
+        process_id: 7,
+        secret_key: 42,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
@@ -3,6 +3,7 @@ use bytes::Bytes;
 mod authentication;
 mod backend_key_data;
 mod bind;
+mod cancel_request;
 mod close;
 mod command_complete;
 mod copy;
@@ -28,6 +29,7 @@ mod terminate;
 pub use authentication::{Authentication, AuthenticationSasl};
 pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
+pub use cancel_request::CancelRequest;
 pub use close::Close;
 pub use command_complete::CommandComplete;
 pub use copy::{CopyData, CopyDone, CopyFail, CopyResponse};
@@ -1,4 +1,5 @@
 use crate::statement::PgStatementMetadata;
+use rbdc::db::{ColumnInfo, MetaData};
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -7,7 +8,7 @@ pub struct PgMetaData {
     pub metadata: Arc<PgStatementMetadata>,
 }
 
-impl rbdc::db::MetaData for PgMetaData {
+impl MetaData for PgMetaData {
     fn column_len(&self) -> usize {
         self.metadata.columns.len()
     }
@@ -24,4 +25,18 @@ impl rbdc::db::MetaData for PgMetaData {
     fn column_type(&self, i: usize) -> String {
         self.metadata.columns[i].name.to_string()
     }
+
+    fn column_ordinal(&self, name: &str) -> Option<usize> {
+        self.metadata.column_names.get(name).copied()
+    }
+
+    fn columns(&self) -> Vec<ColumnInfo> {
+        (0..self.column_len())
+            .map(|i| ColumnInfo {
+                name: self.metadata.columns[i].name.to_string(),
+                column_type: self.column_type(i),
+                nullable: None,
+            })
+            .collect()
+    }
 }
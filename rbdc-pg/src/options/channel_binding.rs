@@ -0,0 +1,47 @@
+use rbdc::Error;
+use std::str::FromStr;
+
+/// Options for SCRAM channel binding (`SCRAM-SHA-256-PLUS`) over SSL connections.
+///
+/// It is used by the [`channel_binding`](super::PgConnectOptions::channel_binding) method.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum PgChannelBinding {
+    /// Never use channel binding.
+    Disable,
+
+    /// Use channel binding if the connection is SSL and the server offers `SCRAM-SHA-256-PLUS`.
+    #[default]
+    Prefer,
+
+    /// Fail the connection unless channel binding is used.
+    Require,
+}
+
+impl PgChannelBinding {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            PgChannelBinding::Disable => "disable",
+            PgChannelBinding::Prefer => "prefer",
+            PgChannelBinding::Require => "require",
+        }
+    }
+}
+
+impl FromStr for PgChannelBinding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "disable" => PgChannelBinding::Disable,
+            "prefer" => PgChannelBinding::Prefer,
+            "require" => PgChannelBinding::Require,
+
+            _ => {
+                return Err(Error::from(format!(
+                    "unknown value {:?} for `channel_binding`",
+                    s
+                )));
+            }
+        })
+    }
+}
@@ -3,20 +3,160 @@ use crate::options::PgConnectOptions;
 use futures_core::future::BoxFuture;
 use rbdc::db::{ConnectOptions, Connection};
 use rbdc::error::Error;
+use rbdc::net::CertificateInput;
+use rbdc::{ErrorKind, ErrorKindExt};
 use std::str::FromStr;
+use url::Url;
 
 impl ConnectOptions for PgConnectOptions {
     fn connect(&self) -> BoxFuture<Result<Box<dyn Connection>, Error>> {
         Box::pin(async move {
-            let v = PgConnection::establish(self)
-                .await
-                .map_err(|e| Error::from(e.to_string()))?;
+            let v = match self.connect_timeout {
+                None => PgConnection::establish(self).await,
+                Some(timeout) => rbdc::rt::timeout(timeout, PgConnection::establish(self))
+                    .await
+                    .map_err(|_| {
+                        Error::with_kind(
+                            format!("connect timed out after {:?}", timeout),
+                            ErrorKind::Timeout,
+                        )
+                    })?,
+            }?;
             Ok(Box::new(v) as Box<dyn Connection>)
         })
     }
 
+    fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+        Ok(Box::new(self.clone()))
+    }
+
     fn set_uri(&mut self, arg: &str) -> Result<(), Error> {
         *self = PgConnectOptions::from_str(arg).map_err(|e| Error::from(e.to_string()))?;
         Ok(())
     }
+
+    fn to_url(&self) -> Result<String, Error> {
+        let mut url = Url::parse("postgres://localhost").map_err(|e| Error::from(e.to_string()))?;
+        let err = |_| Error::from("invalid host/username/password for url");
+        url.set_host(Some(&self.host))
+            .map_err(|e| Error::from(e.to_string()))?;
+        url.set_port(Some(self.port)).map_err(err)?;
+        url.set_username(&self.username).map_err(err)?;
+        url.set_password(self.password.as_deref()).map_err(err)?;
+        if let Some(database) = &self.database {
+            url.set_path(database);
+        }
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(socket) = &self.socket {
+                query.append_pair("host", &socket.to_string_lossy());
+            }
+            query.append_pair("sslmode", self.ssl_mode.as_str());
+            if let Some(cert) = &self.ssl_root_cert {
+                query.append_pair(
+                    "sslrootcert",
+                    &match cert {
+                        CertificateInput::Inline(v) => String::from_utf8_lossy(v).to_string(),
+                        CertificateInput::File(v) => v.to_string_lossy().to_string(),
+                    },
+                );
+            }
+            query.append_pair("channel_binding", self.channel_binding.as_str());
+            query.append_pair(
+                "statement-cache-capacity",
+                &self.statement_cache_capacity.to_string(),
+            );
+            if let Some(name) = &self.application_name {
+                query.append_pair("application_name", name);
+            }
+            if let Some(options) = &self.options {
+                query.append_pair("options", options);
+            }
+            let default = PgConnectOptions::new_without_pgpass();
+            if self.extra_float_digits != default.extra_float_digits {
+                query.append_pair(
+                    "extra_float_digits",
+                    self.extra_float_digits.as_deref().unwrap_or_default(),
+                );
+            }
+            if self.client_encoding != default.client_encoding {
+                query.append_pair("client_encoding", &self.client_encoding);
+            }
+            if let Some(timeout) = self.connect_timeout {
+                query.append_pair("connect_timeout", &format!("{}ms", timeout.as_millis()));
+            }
+            if let Some(timeout) = self.statement_timeout {
+                query.append_pair("statement_timeout", &format!("{}ms", timeout.as_millis()));
+            }
+            if let Some(idle) = self.keepalive_idle {
+                query.append_pair("keepalive_idle", &format!("{}ms", idle.as_millis()));
+            }
+            if let Some(interval) = self.keepalive_interval {
+                query.append_pair("keepalive_interval", &format!("{}ms", interval.as_millis()));
+            }
+        }
+        Ok(url.to_string())
+    }
+
+    fn merge(&mut self, other: &dyn ConnectOptions) {
+        let other = match other.downcast_ref::<PgConnectOptions>() {
+            Some(v) => v,
+            None => return,
+        };
+        let default = PgConnectOptions::new_without_pgpass();
+        if other.host != default.host {
+            self.host = other.host.clone();
+        }
+        if other.port != default.port {
+            self.port = other.port;
+        }
+        if other.socket.is_some() {
+            self.socket = other.socket.clone();
+        }
+        if other.username != default.username {
+            self.username = other.username.clone();
+        }
+        if other.password.is_some() && other.password != default.password {
+            self.password = other.password.clone();
+        }
+        if other.database.is_some() && other.database != default.database {
+            self.database = other.database.clone();
+        }
+        if other.ssl_mode != default.ssl_mode {
+            self.ssl_mode = other.ssl_mode;
+        }
+        if other.ssl_root_cert.is_some() {
+            self.ssl_root_cert = other.ssl_root_cert.clone();
+        }
+        if other.channel_binding != default.channel_binding {
+            self.channel_binding = other.channel_binding;
+        }
+        if other.statement_cache_capacity != default.statement_cache_capacity {
+            self.statement_cache_capacity = other.statement_cache_capacity;
+        }
+        if other.application_name.is_some() && other.application_name != default.application_name {
+            self.application_name = other.application_name.clone();
+        }
+        if other.extra_float_digits != default.extra_float_digits {
+            self.extra_float_digits = other.extra_float_digits.clone();
+        }
+        if other.client_encoding != default.client_encoding {
+            self.client_encoding = other.client_encoding.clone();
+        }
+        if other.options.is_some() && other.options != default.options {
+            self.options = other.options.clone();
+        }
+        if other.connect_timeout.is_some() {
+            self.connect_timeout = other.connect_timeout;
+        }
+        if other.statement_timeout.is_some() {
+            self.statement_timeout = other.statement_timeout;
+        }
+        if other.keepalive_idle.is_some() {
+            self.keepalive_idle = other.keepalive_idle;
+        }
+        if other.keepalive_interval.is_some() {
+            self.keepalive_interval = other.keepalive_interval;
+        }
+    }
 }
@@ -3,11 +3,14 @@ use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{Display, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 mod connect;
 mod parse;
 mod pgpass;
+mod channel_binding;
 mod ssl_mode;
+pub use channel_binding::PgChannelBinding;
 pub use ssl_mode::PgSslMode;
 
 /// Options and flags which can be used to configure a PostgreSQL connection.
@@ -27,6 +30,7 @@ pub use ssl_mode::PgSslMode;
 /// |---------|-------|-----------|
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSslMode`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
+/// | `channel_binding` | `prefer` | Whether SCRAM channel binding (`SCRAM-SHA-256-PLUS`) is used over SSL. See [`PgChannelBinding`]. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 /// | `hostaddr` | `None` | Same as `host`, but only accepts IP addresses. |
@@ -36,6 +40,12 @@ pub use ssl_mode::PgSslMode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `extra_float_digits` | `3` | See [`PgConnectOptions::extra_float_digits`], an empty value does not send it. |
+/// | `client_encoding` | `UTF8` | See [`PgConnectOptions::client_encoding`]. |
+/// | `connect_timeout` | `None` | Maximum time to wait while connecting, such as `5s` or `500ms` (a bare number is seconds). |
+/// | `statement_timeout` | `None` | Abort any statement that runs longer than this, such as `30s` (a bare number is seconds). |
+/// | `keepalive_idle` | `None` | Enables TCP keepalive, idle time before the first probe, such as `60s`. |
+/// | `keepalive_interval` | `None` | Enables TCP keepalive, time between probes, such as `10s`. |
 ///
 /// The URI scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URI parts is optional.
@@ -59,10 +69,16 @@ pub struct PgConnectOptions {
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: PgSslMode,
     pub(crate) ssl_root_cert: Option<CertificateInput>,
+    pub(crate) channel_binding: PgChannelBinding,
     pub(crate) statement_cache_capacity: usize,
     pub(crate) application_name: Option<String>,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
+    pub(crate) client_encoding: Cow<'static, str>,
     pub(crate) options: Option<String>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) statement_timeout: Option<Duration>,
+    pub(crate) keepalive_idle: Option<Duration>,
+    pub(crate) keepalive_interval: Option<Duration>,
 }
 
 impl Default for PgConnectOptions {
@@ -85,6 +101,7 @@ impl PgConnectOptions {
     ///  * `PGDATABASE`
     ///  * `PGSSLROOTCERT`
     ///  * `PGSSLMODE`
+    ///  * `PGCHANNELBINDING`
     ///  * `PGAPPNAME`
     ///
     pub fn new() -> Self {
@@ -115,10 +132,19 @@ impl PgConnectOptions {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
+            channel_binding: var("PGCHANNELBINDING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default(),
             statement_cache_capacity: 100,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("3".into()),
+            client_encoding: "UTF8".into(),
             options: var("PGOPTIONS").ok(),
+            connect_timeout: None,
+            statement_timeout: None,
+            keepalive_idle: None,
+            keepalive_interval: None,
         }
     }
 
@@ -281,6 +307,24 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether SCRAM channel binding (`SCRAM-SHA-256-PLUS`) is used over SSL connections.
+    ///
+    /// By default, it is used when the connection is SSL and the server offers it.
+    /// With [`PgChannelBinding::Require`] the connection fails if it can not be used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rbdc_pg::options::{PgChannelBinding, PgSslMode, PgConnectOptions};
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::Require)
+    ///     .channel_binding(PgChannelBinding::Require);
+    /// ```
+    pub fn channel_binding(mut self, mode: PgChannelBinding) -> Self {
+        self.channel_binding = mode;
+        self
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
@@ -292,6 +336,63 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the maximum time to wait for the connection to be established
+    /// (including TLS and authentication). Defaults to `None`, waiting indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use rbdc_pg::options::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .connect_timeout(Duration::from_secs(5));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the server side `statement_timeout` of the session, statements running longer
+    /// are aborted by the server with an error. Defaults to `None`, the server setting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use rbdc_pg::options::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .statement_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables TCP keepalive (`SO_KEEPALIVE`) and sets how long the connection stays idle
+    /// before the first probe (`TCP_KEEPIDLE`), so firewalls and NAT do not silently drop
+    /// idle connections. Disabled by default, ignored for unix domain sockets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use rbdc_pg::options::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .keepalive_idle(Duration::from_secs(60))
+    ///     .keepalive_interval(Duration::from_secs(10));
+    /// ```
+    pub fn keepalive_idle(mut self, idle: Duration) -> Self {
+        self.keepalive_idle = Some(idle);
+        self
+    }
+
+    /// Enables TCP keepalive (`SO_KEEPALIVE`) and sets the time between probes (`TCP_KEEPINTVL`).
+    /// Disabled by default, see [`keepalive_idle`](Self::keepalive_idle).
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -359,6 +460,23 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the `client_encoding` sent at connection start, `UTF8` by default.
+    ///
+    /// text values are always decoded as UTF-8, so another encoding only suits databases
+    /// whose text is ASCII, non-ASCII text in it fails to decode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rbdc_pg::options::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .client_encoding("SQL_ASCII");
+    /// ```
+    pub fn client_encoding(mut self, client_encoding: &str) -> Self {
+        self.client_encoding = client_encoding.to_owned().into();
+        self
+    }
+
     /// Set additional startup options for the connection as a list of key-value pairs.
     ///
     /// # Example
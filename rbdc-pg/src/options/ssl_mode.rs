@@ -34,6 +34,19 @@ impl Default for PgSslMode {
     }
 }
 
+impl PgSslMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            PgSslMode::Disable => "disable",
+            PgSslMode::Allow => "allow",
+            PgSslMode::Prefer => "prefer",
+            PgSslMode::Require => "require",
+            PgSslMode::VerifyCa => "verify-ca",
+            PgSslMode::VerifyFull => "verify-full",
+        }
+    }
+}
+
 impl FromStr for PgSslMode {
     type Err = Error;
 
@@ -2,7 +2,9 @@ use crate::column::PgColumn;
 use crate::message::DataRow;
 use crate::meta_data::PgMetaData;
 use crate::statement::PgStatementMetadata;
+use crate::type_info::PgTypeInfo;
 use crate::types::decode::Decode;
+use crate::types::money::Money;
 use crate::value::{PgValue, PgValueFormat, PgValueRef};
 use rbdc::db::MetaData;
 use rbdc::Error;
@@ -15,6 +17,8 @@ pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    // see `PgConnection::money_frac_digits`
+    pub(crate) money_frac_digits: u32,
 }
 
 impl PgRow {
@@ -68,6 +72,9 @@ impl rbdc::db::Row for PgRow {
     fn get(&mut self, i: usize) -> Result<Value, Error> {
         match self.try_take(i) {
             Err(e) => Err(Error::from(format!("get error  index:{},error:{}", i, e))),
+            Ok(v) if v.type_info == PgTypeInfo::MONEY && !v.is_null() => Ok(Money::decode(v)?
+                .to_decimal_with(self.money_frac_digits)
+                .into()),
             Ok(v) => Value::decode(v),
         }
     }
@@ -1170,6 +1170,10 @@ impl PgTypeInfo {
     // unknown type, transmitted as text
     pub(crate) const UNKNOWN: Self = Self(PgType::Unknown);
 
+    // oid 0, the server infers the parameter type from the statement,
+    // used for user-defined enum labels which are cast to the column's enum type
+    pub(crate) const UNSPECIFIED: Self = Self(PgType::DeclareWithOid(Oid(0)));
+
     //
     // numeric types
     // https://www.postgresql.org/docs/current/datatype-numeric.html
@@ -6,7 +6,6 @@ use crate::types::{Oid, TypeInfo};
 use crate::value::{PgValue, PgValueFormat};
 use bytes::Buf;
 use rbdc::Error;
-use rbs::Value;
 use std::borrow::Cow;
 
 impl<T: Decode + TypeInfo> Decode for Vec<T> {
@@ -163,20 +162,32 @@ impl<T: Decode + TypeInfo> Decode for Vec<T> {
     }
 }
 
-fn element_type_info<T: TypeInfo>(arg: &Vec<T>) -> PgTypeInfo {
-    if arg.len() == 0 {
-        PgTypeInfo::UNKNOWN
-    } else {
-        arg[0].type_info()
+// the type of the first non NULL element
+fn element_type_info<T: TypeInfo>(arg: &[T]) -> PgTypeInfo {
+    arg.iter()
+        .map(|v| v.type_info())
+        .find(|v| v.0 != PgType::Unknown)
+        .unwrap_or(PgTypeInfo::UNKNOWN)
+}
+
+impl<T: TypeInfo> TypeInfo for Vec<T> {
+    fn type_info(&self) -> PgTypeInfo {
+        element_type_info(self)
+            .0
+            .to_array_type()
+            .unwrap_or(PgTypeInfo::UNKNOWN)
     }
 }
 
-impl Encode for Vec<Value> {
+/// a one dimensional array starting at 1, in the binary format of `array_send`:
+/// ndim, has_nulls, element oid, (length, lower bound) of the dimension,
+/// then every element prefixed with its length (-1 for NULL)
+impl<T: Encode + TypeInfo> Encode for Vec<T> {
     fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
         let type_info = element_type_info(&self);
         buf.extend(&1_i32.to_be_bytes()); // number of dimensions
-        buf.extend(&0_i32.to_be_bytes()); // flags
-                                          // element type
+        let flags = buf.len();
+        buf.extend(&0_i32.to_be_bytes()); // has nulls, set below
         match type_info.0 {
             PgType::DeclareWithName(name) => buf.patch_type_by_name(&name),
             ty => {
@@ -185,9 +196,94 @@ impl Encode for Vec<Value> {
         }
         buf.extend(&(self.len() as i32).to_be_bytes()); // len
         buf.extend(&1_i32.to_be_bytes()); // lower bound
+        let mut has_nulls = false;
         for element in self {
-            buf.encode(element)?;
+            let offset = buf.len();
+            buf.extend(&[0; 4]);
+            let len = match element.encode(buf)? {
+                IsNull::No => (buf.len() - offset - 4) as i32,
+                IsNull::Yes => {
+                    has_nulls = true;
+                    -1
+                }
+            };
+            buf[offset..(offset + 4)].copy_from_slice(&len.to_be_bytes());
+        }
+        if has_nulls {
+            buf[flags..(flags + 4)].copy_from_slice(&1_i32.to_be_bytes());
         }
         Ok(IsNull::No)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::PgTypeInfo;
+    use crate::types::decode::Decode;
+    use crate::types::encode::Encode;
+    use crate::types::TypeInfo;
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+
+    fn round_trip<T: Encode + Decode + TypeInfo + Clone>(v: Vec<T>) -> (Vec<T>, Vec<u8>) {
+        let type_info = v.type_info();
+        let mut buf = PgArgumentBuffer::default();
+        v.encode(&mut buf).unwrap();
+        let bytes = buf.to_vec();
+        let decoded = Vec::<T>::decode(PgValue {
+            value: Some(bytes.clone()),
+            type_info,
+            format: PgValueFormat::Binary,
+        })
+        .unwrap();
+        (decoded, bytes)
+    }
+
+    #[test]
+    fn test_encode_i32() {
+        let v = vec![1, 2, 3];
+        assert_eq!(v.type_info(), PgTypeInfo::INT4_ARRAY);
+        let (decoded, bytes) = round_trip(v.clone());
+        assert_eq!(decoded, v);
+        // ndim, has_nulls, int4 oid, len, lower bound
+        assert_eq!(
+            &bytes[..20],
+            &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 3, 0, 0, 0, 1]
+        );
+        assert_eq!(&bytes[20..28], &[0, 0, 0, 4, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_encode_string() {
+        let v = vec!["a".to_string(), "".to_string(), "say \"hi\"".to_string()];
+        assert_eq!(v.type_info(), PgTypeInfo::VARCHAR_ARRAY);
+        assert_eq!(round_trip(v.clone()).0, v);
+        assert_eq!(round_trip(Vec::<String>::new()).0, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_encode_option_i64() {
+        let v = vec![None, Some(1_i64), None, Some(-2)];
+        // the element type comes from the first non NULL element
+        assert_eq!(v.type_info(), PgTypeInfo::INT8_ARRAY);
+        let (decoded, bytes) = round_trip(v.clone());
+        assert_eq!(decoded, v);
+        assert_eq!(&bytes[4..8], &1_i32.to_be_bytes());
+        assert_eq!(&bytes[20..24], &(-1_i32).to_be_bytes());
+    }
+
+    #[test]
+    fn test_encode_value() {
+        let v = vec![Value::I32(1), Value::Null];
+        let mut buf = PgArgumentBuffer::default();
+        v.encode(&mut buf).unwrap();
+        let decoded = Vec::<Option<i32>>::decode(PgValue {
+            value: Some(buf.to_vec()),
+            type_info: PgTypeInfo::INT4_ARRAY,
+            format: PgValueFormat::Binary,
+        })
+        .unwrap();
+        assert_eq!(decoded, vec![Some(1), None]);
+    }
+}
@@ -21,8 +21,8 @@ impl TryFrom<PgNumeric> for BigDecimal {
                 ..
             } => (digits, sign, weight),
 
-            PgNumeric::NotANumber => {
-                return Err("BigDecimal does not support NaN values".into());
+            PgNumeric::NotANumber | PgNumeric::PositiveInfinity | PgNumeric::NegativeInfinity => {
+                return Err("BigDecimal does not support NaN or Infinity values".into());
             }
         };
 
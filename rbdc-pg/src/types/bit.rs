@@ -0,0 +1,122 @@
+use crate::arguments::PgArgumentBuffer;
+use crate::types::decode::Decode;
+use crate::types::encode::{Encode, IsNull};
+use crate::value::{PgValue, PgValueFormat};
+use byteorder::{BigEndian, ByteOrder};
+use rbdc::Error;
+use std::fmt::{Display, Formatter};
+
+/// `BIT(n)`/`VARBIT` value as a string of `'0'`/`'1'` characters, such as `"10110"`.
+///
+/// on the wire (binary) it is the bit length as `i32` followed by the bits packed
+/// into bytes, most significant bit first, the last byte padded with zeros
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename = "BitString")]
+pub struct BitString(pub String);
+
+impl Display for BitString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Encode for BitString {
+    fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
+        let mut bytes = vec![0u8; self.0.len().div_ceil(8)];
+        for (i, c) in self.0.chars().enumerate() {
+            match c {
+                '0' => {}
+                '1' => bytes[i / 8] |= 0x80 >> (i % 8),
+                _ => {
+                    return Err(Error::from(format!(
+                        "\"{}\" is not a valid binary digit",
+                        c
+                    )));
+                }
+            }
+        }
+        buf.extend(&(self.0.len() as i32).to_be_bytes());
+        buf.extend(&bytes);
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode for BitString {
+    fn decode(value: PgValue) -> Result<Self, Error> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+                if bytes.len() < 4 {
+                    return Err(Error::from("BitString: buffer too short"));
+                }
+                let len = BigEndian::read_i32(bytes);
+                let bits = &bytes[4..];
+                if len < 0 || bits.len() != (len as usize).div_ceil(8) {
+                    return Err(Error::from(format!(
+                        "BitString: {} bytes can not hold {} bits",
+                        bits.len(),
+                        len
+                    )));
+                }
+                Self(
+                    (0..len as usize)
+                        .map(|i| {
+                            if bits[i / 8] & (0x80 >> (i % 8)) != 0 {
+                                '1'
+                            } else {
+                                '0'
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            PgValueFormat::Text => Self(value.as_str()?.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::PgTypeInfo;
+    use crate::types::bit::BitString;
+    use crate::types::decode::Decode;
+    use crate::types::encode::Encode;
+    use crate::value::{PgValue, PgValueFormat};
+
+    fn decode(bytes: &[u8]) -> Result<BitString, rbdc::Error> {
+        BitString::decode(PgValue {
+            value: Some(bytes.to_vec()),
+            type_info: PgTypeInfo::VARBIT,
+            format: PgValueFormat::Binary,
+        })
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(&[0, 0, 0, 8, 0b1010_0101]).unwrap().0, "10100101");
+        // 10 bits, the last 6 bits of the second byte are padding
+        assert_eq!(
+            decode(&[0, 0, 0, 10, 0b1111_0000, 0b0100_0000]).unwrap().0,
+            "1111000001"
+        );
+        assert_eq!(decode(&[0, 0, 0, 0]).unwrap().0, "");
+        assert!(decode(&[0, 0, 0, 9, 0xff]).is_err());
+        assert!(decode(&[0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut buf = PgArgumentBuffer::default();
+        BitString("101".to_string()).encode(&mut buf).unwrap();
+        assert_eq!(&buf[..], &[0, 0, 0, 3, 0b1010_0000]);
+        let mut buf = PgArgumentBuffer::default();
+        BitString("1000000011".to_string())
+            .encode(&mut buf)
+            .unwrap();
+        assert_eq!(&buf[..], &[0, 0, 0, 10, 0b1000_0000, 0b1100_0000]);
+        assert_eq!(decode(&buf[..]).unwrap().0, "1000000011");
+        let mut buf = PgArgumentBuffer::default();
+        assert!(BitString("102".to_string()).encode(&mut buf).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::arguments::PgArgumentBuffer;
+use crate::type_info::PgTypeInfo;
 use crate::types::decode::Decode;
 use crate::types::encode::{Encode, IsNull};
+use crate::types::TypeInfo;
 use crate::value::{PgValue, PgValueFormat};
 use rbdc::Error;
 
@@ -11,6 +13,12 @@ impl Encode for bool {
     }
 }
 
+impl TypeInfo for bool {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::BOOL
+    }
+}
+
 impl Decode for bool {
     fn decode(value: PgValue) -> Result<Self, Error> {
         Ok(match value.format() {
@@ -1,10 +1,12 @@
 use crate::arguments::PgArgumentBuffer;
 use crate::types::decode::Decode;
 use crate::types::encode::{Encode, IsNull};
+use crate::types::numeric::PgNumeric;
 use crate::value::{PgValue, PgValueFormat};
 use bigdecimal::BigDecimal;
 use rbdc::decimal::Decimal;
 use rbdc::Error;
+use rbs::Value;
 
 impl Encode for Decimal {
     fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
@@ -21,3 +23,87 @@ impl Decode for Decimal {
         }
     }
 }
+
+/// decode `NUMERIC` into `Value`, the special values `NaN`, `Infinity` and `-Infinity`
+/// can not be held by `Decimal` and are returned as `Value::String`
+pub(crate) fn decode_numeric(value: PgValue) -> Result<Value, Error> {
+    match value.format() {
+        PgValueFormat::Binary => {
+            let numeric = PgNumeric::decode(value.as_bytes()?)?;
+            if let Some(name) = numeric.special_name() {
+                return Ok(Value::String(name.to_string()));
+            }
+            Ok(Decimal(BigDecimal::try_from(numeric)?).into())
+        }
+        PgValueFormat::Text => match value.as_str()? {
+            v @ ("NaN" | "Infinity" | "-Infinity") => Ok(Value::String(v.to_string())),
+            _ => Ok(Decimal::decode(value)?.into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::type_info::PgTypeInfo;
+    use crate::types::decimal::decode_numeric;
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+
+    fn binary(sign: u16, digits: &[i16]) -> PgValue {
+        let mut buf = vec![];
+        buf.extend((digits.len() as u16).to_be_bytes());
+        buf.extend(0_i16.to_be_bytes());
+        buf.extend(sign.to_be_bytes());
+        buf.extend(0_i16.to_be_bytes());
+        for d in digits {
+            buf.extend(d.to_be_bytes());
+        }
+        PgValue {
+            value: Some(buf),
+            type_info: PgTypeInfo::NUMERIC,
+            format: PgValueFormat::Binary,
+        }
+    }
+
+    fn text(s: &str) -> PgValue {
+        PgValue {
+            value: Some(s.as_bytes().to_vec()),
+            type_info: PgTypeInfo::NUMERIC,
+            format: PgValueFormat::Text,
+        }
+    }
+
+    #[test]
+    fn test_decode_numeric_special_binary() {
+        assert_eq!(
+            decode_numeric(binary(0xC000, &[])).unwrap(),
+            Value::String("NaN".to_string())
+        );
+        assert_eq!(
+            decode_numeric(binary(0xD000, &[])).unwrap(),
+            Value::String("Infinity".to_string())
+        );
+        assert_eq!(
+            decode_numeric(binary(0xF000, &[])).unwrap(),
+            Value::String("-Infinity".to_string())
+        );
+        assert_eq!(
+            decode_numeric(binary(0x0000, &[12])).unwrap(),
+            Value::Ext("Decimal", Box::new(Value::String("12".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_decode_numeric_special_text() {
+        for v in ["NaN", "Infinity", "-Infinity"] {
+            assert_eq!(
+                decode_numeric(text(v)).unwrap(),
+                Value::String(v.to_string())
+            );
+        }
+        assert_eq!(
+            decode_numeric(text("1.5")).unwrap(),
+            Value::Ext("Decimal", Box::new(Value::String("1.5".to_string())))
+        );
+    }
+}
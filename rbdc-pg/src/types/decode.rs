@@ -5,3 +5,13 @@ pub trait Decode: Sized {
     /// Decode a new value of this type using a raw value from the database.
     fn decode(value: PgValue) -> Result<Self, Error>;
 }
+
+/// SQL `NULL` is `None`
+impl<T: Decode> Decode for Option<T> {
+    fn decode(value: PgValue) -> Result<Self, Error> {
+        if value.is_null() {
+            return Ok(None);
+        }
+        T::decode(value).map(Some)
+    }
+}
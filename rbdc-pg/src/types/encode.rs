@@ -11,6 +11,15 @@ pub trait Encode {
     fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error>;
 }
 
+impl<T: Encode> Encode for Option<T> {
+    fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
+        match self {
+            Some(v) => v.encode(buf),
+            None => Ok(IsNull::Yes),
+        }
+    }
+}
+
 impl PgArguments {
     pub fn from_args(args: Vec<Value>) -> Result<Self, Error> {
         let mut arg = PgArguments {
@@ -1,6 +1,8 @@
 use crate::arguments::PgArgumentBuffer;
+use crate::type_info::PgTypeInfo;
 use crate::types::decode::Decode;
 use crate::types::encode::{Encode, IsNull};
+use crate::types::TypeInfo;
 use crate::value::{PgValue, PgValueFormat};
 use byteorder::{BigEndian, ByteOrder};
 use rbdc::Error;
@@ -48,6 +50,18 @@ impl Encode for f32 {
     }
 }
 
+impl TypeInfo for f64 {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::FLOAT8
+    }
+}
+
+impl TypeInfo for f32 {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::FLOAT4
+    }
+}
+
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,311 @@
+use crate::arguments::PgArgumentBuffer;
+use crate::types::encode::IsNull;
+use crate::value::{PgValue, PgValueFormat};
+use byteorder::{BigEndian, ByteOrder};
+use rbdc::Error;
+use rbs::value::map::ValueMap;
+use rbs::Value;
+
+// https://www.postgresql.org/docs/current/datatype-geometric.html
+//
+// decoded values, every number is a `Value::F64`:
+// | type      | value                                   | text                      |
+// |-----------|-----------------------------------------|---------------------------|
+// | `point`   | `[x, y]`                                | `(x,y)`                   |
+// | `line`    | `[a, b, c]` of `ax + by + c = 0`        | `{a,b,c}`                 |
+// | `lseg`    | `[[x1, y1], [x2, y2]]`                  | `[(x1,y1),(x2,y2)]`       |
+// | `box`     | `[[x1, y1], [x2, y2]]`, upper right first | `(x1,y1),(x2,y2)`       |
+// | `path`    | `{"closed": bool, "points": [[x, y], ..]}` | `((x,y),..)` or `[(x,y),..]` |
+// | `polygon` | `[[x, y], ..]`                          | `((x,y),..)`              |
+// | `circle`  | `[[x, y], r]`                           | `<(x,y),r>`               |
+
+/// decode the geometric type `type_name` (`"Point"`, `"Line"`, ...) from the binary or text format
+pub fn decode_geometry(type_name: &str, value: PgValue) -> Result<Value, Error> {
+    let (closed, numbers) = match value.format() {
+        PgValueFormat::Binary => read_binary(type_name, value.as_bytes()?)?,
+        PgValueFormat::Text => read_text(value.as_str()?)?,
+    };
+    let expect = match type_name {
+        "Point" => Some(2),
+        "Line" | "Circle" => Some(3),
+        "Lseg" | "Box" => Some(4),
+        _ => None,
+    };
+    if expect.map_or(numbers.len() % 2 != 0, |n| numbers.len() != n) {
+        return Err(Error::from(format!(
+            "{}: unexpected {} coordinates",
+            type_name,
+            numbers.len()
+        )));
+    }
+    Ok(match type_name {
+        "Point" => point(numbers[0], numbers[1]),
+        "Line" => Value::Array(numbers.into_iter().map(Value::F64).collect()),
+        "Circle" => Value::Array(vec![point(numbers[0], numbers[1]), Value::F64(numbers[2])]),
+        "Path" => {
+            let mut m = ValueMap::with_capacity(2);
+            m.insert("closed".into(), Value::Bool(closed));
+            m.insert("points".into(), points(&numbers));
+            Value::Map(m)
+        }
+        _ => points(&numbers),
+    })
+}
+
+/// encode a value produced by [decode_geometry] in the binary format,
+/// `Value::Binary` is sent as is
+pub fn encode_geometry(
+    type_name: &str,
+    value: Value,
+    buf: &mut PgArgumentBuffer,
+) -> Result<IsNull, Error> {
+    if let Value::Binary(v) = value {
+        buf.extend(&v);
+        return Ok(IsNull::No);
+    }
+    let mut closed = true;
+    let mut numbers = vec![];
+    match value {
+        Value::Map(m) => {
+            closed = m["closed"].as_bool().unwrap_or(true);
+            flatten(&m["points"], &mut numbers);
+        }
+        v => flatten(&v, &mut numbers),
+    }
+    match type_name {
+        "Path" => {
+            buf.push(closed as u8);
+            buf.extend(&((numbers.len() / 2) as i32).to_be_bytes());
+        }
+        "Polygon" => buf.extend(&((numbers.len() / 2) as i32).to_be_bytes()),
+        _ => {}
+    }
+    for v in numbers {
+        buf.extend(&v.to_be_bytes());
+    }
+    Ok(IsNull::No)
+}
+
+fn point(x: f64, y: f64) -> Value {
+    Value::Array(vec![Value::F64(x), Value::F64(y)])
+}
+
+fn points(numbers: &[f64]) -> Value {
+    Value::Array(numbers.chunks(2).map(|v| point(v[0], v[1])).collect())
+}
+
+fn flatten(value: &Value, numbers: &mut Vec<f64>) {
+    match value {
+        Value::Array(arr) => {
+            for v in arr {
+                flatten(v, numbers);
+            }
+        }
+        v => numbers.push(v.as_f64().unwrap_or_default()),
+    }
+}
+
+// (closed, coordinates) of the binary format: path and polygon start with a header
+fn read_binary(type_name: &str, mut bytes: &[u8]) -> Result<(bool, Vec<f64>), Error> {
+    let mut closed = true;
+    let header = match type_name {
+        "Path" => 5,
+        "Polygon" => 4,
+        _ => 0,
+    };
+    if bytes.len() < header {
+        return Err(Error::from(format!("{}: buffer too short", type_name)));
+    }
+    if type_name == "Path" {
+        closed = bytes[0] != 0;
+        bytes = &bytes[1..];
+    }
+    if header > 0 {
+        let n = BigEndian::read_i32(bytes);
+        bytes = &bytes[4..];
+        if n < 0 || bytes.len() != n as usize * 16 {
+            return Err(Error::from(format!(
+                "{}: {} bytes can not hold {} points",
+                type_name,
+                bytes.len(),
+                n
+            )));
+        }
+    }
+    let chunks = bytes.chunks_exact(8);
+    if !chunks.remainder().is_empty() {
+        return Err(Error::from(format!("{}: invalid length", type_name)));
+    }
+    Ok((closed, chunks.map(BigEndian::read_f64).collect()))
+}
+
+// (closed, coordinates) of the text format, `closed` only matters for a path,
+// which is written `[...]` when open
+fn read_text(s: &str) -> Result<(bool, Vec<f64>), Error> {
+    let s = s.trim();
+    let closed = !s.starts_with('[');
+    let mut numbers = vec![];
+    for v in s.split(['(', ')', '[', ']', '{', '}', '<', '>', ',']) {
+        let v = v.trim();
+        if v.is_empty() {
+            continue;
+        }
+        numbers.push(
+            v.parse::<f64>()
+                .map_err(|e| Error::from(format!("invalid coordinate {:?}: {}", v, e)))?,
+        );
+    }
+    Ok((closed, numbers))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::PgTypeInfo;
+    use crate::types::geometry::{decode_geometry, encode_geometry};
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+
+    fn binary(type_name: &str, type_info: PgTypeInfo, bytes: Vec<u8>) -> Value {
+        decode_geometry(
+            type_name,
+            PgValue {
+                value: Some(bytes),
+                type_info,
+                format: PgValueFormat::Binary,
+            },
+        )
+        .unwrap()
+    }
+
+    fn text(type_name: &str, type_info: PgTypeInfo, s: &str) -> Value {
+        decode_geometry(
+            type_name,
+            PgValue {
+                value: Some(s.as_bytes().to_vec()),
+                type_info,
+                format: PgValueFormat::Text,
+            },
+        )
+        .unwrap()
+    }
+
+    fn f64s(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    fn pt(x: f64, y: f64) -> Value {
+        Value::Array(vec![Value::F64(x), Value::F64(y)])
+    }
+
+    // encode `value` and decode it back
+    fn round_trip(type_name: &str, type_info: PgTypeInfo, value: Value) -> Value {
+        let mut buf = PgArgumentBuffer::default();
+        encode_geometry(type_name, value, &mut buf).unwrap();
+        binary(type_name, type_info, buf.to_vec())
+    }
+
+    #[test]
+    fn test_point() {
+        let v = pt(1.5, -2.0);
+        assert_eq!(binary("Point", PgTypeInfo::POINT, f64s(&[1.5, -2.0])), v);
+        assert_eq!(text("Point", PgTypeInfo::POINT, "(1.5,-2)"), v);
+        assert_eq!(round_trip("Point", PgTypeInfo::POINT, v.clone()), v);
+    }
+
+    #[test]
+    fn test_line() {
+        let v = Value::Array(vec![Value::F64(1.0), Value::F64(-1.0), Value::F64(0.5)]);
+        assert_eq!(binary("Line", PgTypeInfo::LINE, f64s(&[1.0, -1.0, 0.5])), v);
+        assert_eq!(text("Line", PgTypeInfo::LINE, "{1,-1,0.5}"), v);
+        assert_eq!(round_trip("Line", PgTypeInfo::LINE, v.clone()), v);
+    }
+
+    #[test]
+    fn test_lseg() {
+        let v = Value::Array(vec![pt(1.0, 2.0), pt(3.0, 4.0)]);
+        assert_eq!(
+            binary("Lseg", PgTypeInfo::LSEG, f64s(&[1.0, 2.0, 3.0, 4.0])),
+            v
+        );
+        assert_eq!(text("Lseg", PgTypeInfo::LSEG, "[(1,2),(3,4)]"), v);
+        assert_eq!(round_trip("Lseg", PgTypeInfo::LSEG, v.clone()), v);
+    }
+
+    #[test]
+    fn test_box() {
+        let v = Value::Array(vec![pt(3.0, 4.0), pt(1.0, 2.0)]);
+        assert_eq!(
+            binary("Box", PgTypeInfo::BOX, f64s(&[3.0, 4.0, 1.0, 2.0])),
+            v
+        );
+        assert_eq!(text("Box", PgTypeInfo::BOX, "(3,4),(1,2)"), v);
+        assert_eq!(round_trip("Box", PgTypeInfo::BOX, v.clone()), v);
+    }
+
+    #[test]
+    fn test_path() {
+        let path = |closed: bool| {
+            let mut m = rbs::value::map::ValueMap::new();
+            m.insert("closed".into(), Value::Bool(closed));
+            m.insert(
+                "points".into(),
+                Value::Array(vec![pt(0.0, 0.0), pt(1.0, 1.0), pt(2.0, 0.0)]),
+            );
+            Value::Map(m)
+        };
+        let mut bytes = vec![0u8];
+        bytes.extend(3i32.to_be_bytes());
+        bytes.extend(f64s(&[0.0, 0.0, 1.0, 1.0, 2.0, 0.0]));
+        assert_eq!(binary("Path", PgTypeInfo::PATH, bytes), path(false));
+        assert_eq!(
+            text("Path", PgTypeInfo::PATH, "[(0,0),(1,1),(2,0)]"),
+            path(false)
+        );
+        assert_eq!(
+            text("Path", PgTypeInfo::PATH, "((0,0),(1,1),(2,0))"),
+            path(true)
+        );
+        assert_eq!(round_trip("Path", PgTypeInfo::PATH, path(true)), path(true));
+        assert_eq!(
+            round_trip("Path", PgTypeInfo::PATH, path(false)),
+            path(false)
+        );
+    }
+
+    #[test]
+    fn test_polygon() {
+        let v = Value::Array(vec![pt(0.0, 0.0), pt(0.0, 1.0), pt(1.0, 0.0)]);
+        let mut bytes = 3i32.to_be_bytes().to_vec();
+        bytes.extend(f64s(&[0.0, 0.0, 0.0, 1.0, 1.0, 0.0]));
+        assert_eq!(binary("Polygon", PgTypeInfo::POLYGON, bytes), v);
+        assert_eq!(
+            text("Polygon", PgTypeInfo::POLYGON, "((0,0),(0,1),(1,0))"),
+            v
+        );
+        assert_eq!(round_trip("Polygon", PgTypeInfo::POLYGON, v.clone()), v);
+    }
+
+    #[test]
+    fn test_circle() {
+        let v = Value::Array(vec![pt(1.0, 2.0), Value::F64(3.0)]);
+        assert_eq!(
+            binary("Circle", PgTypeInfo::CIRCLE, f64s(&[1.0, 2.0, 3.0])),
+            v
+        );
+        assert_eq!(text("Circle", PgTypeInfo::CIRCLE, "<(1,2),3>"), v);
+        assert_eq!(round_trip("Circle", PgTypeInfo::CIRCLE, v.clone()), v);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let value = |bytes: Vec<u8>| PgValue {
+            value: Some(bytes),
+            type_info: PgTypeInfo::POLYGON,
+            format: PgValueFormat::Binary,
+        };
+        assert!(decode_geometry("Point", value(f64s(&[1.0]))).is_err());
+        assert!(decode_geometry("Polygon", value(vec![0, 0, 0, 2])).is_err());
+        assert!(decode_geometry("Polygon", value(vec![0xff, 0xff, 0xff, 0xff])).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::arguments::PgArgumentBuffer;
+use crate::types::decode::Decode;
+use crate::types::encode::{Encode, IsNull};
+use crate::value::{PgValue, PgValueFormat};
+use byteorder::{BigEndian, ByteOrder};
+use rbdc::Error;
+use rbs::value::map::ValueMap;
+use rbs::Value;
+use std::collections::BTreeMap;
+
+/// the `hstore` extension type, a map of text keys to nullable text values.
+///
+/// decoded as `Value::Ext("Hstore", Map)` whose values are `Value::String` or `Value::Null`,
+/// encoding `Value::Ext("Hstore", Map)` binds a `hstore` parameter
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+#[serde(rename = "Hstore")]
+pub struct Hstore(pub BTreeMap<String, Option<String>>);
+
+impl From<Hstore> for Value {
+    fn from(arg: Hstore) -> Self {
+        let mut m = ValueMap::with_capacity(arg.0.len());
+        for (k, v) in arg.0 {
+            m.insert(
+                Value::String(k),
+                v.map(Value::String).unwrap_or(Value::Null),
+            );
+        }
+        Value::Ext("Hstore", Box::new(Value::Map(m)))
+    }
+}
+
+impl TryFrom<Value> for Hstore {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Ext(_, v) => Hstore::try_from(*v),
+            Value::Map(m) => {
+                let mut map = BTreeMap::new();
+                for (k, v) in m {
+                    let k = match k {
+                        Value::String(k) => k,
+                        k => k.to_string(),
+                    };
+                    let v = match v {
+                        Value::Null => None,
+                        Value::String(v) => Some(v),
+                        v => Some(v.to_string()),
+                    };
+                    map.insert(k, v);
+                }
+                Ok(Hstore(map))
+            }
+            v => Err(Error::from(format!("warn type decode Hstore:{}", v))),
+        }
+    }
+}
+
+// binary format: i32 count, then per entry i32 key length, key, i32 value length (-1 for NULL), value
+impl Encode for Hstore {
+    fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
+        buf.extend(&(self.0.len() as i32).to_be_bytes());
+        for (k, v) in self.0 {
+            buf.extend(&(k.len() as i32).to_be_bytes());
+            buf.extend(k.as_bytes());
+            match v {
+                None => buf.extend(&(-1i32).to_be_bytes()),
+                Some(v) => {
+                    buf.extend(&(v.len() as i32).to_be_bytes());
+                    buf.extend(v.as_bytes());
+                }
+            }
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode for Hstore {
+    fn decode(value: PgValue) -> Result<Self, Error> {
+        match value.format() {
+            PgValueFormat::Binary => decode_binary(value.as_bytes()?),
+            PgValueFormat::Text => decode_text(value.as_str()?),
+        }
+    }
+}
+
+fn decode_binary(mut buf: &[u8]) -> Result<Hstore, Error> {
+    fn read_i32(buf: &mut &[u8]) -> Result<i32, Error> {
+        if buf.len() < 4 {
+            return Err(Error::from("hstore: buffer too short"));
+        }
+        let v = BigEndian::read_i32(buf);
+        *buf = &buf[4..];
+        Ok(v)
+    }
+    fn read_str(buf: &mut &[u8], len: i32) -> Result<String, Error> {
+        let len = len as usize;
+        if buf.len() < len {
+            return Err(Error::from("hstore: buffer too short"));
+        }
+        let s = String::from_utf8(buf[..len].to_vec())
+            .map_err(|e| Error::from(format!("hstore: {}", e)))?;
+        *buf = &buf[len..];
+        Ok(s)
+    }
+    let count = read_i32(&mut buf)?;
+    let mut map = BTreeMap::new();
+    for _ in 0..count {
+        let len = read_i32(&mut buf)?;
+        if len < 0 {
+            return Err(Error::from("hstore: NULL key"));
+        }
+        let k = read_str(&mut buf, len)?;
+        let len = read_i32(&mut buf)?;
+        let v = if len < 0 {
+            None
+        } else {
+            Some(read_str(&mut buf, len)?)
+        };
+        map.insert(k, v);
+    }
+    Ok(Hstore(map))
+}
+
+// text format: `"a"=>"1", "b"=>NULL`, quotes and backslashes are escaped with a backslash
+fn decode_text(s: &str) -> Result<Hstore, Error> {
+    let mut map = BTreeMap::new();
+    let mut chars = s.chars().peekable();
+    let read = |chars: &mut std::iter::Peekable<std::str::Chars>| -> Result<Option<String>, Error> {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        match chars.peek() {
+            None => Ok(None),
+            Some('"') => {
+                chars.next();
+                let mut v = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => v.extend(chars.next()),
+                        Some('"') => return Ok(Some(v)),
+                        Some(c) => v.push(c),
+                        None => return Err(Error::from(format!("hstore: unterminated {:?}", s))),
+                    }
+                }
+            }
+            Some(_) => {
+                let mut v = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !matches!(c, ',' | '='))
+                {
+                    v.push(c);
+                }
+                Ok(Some(v))
+            }
+        }
+    };
+    while let Some(k) = read(&mut chars)? {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.next() != Some('=') || chars.next() != Some('>') {
+            return Err(Error::from(format!("hstore: expected `=>` in {:?}", s)));
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let quoted = chars.peek() == Some(&'"');
+        let v = read(&mut chars)?
+            .ok_or_else(|| Error::from(format!("hstore: missing value in {:?}", s)))?;
+        let v = if !quoted && v.eq_ignore_ascii_case("NULL") {
+            None
+        } else {
+            Some(v)
+        };
+        map.insert(k, v);
+    }
+    Ok(Hstore(map))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::PgTypeInfo;
+    use crate::types::decode::Decode;
+    use crate::types::encode::Encode;
+    use crate::types::hstore::Hstore;
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+    use std::collections::BTreeMap;
+
+    fn hstore() -> Hstore {
+        let mut m = BTreeMap::new();
+        m.insert("a".to_string(), Some("1".to_string()));
+        m.insert("b".to_string(), None);
+        m.insert("c d".to_string(), Some("say \"hi\"".to_string()));
+        Hstore(m)
+    }
+
+    fn value(format: PgValueFormat, bytes: Vec<u8>) -> PgValue {
+        PgValue {
+            value: Some(bytes),
+            type_info: PgTypeInfo::with_name("hstore"),
+            format,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut buf = PgArgumentBuffer::default();
+        hstore().encode(&mut buf).unwrap();
+        let decoded = Hstore::decode(value(PgValueFormat::Binary, buf.to_vec())).unwrap();
+        assert_eq!(decoded, hstore());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let text = r#""a"=>"1", "b"=>NULL, "c d"=>"say \"hi\"""#;
+        let decoded = Hstore::decode(value(PgValueFormat::Text, text.as_bytes().to_vec())).unwrap();
+        assert_eq!(decoded, hstore());
+        let decoded = Hstore::decode(value(PgValueFormat::Text, vec![])).unwrap();
+        assert_eq!(decoded, Hstore::default());
+        assert!(Hstore::decode(value(PgValueFormat::Text, b"\"a\"".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_value() {
+        let v = Value::from(hstore());
+        let Value::Ext("Hstore", m) = &v else {
+            panic!("{:?}", v)
+        };
+        assert_eq!(m["b"], Value::Null);
+        assert_eq!(Hstore::try_from(v).unwrap(), hstore());
+    }
+}
@@ -188,6 +188,24 @@ impl TypeInfo for i8 {
     }
 }
 
+impl TypeInfo for i16 {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::INT2
+    }
+}
+
+impl TypeInfo for i32 {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::INT4
+    }
+}
+
+impl TypeInfo for i64 {
+    fn type_info(&self) -> PgTypeInfo {
+        PgTypeInfo::INT8
+    }
+}
+
 
 
 #[cfg(test)]
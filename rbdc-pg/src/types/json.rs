@@ -35,47 +35,40 @@ impl Encode for Json {
 
 impl Decode for Json {
     fn decode(value: PgValue) -> Result<Self, Error> {
-        let fmt = value.format();
-        let type_info = value.type_info;
-        let mut buf = value.value.unwrap_or_default();
-        if buf.len() == 0 {
-            return Ok(Json {
-                0: "null".to_string(),
-            });
-        }
-        if fmt == PgValueFormat::Binary && type_info == PgTypeInfo::JSONB {
-            assert_eq!(
-                buf[0], 1,
-                "unsupported JSONB format version {}; please open an issue",
-                buf[0]
-            );
-            buf.remove(0);
-        }
         Ok(Self {
-            0: unsafe { String::from_utf8_unchecked(buf) },
+            0: json_text(value)?.unwrap_or_else(|| "null".to_string()),
         })
     }
 }
 
-pub fn decode_json(value: PgValue) -> Result<Value, Error> {
+/// the JSON text of a `json` or `jsonb` value, `None` for an empty value.
+/// binary `jsonb` is the text prefixed with a format version byte, `json` and text `jsonb` are plain text
+fn json_text(value: PgValue) -> Result<Option<String>, Error> {
     let fmt = value.format();
     let type_info = value.type_info;
     let mut buf = value.value.unwrap_or_default();
     if buf.len() == 0 {
-        return Ok(Value::Null);
+        return Ok(None);
     }
     if fmt == PgValueFormat::Binary && type_info == PgTypeInfo::JSONB {
-        assert_eq!(
-            buf[0], 1,
-            "unsupported JSONB format version {}; please open an issue",
-            buf[0]
-        );
+        if buf[0] != 1 {
+            return Err(Error::from(format!(
+                "unsupported JSONB format version {}",
+                buf[0]
+            )));
+        }
         buf.remove(0);
     }
-    Ok(
-        serde_json::from_str(&unsafe { String::from_utf8_unchecked(buf) })
-            .map_err(|e| Error::from(e.to_string()))?,
-    )
+    Ok(Some(unsafe { String::from_utf8_unchecked(buf) }))
+}
+
+/// objects decode to a `Value::Map` keeping the keys in the order the server sends them.
+/// that is the written order for `json`, `jsonb` stores its keys sorted (shorter keys first)
+pub fn decode_json(value: PgValue) -> Result<Value, Error> {
+    match json_text(value)? {
+        None => Ok(Value::Null),
+        Some(v) => serde_json::from_str(&v).map_err(|e| Error::from(e.to_string())),
+    }
 }
 
 pub fn encode_json(v: Value, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
@@ -90,8 +83,10 @@ pub fn encode_json(v: Value, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error
     // JSONB version (as of 2020-03-20)
     buf.push(1);
 
-    // the JSON data written to the buffer is the same regardless of parameter type
-    buf.write_all(&v.to_string().into_bytes())?;
+    // the JSON data written to the buffer is the same regardless of parameter type.
+    // `Value`'s `Display` does not escape strings, serde_json does
+    let bytes = serde_json::to_vec(&v).map_err(|e| Error::from(e.to_string()))?;
+    buf.write_all(&bytes)?;
 
     Ok(IsNull::No)
 }
@@ -101,3 +96,81 @@ impl TypeInfo for Json {
         PgTypeInfo::JSONB
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::PgTypeInfo;
+    use crate::types::json::{decode_json, encode_json};
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+
+    const TEXT: &str = r#"{"b":1,"a":{"d":[1,"say \"hi\""],"c":null}}"#;
+
+    fn value(type_info: PgTypeInfo, format: PgValueFormat, bytes: Vec<u8>) -> PgValue {
+        PgValue {
+            value: Some(bytes),
+            type_info,
+            format,
+        }
+    }
+
+    fn keys(v: &Value) -> Vec<String> {
+        match v {
+            Value::Map(m) => m.into_iter().map(|(k, _)| k.to_string()).collect(),
+            v => panic!("{:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_decode_key_order() {
+        let mut jsonb = vec![1];
+        jsonb.extend(TEXT.as_bytes());
+        for v in [
+            value(
+                PgTypeInfo::JSON,
+                PgValueFormat::Binary,
+                TEXT.as_bytes().to_vec(),
+            ),
+            value(
+                PgTypeInfo::JSON,
+                PgValueFormat::Text,
+                TEXT.as_bytes().to_vec(),
+            ),
+            value(PgTypeInfo::JSONB, PgValueFormat::Binary, jsonb),
+            value(
+                PgTypeInfo::JSONB,
+                PgValueFormat::Text,
+                TEXT.as_bytes().to_vec(),
+            ),
+        ] {
+            let v = decode_json(v).unwrap();
+            assert_eq!(keys(&v), vec![r#""b""#, r#""a""#]);
+            assert_eq!(keys(&v["a"]), vec![r#""d""#, r#""c""#]);
+            assert_eq!(v["a"]["d"][1], Value::String("say \"hi\"".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let v: Value = serde_json::from_str(TEXT).unwrap();
+        let mut buf = PgArgumentBuffer::default();
+        encode_json(v.clone(), &mut buf).unwrap();
+        let decoded = decode_json(value(
+            PgTypeInfo::JSONB,
+            PgValueFormat::Binary,
+            buf.to_vec(),
+        ))
+        .unwrap();
+        assert_eq!(decoded, v);
+        assert_eq!(keys(&decoded), vec![r#""b""#, r#""a""#]);
+    }
+
+    #[test]
+    fn test_decode_version() {
+        let v = value(PgTypeInfo::JSONB, PgValueFormat::Binary, b"\x02{}".to_vec());
+        assert!(decode_json(v).is_err());
+        let v = value(PgTypeInfo::JSONB, PgValueFormat::Binary, vec![]);
+        assert_eq!(decode_json(v).unwrap(), Value::Null);
+    }
+}
@@ -3,6 +3,7 @@ pub mod oid;
 pub use oid::Oid;
 pub mod array;
 pub mod bigdecimal;
+pub mod bit;
 pub mod bool;
 pub mod byte;
 pub mod date;
@@ -11,6 +12,8 @@ pub mod decimal;
 pub mod decode;
 pub mod encode;
 pub mod float;
+pub mod geometry;
+pub mod hstore;
 pub mod int;
 pub mod json;
 pub mod money;
@@ -28,6 +31,16 @@ pub trait TypeInfo {
     fn type_info(&self) -> PgTypeInfo;
 }
 
+/// `None` has no type of its own
+impl<T: TypeInfo> TypeInfo for Option<T> {
+    fn type_info(&self) -> PgTypeInfo {
+        match self {
+            Some(v) => v.type_info(),
+            None => PgTypeInfo::UNKNOWN,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
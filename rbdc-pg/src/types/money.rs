@@ -2,7 +2,9 @@ use crate::arguments::PgArgumentBuffer;
 use crate::types::decode::Decode;
 use crate::types::encode::{Encode, IsNull};
 use crate::value::{PgValue, PgValueFormat};
+use bigdecimal::BigDecimal;
 use byteorder::{BigEndian, ByteOrder};
+use rbdc::decimal::Decimal;
 use rbdc::Error;
 use rbs::Value;
 use std::fmt::{Display, Formatter};
@@ -17,10 +19,42 @@ use std::fmt::{Display, Formatter};
 /// just be the units of the currency.
 ///
 /// See the type-level docs for an explanation of `locale_frac_units`.
+///
+/// A `MONEY` column is decoded from the binary format only and becomes a `Value::Ext("Decimal")`
+/// scaled by the fractional digits of the server `lc_monetary`, which the connection reads once
+/// at startup (see [`Money::to_decimal_with`]).
+/// Encoding `Value::Ext("Money", I64)` sends this integer form,
+/// `Value::Ext("Money", String)` such as `"1234.56"` is sent as a `NUMERIC`
+/// that the server converts with its own `lc_monetary`.
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename = "Money")]
 pub struct Money(pub i64);
 
+impl Money {
+    /// the value in currency units, assumes the locale has `frac_digits=2` (`12345` -> `123.45`)
+    pub fn to_decimal(&self) -> Decimal {
+        self.to_decimal_with(2)
+    }
+
+    /// the value in currency units of a locale with `frac_digits` fractional digits
+    /// (`12345` -> `123.45` for 2, `12345` for 0)
+    pub fn to_decimal_with(&self, frac_digits: u32) -> Decimal {
+        Decimal(BigDecimal::new(self.0.into(), frac_digits as i64))
+    }
+
+    /// the wire integer of `v` for a locale with `frac_digits` fractional digits,
+    /// rounded half to even like the server does (`123.456` -> `12346` for 2)
+    pub fn from_decimal(v: &Decimal, frac_digits: u32) -> Result<Self, Error> {
+        let (units, _) =
+            v.0.round(frac_digits as i64)
+                .with_scale(frac_digits as i64)
+                .into_bigint_and_exponent();
+        i64::try_from(&units)
+            .map(Money)
+            .map_err(|_| Error::from(format!("money out of range: {}", v.0)))
+    }
+}
+
 impl Display for Money {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -48,6 +82,7 @@ impl Decode for Money {
                     let cents = BigEndian::read_i64(value.as_bytes()?);
                     Ok(cents)
                 }
+                // the text format depends on the server `lc_monetary`, such as `$1,234.56` or `1.234,56 €`
                 PgValueFormat::Text => Err(Error::from(
                     "Reading a `MONEY` value in text format is not supported.",
                 )),
@@ -55,3 +90,56 @@ impl Decode for Money {
         }?))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::type_info::PgTypeInfo;
+    use crate::types::decode::Decode;
+    use crate::types::money::Money;
+    use crate::value::{PgValue, PgValueFormat};
+    use rbs::Value;
+
+    #[test]
+    fn test_decode_negative() {
+        let v = PgValue {
+            value: Some((-12345i64).to_be_bytes().to_vec()),
+            type_info: PgTypeInfo::MONEY,
+            format: PgValueFormat::Binary,
+        };
+        assert_eq!(
+            Value::decode(v).unwrap(),
+            Value::Ext("Decimal", Box::new(Value::String("-123.45".to_string())))
+        );
+        assert_eq!(Money(100).to_decimal().to_string(), "1.00");
+    }
+
+    #[test]
+    fn test_frac_digits() {
+        use rbdc::decimal::Decimal;
+        use std::str::FromStr;
+
+        // `$1234.56` with `frac_digits=2`
+        let v = Decimal::from_str("1234.56").unwrap();
+        let money = Money::from_decimal(&v, 2).unwrap();
+        assert_eq!(money, Money(123456));
+        assert_eq!(money.to_decimal_with(2), v);
+        // a locale without fractional units, such as `ja_JP`
+        assert_eq!(Money(1234).to_decimal_with(0).to_string(), "1234");
+        assert_eq!(Money::from_decimal(&v, 0).unwrap(), Money(1235));
+        assert_eq!(
+            Money::from_decimal(&Decimal::from_str("0.125").unwrap(), 2).unwrap(),
+            Money(12)
+        );
+        assert!(Money::from_decimal(&Decimal::from_str("1e30").unwrap(), 2).is_err());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let v = PgValue {
+            value: Some(b"-$123.45".to_vec()),
+            type_info: PgTypeInfo::MONEY,
+            format: PgValueFormat::Text,
+        };
+        assert!(Money::decode(v).is_err());
+    }
+}
@@ -8,6 +8,12 @@ pub(crate) enum PgNumeric {
     /// Equivalent to the `'NaN'` value in Postgres. The result of, e.g. `1 / 0`.
     NotANumber,
 
+    /// Equivalent to the `'Infinity'` value in Postgres 14+.
+    PositiveInfinity,
+
+    /// Equivalent to the `'-Infinity'` value in Postgres 14+.
+    NegativeInfinity,
+
     /// A populated `NUMERIC` value.
     ///
     /// A description of these fields can be found here (although the type being described is the
@@ -51,6 +57,9 @@ pub(crate) enum PgNumeric {
 const SIGN_POS: u16 = 0x0000;
 const SIGN_NEG: u16 = 0x4000;
 const SIGN_NAN: u16 = 0xC000; // overflows i16 (C equivalent truncates from integer literal)
+                              // https://github.com/postgres/postgres/blob/REL_14_0/src/backend/utils/adt/numeric.c#L200-L203
+const SIGN_PINF: u16 = 0xD000;
+const SIGN_NINF: u16 = 0xF000;
 
 /// Possible sign values for [PgNumeric].
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -66,7 +75,9 @@ impl PgNumericSign {
             SIGN_POS => Ok(PgNumericSign::Positive),
             SIGN_NEG => Ok(PgNumericSign::Negative),
 
-            SIGN_NAN => unreachable!("sign value for NaN passed to PgNumericSign"),
+            SIGN_NAN | SIGN_PINF | SIGN_NINF => {
+                unreachable!("sign value for NaN or Infinity passed to PgNumericSign")
+            }
 
             _ => Err(format!("invalid value for PgNumericSign: {:#04X}", val).into()),
         }
@@ -83,6 +94,10 @@ impl PgNumeric {
 
         if sign == SIGN_NAN {
             Ok(PgNumeric::NotANumber)
+        } else if sign == SIGN_PINF {
+            Ok(PgNumeric::PositiveInfinity)
+        } else if sign == SIGN_NINF {
+            Ok(PgNumeric::NegativeInfinity)
         } else {
             let digits: Vec<_> = (0..num_digits).map(|_| buf.get_i16()).collect::<_>();
 
@@ -124,12 +139,57 @@ impl PgNumeric {
                 }
             }
 
-            PgNumeric::NotANumber => {
-                buf.extend(&0_i16.to_be_bytes());
-                buf.extend(&0_i16.to_be_bytes());
-                buf.extend(&SIGN_NAN.to_be_bytes());
-                buf.extend(&0_i16.to_be_bytes());
-            }
+            PgNumeric::NotANumber => Self::encode_special(SIGN_NAN, buf),
+
+            PgNumeric::PositiveInfinity => Self::encode_special(SIGN_PINF, buf),
+
+            PgNumeric::NegativeInfinity => Self::encode_special(SIGN_NINF, buf),
         }
     }
+
+    fn encode_special(sign: u16, buf: &mut PgArgumentBuffer) {
+        buf.extend(&0_i16.to_be_bytes());
+        buf.extend(&0_i16.to_be_bytes());
+        buf.extend(&sign.to_be_bytes());
+        buf.extend(&0_i16.to_be_bytes());
+    }
+
+    /// the Postgres text form of `NaN`, `Infinity` and `-Infinity`, `None` for a number
+    pub(crate) fn special_name(&self) -> Option<&'static str> {
+        match self {
+            PgNumeric::NotANumber => Some("NaN"),
+            PgNumeric::PositiveInfinity => Some("Infinity"),
+            PgNumeric::NegativeInfinity => Some("-Infinity"),
+            PgNumeric::Number { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::numeric::PgNumeric;
+
+    fn special(sign: u16) -> Vec<u8> {
+        let mut buf = vec![0, 0, 0, 0];
+        buf.extend(sign.to_be_bytes());
+        buf.extend([0, 0]);
+        buf
+    }
+
+    #[test]
+    fn test_decode_special() {
+        assert_eq!(
+            PgNumeric::decode(&special(0xC000)).unwrap(),
+            PgNumeric::NotANumber
+        );
+        assert_eq!(
+            PgNumeric::decode(&special(0xD000)).unwrap(),
+            PgNumeric::PositiveInfinity
+        );
+        assert_eq!(
+            PgNumeric::decode(&special(0xF000)).unwrap(),
+            PgNumeric::NegativeInfinity
+        );
+        assert!(PgNumeric::decode(&special(0x1000)).is_err());
+    }
 }
@@ -13,7 +13,7 @@ impl Encode for Timestamp {
             mon: 1,
             year: 2000,
         });
-        let dt = fastdate::DateTime::from_timestamp_millis(self.0);
+        let dt = fastdate::DateTime::from_timestamp_micros(self.0);
         let micros;
         if dt >= epoch {
             micros = (dt - epoch).as_micros() as i64;
@@ -42,12 +42,12 @@ impl Decode for Timestamp {
                         epoch + std::time::Duration::from_micros(us as u64)
                     }
                 };
-                Timestamp(v.unix_timestamp_millis())
+                Timestamp(v.unix_timestamp_micros())
             }
             PgValueFormat::Text => {
                 //2023-11-08 16:38:06.157
                 let s = value.as_str()?;
-                Timestamp(fastdate::DateTime::from_str(&format!("{}Z", s)).map_err(|e|Error::from(e.to_string()))?.unix_timestamp_millis())
+                Timestamp(fastdate::DateTime::from_str(&format!("{}Z", s)).map_err(|e|Error::from(e.to_string()))?.unix_timestamp_micros())
             }
         })
     }
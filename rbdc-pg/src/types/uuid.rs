@@ -8,22 +8,18 @@ use std::str::FromStr;
 
 impl Encode for Uuid {
     fn encode(self, buf: &mut PgArgumentBuffer) -> Result<IsNull, Error> {
-        let uuid = uuid::Uuid::from_str(&self.0).map_err(|e| Error::from(e.to_string()))?;
-        buf.extend_from_slice(uuid.as_bytes());
+        buf.extend_from_slice(self.as_bytes());
         Ok(IsNull::No)
     }
 }
 
 impl Decode for Uuid {
     fn decode(value: PgValue) -> Result<Self, Error> {
-        Ok(Self(match value.format() {
-            PgValueFormat::Binary => uuid::Uuid::from_slice(value.as_bytes()?)
-                .map_err(|e| Error::from(format!("Decode Uuid:{}", e)))?
-                .to_string(),
-            PgValueFormat::Text => value
-                .as_str()?
-                .parse()
-                .map_err(|e| Error::from(format!("Decode Uuid str:{}", e)))?,
-        }))
+        match value.format() {
+            PgValueFormat::Binary => Uuid::from_slice(value.as_bytes()?)
+                .map_err(|e| Error::from(format!("Decode Uuid:{}", e))),
+            PgValueFormat::Text => Uuid::from_str(value.as_str()?)
+                .map_err(|e| Error::from(format!("Decode Uuid str:{}", e))),
+        }
     }
 }
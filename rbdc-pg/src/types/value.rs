@@ -2,9 +2,13 @@ use crate::arguments::PgArgumentBuffer;
 use crate::type_info::PgType;
 use crate::type_info::PgTypeInfo;
 use crate::type_info::PgTypeKind;
+use crate::types::bit::BitString;
 use crate::types::byte::Bytea;
+use crate::types::decimal::decode_numeric;
 use crate::types::decode::Decode;
 use crate::types::encode::{Encode, IsNull};
+use crate::types::geometry::{decode_geometry, encode_geometry};
+use crate::types::hstore::Hstore;
 use crate::types::json::{decode_json, encode_json};
 use crate::types::money::Money;
 use crate::types::timestamptz::Timestamptz;
@@ -38,7 +42,7 @@ impl TypeInfo for Value {
             Value::Binary(_) => PgTypeInfo::BYTEA_ARRAY,
             Value::Array(_) => PgTypeInfo::JSON,
             Value::Map(_) => PgTypeInfo::JSON,
-            Value::Ext(type_name, _) => {
+            Value::Ext(type_name, v) => {
                 match *type_name {
                     "Uuid" => PgTypeInfo::UUID,
                     //decimal = 12345678
@@ -91,8 +95,14 @@ impl TypeInfo for Value {
                     "DateRange" => PgTypeInfo::DATE_RANGE,
                     "Int8Range" => PgTypeInfo::INT8_RANGE,
                     "Jsonpath" => PgTypeInfo::JSONPATH,
+                    // a decimal string is converted to money by the server, with its `lc_monetary`
+                    "Money" if v.is_str() => PgTypeInfo::NUMERIC,
                     "Money" => PgTypeInfo::MONEY,
+                    //extension type, the oid is looked up by name
+                    "Hstore" => PgTypeInfo::with_name("hstore"),
                     "Void" => PgTypeInfo::VOID,
+                    //enum label = "happy", the server casts it to the enum type
+                    "Enum" => PgTypeInfo::UNSPECIFIED,
                     "Custom" => PgTypeInfo::UNKNOWN,
                     "DeclareWithName" => PgTypeInfo::UNKNOWN,
                     "DeclareWithOid" => PgTypeInfo::UNKNOWN,
@@ -122,60 +132,12 @@ impl Decode for Value {
             PgType::Text => Value::String(Decode::decode(arg)?),
             PgType::Oid => Value::Ext("Oid", Box::new(Value::U32(Decode::decode(arg)?))),
             PgType::Json => decode_json(arg)?,
-            PgType::Point => Value::Ext(
-                "Point",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
-            PgType::Lseg => Value::Ext(
-                "Lseg",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
-            PgType::Path => Value::Ext(
-                "Path",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
-            PgType::Box => Value::Ext(
-                "Box",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
-            PgType::Polygon => Value::Ext(
-                "Polygon",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
-            PgType::Line => Value::Ext(
-                "Line",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
+            PgType::Point => Value::Ext("Point", Box::new(decode_geometry("Point", arg)?)),
+            PgType::Lseg => Value::Ext("Lseg", Box::new(decode_geometry("Lseg", arg)?)),
+            PgType::Path => Value::Ext("Path", Box::new(decode_geometry("Path", arg)?)),
+            PgType::Box => Value::Ext("Box", Box::new(decode_geometry("Box", arg)?)),
+            PgType::Polygon => Value::Ext("Polygon", Box::new(decode_geometry("Polygon", arg)?)),
+            PgType::Line => Value::Ext("Line", Box::new(decode_geometry("Line", arg)?)),
             PgType::Cidr => Value::Ext(
                 "Cidr",
                 Box::new(Value::Binary({
@@ -189,15 +151,7 @@ impl Decode for Value {
             PgType::Float4 => Value::F32(Decode::decode(arg)?),
             PgType::Float8 => Value::F32(Decode::decode(arg)?),
             PgType::Unknown => Value::Null,
-            PgType::Circle => Value::Ext(
-                "Circle",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
-            ),
+            PgType::Circle => Value::Ext("Circle", Box::new(decode_geometry("Circle", arg)?)),
             PgType::Macaddr8 => Value::Ext(
                 "Macaddr8",
                 Box::new(Value::Binary({
@@ -254,23 +208,13 @@ impl Decode for Value {
             PgType::Timetz => Timetz::decode(arg)?.into(),
             PgType::Bit => Value::Ext(
                 "Bit",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
+                Box::new(Value::String(BitString::decode(arg)?.0)),
             ),
             PgType::Varbit => Value::Ext(
                 "Varbit",
-                Box::new(Value::Binary({
-                    match arg.format() {
-                        PgValueFormat::Binary => arg.as_bytes()?.to_owned(),
-                        PgValueFormat::Text => arg.as_str()?.as_bytes().to_vec(),
-                    }
-                })),
+                Box::new(Value::String(BitString::decode(arg)?.0)),
             ),
-            PgType::Numeric => Decimal::decode(arg)?.into(),
+            PgType::Numeric => decode_numeric(arg)?,
             PgType::Record => Value::Ext(
                 "Record",
                 Box::new(Value::Binary({
@@ -345,7 +289,7 @@ impl Decode for Value {
                     }
                 })),
             ),
-            PgType::Money => Money::decode(arg)?.into(),
+            PgType::Money => Money::decode(arg)?.to_decimal().into(),
             PgType::Void => Value::Ext(
                 "Ext",
                 Box::new(Value::Binary({
@@ -357,6 +301,7 @@ impl Decode for Value {
             ),
             PgType::Custom(ref pg_custom_type) => match &pg_custom_type.kind {
                 PgTypeKind::Enum(_) => Value::String(Decode::decode(arg)?),
+                _ if &*pg_custom_type.name == "hstore" => Hstore::decode(arg)?.into(),
                 _ => Value::Ext(
                     "Custom",
                     Box::new(Value::Binary({
@@ -454,7 +399,7 @@ impl Encode for Value {
             Value::Map(v) => encode_json(Value::Map(v), buf)?,
             Value::Ext(type_name, v) => {
                 match type_name {
-                    "Uuid" => Uuid(v.into_string().unwrap_or_default()).encode(buf)?,
+                    "Uuid" => Uuid::from_str(&v.into_string().unwrap_or_default())?.encode(buf)?,
                     //decimal = 12345678
                     "Decimal" => Decimal::from_str(v.as_str().unwrap_or_default())
                         .unwrap_or_default()
@@ -470,7 +415,7 @@ impl Encode for Value {
                     ).map_err(|e|Error::from(e.to_string()))?)
                     .encode(buf)?,
                     //RFC3339 = "2006-01-02 15:04:05.999999"
-                    "Timestamp" => Timestamp(v.as_i64().unwrap_or_default()).encode(buf)?,
+                    "Timestamp" => Timestamp::from_ext_value(&v).unwrap_or_default().encode(buf)?,
                     "DateTime" => DateTime(fastdate::DateTime::from_str(
                         &v.into_string().unwrap_or_default(),
                     ).map_err(|e|Error::from(e.to_string()))?)
@@ -484,17 +429,17 @@ impl Encode for Value {
                     "Text" => v.into_string().unwrap_or_default().encode(buf)?,
                     "Oid" => Oid::from(v.as_u64().unwrap_or_default() as u32).encode(buf)?,
                     "Json" => Json(v.into_string().unwrap_or_default()).encode(buf)?,
-                    "Point" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Lseg" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Path" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Box" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Polygon" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Line" => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    "Point" => encode_geometry(type_name, *v, buf)?,
+                    "Lseg" => encode_geometry(type_name, *v, buf)?,
+                    "Path" => encode_geometry(type_name, *v, buf)?,
+                    "Box" => encode_geometry(type_name, *v, buf)?,
+                    "Polygon" => encode_geometry(type_name, *v, buf)?,
+                    "Line" => encode_geometry(type_name, *v, buf)?,
                     "Cidr" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "Float4" => (v.as_f64().unwrap_or_default() as f32).encode(buf)?,
                     "Float8" => v.as_f64().unwrap_or_default().encode(buf)?,
                     "Unknown" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Circle" => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    "Circle" => encode_geometry(type_name, *v, buf)?,
                     "Macaddr8" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "Macaddr" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "Inet" => v.into_bytes().unwrap_or_default().encode(buf)?,
@@ -509,8 +454,16 @@ impl Encode for Value {
                         Timetz(rbs::from_value(*v).map_err(|e| Error::from(e.to_string()))?)
                             .encode(buf)?
                     }
-                    "Bit" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Varbit" => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    //Bit = "10110"
+                    "Bit" => match *v {
+                        Value::String(bits) => BitString(bits).encode(buf)?,
+                        v => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    },
+                    //Bit = "10110"
+                    "Varbit" => match *v {
+                        Value::String(bits) => BitString(bits).encode(buf)?,
+                        v => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    },
                     "Numeric" => Decimal::from_str(v.as_str().unwrap_or_default())
                         .unwrap_or_default()
                         .encode(buf)?,
@@ -523,8 +476,13 @@ impl Encode for Value {
                     "DateRange" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "Int8Range" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "Jsonpath" => v.into_bytes().unwrap_or_default().encode(buf)?,
-                    "Money" => Money(v.as_i64().unwrap_or_default()).encode(buf)?,
+                    "Money" => match *v {
+                        Value::String(v) => Decimal::from_str(&v)?.encode(buf)?,
+                        v => Money(v.as_i64().unwrap_or_default()).encode(buf)?,
+                    },
+                    "Hstore" => Hstore::try_from(*v)?.encode(buf)?,
                     "Void" => v.into_bytes().unwrap_or_default().encode(buf)?,
+                    "Enum" => v.into_string().unwrap_or_default().encode(buf)?,
                     "Custom" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "DeclareWithName" => v.into_bytes().unwrap_or_default().encode(buf)?,
                     "DeclareWithOid" => v.into_bytes().unwrap_or_default().encode(buf)?,
@@ -534,3 +492,91 @@ impl Encode for Value {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::arguments::PgArgumentBuffer;
+    use crate::type_info::{PgCustomType, PgType, PgTypeInfo, PgTypeKind};
+    use crate::types::decode::Decode;
+    use crate::types::encode::Encode;
+    use crate::types::{Oid, TypeInfo};
+    use crate::value::{PgValue, PgValueFormat};
+    use rbdc::ext::ustr::UStr;
+    use rbdc::timestamp::Timestamp;
+    use rbs::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_encode_enum() {
+        let v = Value::String("happy".to_string()).into_ext("Enum");
+        assert_eq!(v.type_info().0.oid(), Oid(0));
+        let mut buf = PgArgumentBuffer::default();
+        v.encode(&mut buf).unwrap();
+        assert_eq!(&buf[..], b"happy");
+    }
+
+    #[test]
+    fn test_encode_timestamp() {
+        let encode = |v: Value| {
+            let mut buf = PgArgumentBuffer::default();
+            v.into_ext("Timestamp").encode(&mut buf).unwrap();
+            buf.to_vec()
+        };
+        let expect = |t: Timestamp| {
+            let mut buf = PgArgumentBuffer::default();
+            t.encode(&mut buf).unwrap();
+            buf.to_vec()
+        };
+        // micros are never read as millis, 1971-01-01 included
+        let t = Timestamp::from_unix_seconds(31_536_000);
+        assert_eq!(encode(t.clone().into()), expect(t));
+        // the `U64` millis payload of older releases
+        let millis = 1_713_491_896_123i64;
+        assert_eq!(
+            encode(Value::U64(millis as u64)),
+            expect(Timestamp::from_unix_millis(millis))
+        );
+    }
+
+    #[test]
+    fn test_decode_enum() {
+        let mood = PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+            oid: Oid(16386),
+            name: UStr::Static("mood"),
+            kind: PgTypeKind::Enum(Arc::from(vec!["sad".to_string(), "happy".to_string()])),
+        })));
+        for format in [PgValueFormat::Binary, PgValueFormat::Text] {
+            let v: Value = Decode::decode(PgValue {
+                value: Some(b"happy".to_vec()),
+                type_info: mood.clone(),
+                format,
+            })
+            .unwrap();
+            assert_eq!(v, Value::String("happy".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_hstore() {
+        let mut m = rbs::value::map::ValueMap::new();
+        m.insert("a".into(), "1".into());
+        m.insert("b".into(), Value::Null);
+        let v = Value::Map(m).into_ext("Hstore");
+        assert_eq!(v.type_info(), PgTypeInfo::with_name("hstore"));
+        let mut buf = PgArgumentBuffer::default();
+        v.clone().encode(&mut buf).unwrap();
+
+        let hstore = PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+            oid: Oid(16390),
+            name: UStr::Static("hstore"),
+            kind: PgTypeKind::Simple,
+        })));
+        let decoded: Value = Decode::decode(PgValue {
+            value: Some(buf.to_vec()),
+            type_info: hstore,
+            format: PgValueFormat::Binary,
+        })
+        .unwrap();
+        assert_eq!(decoded, v);
+    }
+}
@@ -1,26 +1,83 @@
 use dark_std::sync::AtomicDuration;
 use futures_core::future::BoxFuture;
+use futures_util::future::join_all;
 use log::info;
 use rbdc::db::{Connection, ExecResult, Row};
 use rbdc::pool::conn_box::ConnectionBox;
 use rbdc::pool::conn_manager::ConnManager;
-use rbdc::pool::Pool;
-use rbdc::Error;
+use rbdc::pool::{Pool, PoolState};
+use rbdc::{Error, ErrorKind, ErrorKindExt};
 use rbs::value::map::ValueMap;
 use rbs::Value;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub mod pool;
 
 #[derive(Debug)]
 pub struct FastPool {
     pub manager: ConnManagerProxy,
-    pub inner: fast_pool::Pool<ConnManagerProxy>,
+    pub inner: pool::Pool<ConnManagerProxy>,
     pub timeout: AtomicDuration,
 }
 
 #[derive(Debug)]
 pub struct ConnManagerProxy {
     inner: ConnManager,
-    conn: Option<fast_pool::ConnectionBox<ConnManagerProxy>>,
+    conn: Option<pool::ConnectionBox<ConnManagerProxy>>,
+    lifetime: Arc<Lifetime>,
+}
+
+/// max lifetime and the creation time of every open connection, shared by the pool and its proxies
+#[derive(Debug)]
+struct Lifetime {
+    max: AtomicDuration,
+    next_id: AtomicU64,
+    created: Mutex<HashMap<u64, Instant>>,
+}
+
+impl Default for Lifetime {
+    fn default() -> Self {
+        Self {
+            max: AtomicDuration::new(None),
+            next_id: AtomicU64::new(0),
+            created: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// a pooled connection and the time it was opened
+#[derive(Debug)]
+pub struct TimestampedGuard {
+    pub inner: ConnectionBox,
+    pub created_at: Instant,
+    id: u64,
+    lifetime: Arc<Lifetime>,
+}
+
+impl Deref for TimestampedGuard {
+    type Target = ConnectionBox;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for TimestampedGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Drop for TimestampedGuard {
+    fn drop(&mut self) {
+        if let Ok(mut created) = self.lifetime.created.lock() {
+            created.remove(&self.id);
+        }
+    }
 }
 
 impl From<ConnManager> for ConnManagerProxy {
@@ -28,6 +85,54 @@ impl From<ConnManager> for ConnManagerProxy {
         ConnManagerProxy {
             inner: value,
             conn: None,
+            lifetime: Arc::new(Lifetime::default()),
+        }
+    }
+}
+
+impl ConnManagerProxy {
+    /// time since the underlying connection was opened, `0` once the connection is dropped
+    pub fn connection_age(&self) -> Duration {
+        self.conn
+            .as_ref()
+            .map(|v| v.created_at.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// take ownership of the pooled connection, e.g. for a long-lived `LISTEN` loop.
+    ///
+    /// the connection is released from the pool and is never returned to it or recycled,
+    /// closing it is up to the caller. its slot is freed on the next checkout, so the pool
+    /// opens a new connection in its place
+    pub fn into_inner(mut self) -> Option<Box<dyn Connection>> {
+        self.take_inner()
+    }
+
+    fn take_inner(&mut self) -> Option<Box<dyn Connection>> {
+        let guard = self.conn.as_mut()?.inner.as_mut()?;
+        let conn = guard.inner.conn.take()?;
+        if let Ok(mut created) = guard.lifetime.created.lock() {
+            created.remove(&guard.id);
+        }
+        // only a failed `check` lowers the pool's connection count: the empty guard goes back
+        // to the idle queue and fails its next `check`, which is how the pool gives up a slot
+        self.conn = None;
+        Some(conn)
+    }
+}
+
+impl FastPool {
+    /// age of the oldest open connection (idle or in use), `None` when there is none
+    pub fn oldest_connection_age(&self) -> Option<Duration> {
+        let created = self.manager.lifetime.created.lock().ok()?;
+        created.values().min().map(|v| v.elapsed())
+    }
+
+    fn proxy(&self, conn: pool::ConnectionBox<ConnManagerProxy>) -> ConnManagerProxy {
+        ConnManagerProxy {
+            inner: conn.manager_proxy.clone(),
+            conn: Some(conn),
+            lifetime: self.manager.lifetime.clone(),
         }
     }
 }
@@ -38,9 +143,15 @@ impl Pool for FastPool {
     where
         Self: Sized,
     {
+        let proxy = ConnManagerProxy::from(manager.clone());
+        let lifetime = proxy.lifetime.clone();
         Ok(Self {
-            manager: manager.clone().into(),
-            inner: fast_pool::Pool::new(manager.into()),
+            manager: proxy,
+            inner: pool::Pool::new(ConnManagerProxy {
+                inner: manager,
+                conn: None,
+                lifetime,
+            }),
             timeout: AtomicDuration::new(None),
         })
     }
@@ -49,42 +160,68 @@ impl Pool for FastPool {
         let v = self
             .inner
             .get_timeout(self.timeout.get())
-            .await
-            .map_err(|e| Error::from(e.to_string()))?;
-        let proxy = ConnManagerProxy {
-            inner: v.manager_proxy.clone(),
-            conn: Some(v),
-        };
-        Ok(Box::new(proxy))
+            .await?;
+        Ok(Box::new(self.proxy(v)))
     }
 
-    async fn get_timeout(&self, mut d: Duration) -> Result<Box<dyn Connection>, Error> {
+    async fn get_timeout(&self, d: Duration) -> Result<Box<dyn Connection>, Error> {
+        self.get_with_timeout_and_label(d, "").await
+    }
+
+    async fn get_with_timeout_and_label(
+        &self,
+        mut d: Duration,
+        label: &str,
+    ) -> Result<Box<dyn Connection>, Error> {
+        let with_label = |msg: String| {
+            if label.is_empty() {
+                msg
+            } else {
+                format!("{} (label: {})", msg, label)
+            }
+        };
         if d.is_zero() {
             let state = self.inner.state();
             if state.in_use < state.max_open {
                 d = Duration::from_secs(10);
             } else {
-                return Err(Error::from("Time out in the connection pool"));
+                return Err(Error::with_kind(
+                    with_label("Time out in the connection pool".to_string()),
+                    ErrorKind::Timeout,
+                ));
             }
         }
         let v = self
             .inner
             .get_timeout(Some(d))
             .await
-            .map_err(|e| Error::from(e.to_string()))?;
-        let proxy = ConnManagerProxy {
-            inner: v.manager_proxy.clone(),
-            conn: Some(v),
-        };
-        Ok(Box::new(proxy))
+            .map_err(|e| Error::from(with_label(e.to_string())))?;
+        Ok(Box::new(self.proxy(v)))
+    }
+
+    /// takes an idle connection, `None` without waiting when there is none. never opens a connection
+    async fn try_get(&self) -> Option<Box<dyn Connection>> {
+        let v = self.inner.try_get().await?;
+        Some(Box::new(self.proxy(v)))
+    }
+
+    /// opens the connections concurrently
+    async fn warmup(&self, n: u64) -> Result<(), Error> {
+        let n = n.min(self.inner.state().max_open);
+        let conns = join_all((0..n).map(|_| self.get())).await;
+        for conn in conns {
+            conn?;
+        }
+        Ok(())
     }
 
     async fn set_timeout(&self, timeout: Option<Duration>) {
         self.timeout.store(timeout);
     }
 
-    async fn set_conn_max_lifetime(&self, _max_lifetime: Option<Duration>) {
-        info!("FastPool not support method set_conn_max_lifetime");
+    /// connections older than `max_lifetime` are closed and replaced when they are next checked out
+    async fn set_conn_max_lifetime(&self, max_lifetime: Option<Duration>) {
+        self.manager.lifetime.max.store(max_lifetime);
     }
 
     async fn set_max_idle_conns(&self, _n: u64) {
@@ -107,19 +244,59 @@ impl Pool for FastPool {
         m.insert("in_use".to_string().into(), state.in_use.into());
         m.insert("idle".to_string().into(), state.idle.into());
         m.insert("waits".to_string().into(), state.waits.into());
+        if let Some(age) = self.oldest_connection_age() {
+            m.insert(
+                "oldest_connection_age".to_string().into(),
+                (age.as_millis() as u64).into(),
+            );
+        }
         Value::Map(m)
     }
+
+    /// the pool does not track `connecting` and `checking`, they are always `0`
+    async fn pool_state(&self) -> PoolState {
+        let state = self.inner.state();
+        PoolState {
+            max_open: state.max_open,
+            connections: state.connections,
+            in_use: state.in_use,
+            idle: state.idle,
+            waits: state.waits,
+            connecting: 0,
+            checking: 0,
+        }
+    }
 }
 
-impl fast_pool::Manager for ConnManagerProxy {
-    type Connection = ConnectionBox;
+impl pool::Manager for ConnManagerProxy {
+    type Connection = TimestampedGuard;
     type Error = Error;
 
     async fn connect(&self) -> Result<Self::Connection, Self::Error> {
-        self.inner.connect().await
+        let inner = self.inner.connect().await?;
+        let created_at = Instant::now();
+        let id = self.lifetime.next_id.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut created) = self.lifetime.created.lock() {
+            created.insert(id, created_at);
+        }
+        Ok(TimestampedGuard {
+            inner,
+            created_at,
+            id,
+            lifetime: self.lifetime.clone(),
+        })
     }
 
     async fn check(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        if conn.inner.conn.is_none() {
+            return Err(Error::from("connection was detached"));
+        }
+        if let Some(max) = self.lifetime.max.get() {
+            if conn.created_at.elapsed() > max {
+                _ = conn.close().await;
+                return Err(Error::from("connection exceeded max_lifetime"));
+            }
+        }
         let r = self.inner.check(conn).await;
         match r {
             Ok(_) => Ok(()),
@@ -131,6 +308,11 @@ impl fast_pool::Manager for ConnManagerProxy {
     }
 }
 
+// the connection was detached by `into_inner` or already returned to the pool
+fn conn_is_drop() -> Error {
+    Error::with_kind("conn is drop".to_string(), ErrorKind::Connection)
+}
+
 impl Connection for ConnManagerProxy {
     fn get_rows(
         &mut self,
@@ -138,7 +320,7 @@ impl Connection for ConnManagerProxy {
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Box<dyn Row>>, Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().get_rows(sql, params)
     }
@@ -149,47 +331,132 @@ impl Connection for ConnManagerProxy {
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Value>, Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().get_values(sql, params)
     }
 
     fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().exec(sql, params)
     }
 
+    fn execute_many(
+        &mut self,
+        sql: &str,
+        params_sets: Vec<Vec<Value>>,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().execute_many(sql, params_sets)
+    }
+
+    fn exec_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn
+            .as_mut()
+            .unwrap()
+            .exec_timeout(sql, params, timeout)
+    }
+
+    fn get_rows_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn
+            .as_mut()
+            .unwrap()
+            .get_rows_timeout(sql, params, timeout)
+    }
+
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().server_version()
+    }
+
+    fn fetch_scalar(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> BoxFuture<'_, Result<Value, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().fetch_scalar(sql, params)
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().table_exists(table)
+    }
+
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().explain(sql, params)
+    }
+
+    /// see [`ConnManagerProxy::into_inner`]
+    fn detach(&mut self) -> Option<Box<dyn Connection>> {
+        self.take_inner()
+    }
+
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        if self.conn.is_none() {
+            return Box::pin(async { Err(conn_is_drop()) });
+        }
+        self.conn.as_mut().unwrap().reset()
+    }
+
     fn ping(&mut self) -> BoxFuture<Result<(), Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().ping()
     }
 
     fn close(&mut self) -> BoxFuture<Result<(), Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().close()
     }
 
     fn begin(&mut self) -> BoxFuture<Result<(), Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().begin()
     }
     fn commit(&mut self) -> BoxFuture<Result<(), Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().commit()
     }
     fn rollback(&mut self) -> BoxFuture<Result<(), Error>> {
         if self.conn.is_none() {
-            return Box::pin(async { Err(Error::from("conn is drop")) });
+            return Box::pin(async { Err(conn_is_drop()) });
         }
         self.conn.as_mut().unwrap().rollback()
     }
@@ -201,19 +468,32 @@ mod test {
     use futures_core::future::BoxFuture;
     use rbdc::db::{ConnectOptions, Connection, Driver, ExecResult, Row};
     use rbdc::pool::conn_manager::ConnManager;
-    use rbdc::pool::Pool;
-    use rbs::{Error, Value};
+    use rbdc::pool::{Pool, PoolState};
+    use rbdc::{Error, ErrorKind, ErrorKindExt};
+    use rbs::Value;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
 
-    #[derive(Debug)]
+    static RESETS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Clone)]
     pub struct Opt {}
     impl ConnectOptions for Opt {
         fn connect(&self) -> BoxFuture<Result<Box<dyn Connection>, Error>> {
             Box::pin(async { Ok(Box::new(Conn {}) as Box<dyn Connection>) })
         }
 
+        fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+            Ok(Box::new(self.clone()))
+        }
+
         fn set_uri(&mut self, _uri: &str) -> Result<(), Error> {
             Ok(())
         }
+
+        fn to_url(&self) -> Result<String, Error> {
+            Ok(String::new())
+        }
     }
 
     #[derive(Debug)]
@@ -236,6 +516,11 @@ mod test {
             Box::pin(async { Ok(ExecResult::default()) })
         }
 
+        fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            RESETS.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        }
+
         fn ping(&mut self) -> BoxFuture<Result<(), Error>> {
             Box::pin(async { Ok(()) })
         }
@@ -273,4 +558,154 @@ mod test {
         let pool = Box::new(FastPool::new(ConnManager::new(D {}, "").unwrap()));
         println!("ok={}", pool.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_try_get() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(1).await;
+        pool.warmup(1).await.unwrap();
+        let conn = pool.try_get().await;
+        assert!(conn.is_some());
+        assert!(pool.try_get().await.is_none());
+        drop(conn);
+        assert!(pool.try_get().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_try_get_none_at_capacity() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(2).await;
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+        let start = Instant::now();
+        assert!(pool.try_get().await.is_none());
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(pool.pool_state().await.waits, 0);
+    }
+
+    #[tokio::test]
+    async fn test_try_get_does_not_connect() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(2).await;
+        let opened = || pool.manager.lifetime.created.lock().unwrap().len();
+        assert!(pool.try_get().await.is_none());
+        assert_eq!(opened(), 0);
+        // fewer than max_open were opened, the idle connection is taken as is
+        drop(pool.get().await.unwrap());
+        assert_eq!(pool.pool_state().await.idle, 1);
+        let conn = pool.try_get().await;
+        assert!(conn.is_some());
+        assert_eq!(opened(), 1);
+        assert_eq!(pool.pool_state().await.idle, 0);
+        assert!(pool.try_get().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pool_state() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(2).await;
+        let _conn = pool.get().await.unwrap();
+        let state = pool.pool_state().await;
+        assert_eq!(state.max_open, 2);
+        assert_eq!(state.in_use, 1);
+        assert_eq!(state.connections, 1);
+        assert_eq!(PoolState::from_value(&pool.state().await), state);
+    }
+
+    #[tokio::test]
+    async fn test_warmup() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(10).await;
+        pool.warmup(5).await.unwrap();
+        let state = pool.pool_state().await;
+        assert_eq!(state.idle, 5);
+        assert_eq!(state.in_use, 0);
+        // capped at max_open
+        pool.set_max_open_conns(7).await;
+        pool.warmup(20).await.unwrap();
+        assert_eq!(pool.pool_state().await.idle, 7);
+    }
+
+    #[tokio::test]
+    async fn test_detach() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(2).await;
+        let mut conn = pool.get().await.unwrap();
+        let mut raw = conn.detach().unwrap();
+        assert!(raw.ping().await.is_ok());
+        assert!(conn.ping().await.is_err());
+        assert!(conn.detach().is_none());
+        drop(conn);
+        let state = pool.inner.state();
+        assert_eq!(state.in_use, 0);
+        assert_eq!(pool.oldest_connection_age(), None);
+
+        // every detach frees its slot, the pool keeps handing out max_open connections
+        for _ in 0..4 {
+            let mut conn = tokio::time::timeout(Duration::from_secs(1), pool.get())
+                .await
+                .unwrap()
+                .unwrap();
+            conn.detach().unwrap();
+        }
+        let a = pool.get().await.unwrap();
+        let b = pool.get().await.unwrap();
+        assert!(pool.try_get().await.is_none());
+        drop((a, b));
+        assert_eq!(pool.pool_state().await.connections, 2);
+    }
+
+    #[tokio::test]
+    async fn test_reset_on_check() {
+        let manager = ConnManager::new(D {}, "").unwrap().reset_on_check(true);
+        assert!(manager.is_reset_on_check());
+        let pool = FastPool::new(manager).unwrap();
+        pool.set_max_open_conns(1).await;
+        drop(pool.get().await.unwrap());
+        let before = RESETS.load(Ordering::SeqCst);
+        // the idle connection is checked (reset) before it is handed out again
+        drop(pool.get().await.unwrap());
+        assert_eq!(RESETS.load(Ordering::SeqCst), before + 1);
+    }
+
+    #[tokio::test]
+    async fn test_max_lifetime() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(1).await;
+        pool.set_conn_max_lifetime(Some(Duration::from_millis(100)))
+            .await;
+        drop(pool.get().await.unwrap());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(pool.oldest_connection_age().unwrap() >= Duration::from_millis(200));
+        // the expired idle connection is replaced by a fresh one
+        let conn = pool.proxy(pool.inner.get().await.unwrap());
+        assert!(conn.connection_age() < Duration::from_millis(50));
+        assert!(pool.oldest_connection_age().unwrap() < Duration::from_millis(50));
+        assert_eq!(pool.pool_state().await.connections, 1);
+        drop(conn);
+        assert!(pool.state().await["oldest_connection_age"]
+            .as_u64()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_with_timeout_and_label() {
+        let pool = FastPool::new(ConnManager::new(D {}, "").unwrap()).unwrap();
+        pool.set_max_open_conns(1).await;
+        let _conn = pool.get().await.unwrap();
+        let e = pool
+            .get_with_timeout_and_label(Duration::from_millis(10), "report_job")
+            .await
+            .err()
+            .unwrap();
+        assert!(e.to_string().contains("(label: report_job)"), "{}", e);
+        assert_eq!(e.kind(), ErrorKind::Timeout);
+        let e = pool
+            .get_with_timeout_and_label(Duration::ZERO, "report_job")
+            .await
+            .err()
+            .unwrap();
+        assert!(e.to_string().contains("(label: report_job)"), "{}", e);
+        assert_eq!(e.kind(), ErrorKind::Timeout);
+    }
 }
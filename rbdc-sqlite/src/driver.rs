@@ -20,7 +20,7 @@ impl Driver for SqliteDriver {
                 let conn = opt.connect().await?;
                 Ok(Box::new(conn) as Box<dyn Connection>)
             } else {
-                Err(Error::from("downcast_ref failure"))
+                Err(Error::from("expected SqliteConnectOptions"))
             }
         })
     }
@@ -29,8 +29,9 @@ impl Driver for SqliteDriver {
         &'a self,
         opt: &'a dyn ConnectOptions,
     ) -> BoxFuture<'a,Result<Box<dyn Connection>, Error>> {
-        let opt: &SqliteConnectOptions = opt.downcast_ref().unwrap();
+        let opt = opt.downcast_ref::<SqliteConnectOptions>();
         Box::pin(async move {
+            let opt = opt.ok_or_else(|| Error::from("expected SqliteConnectOptions"))?;
             let conn = opt.connect().await?;
             Ok(Box::new(conn) as Box<dyn Connection>)
         })
@@ -49,8 +50,44 @@ impl Placeholder for SqliteDriver {
 
 #[cfg(test)]
 mod test {
+    use crate::driver::SqliteDriver;
+    use futures_core::future::BoxFuture;
+    use rbdc::db::{ConnectOptions, Connection, Driver};
+    use rbdc::Error;
+
+    #[derive(Debug, Clone)]
+    struct OtherOptions;
+
+    impl ConnectOptions for OtherOptions {
+        fn connect(&self) -> BoxFuture<'_, Result<Box<dyn Connection>, Error>> {
+            Box::pin(async { Err(Error::from("unused")) })
+        }
+
+        fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+            Ok(Box::new(self.clone()))
+        }
+
+        fn set_uri(&mut self, _uri: &str) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn to_url(&self) -> Result<String, Error> {
+            Ok(String::new())
+        }
+    }
+
     #[test]
     fn test_default() {}
+
+    #[tokio::test]
+    async fn test_connect_opt_wrong_type() {
+        let e = SqliteDriver {}
+            .connect_opt(&OtherOptions)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(e.to_string(), "expected SqliteConnectOptions");
+    }
 }
 // #[cfg(test)]
 // mod test {
@@ -4,7 +4,11 @@ use std::fmt::{self, Display, Formatter};
 use std::os::raw::c_int;
 use std::{borrow::Cow, str::from_utf8_unchecked};
 
-use libsqlite3_sys::{sqlite3, sqlite3_errmsg, sqlite3_extended_errcode};
+use libsqlite3_sys::{
+    sqlite3, sqlite3_errmsg, sqlite3_extended_errcode, SQLITE_AUTH, SQLITE_BUSY, SQLITE_CANTOPEN,
+    SQLITE_CONSTRAINT, SQLITE_LOCKED, SQLITE_MISMATCH, SQLITE_PERM, SQLITE_RANGE, SQLITE_TOOBIG,
+};
+use rbdc::{ErrorKind, ErrorKindExt};
 
 // Error Codes And Messages
 // https://www.sqlite.org/c3ref/errcode.html
@@ -73,6 +77,15 @@ impl SqliteError {
 
 impl From<SqliteError> for rbdc::Error {
     fn from(e: SqliteError) -> Self {
-        Self::from(e.to_string())
+        // https://www.sqlite.org/rescode.html , the low 8 bits are the primary result code
+        let kind = match e.code & 0xff {
+            SQLITE_BUSY | SQLITE_LOCKED => ErrorKind::Timeout,
+            SQLITE_CANTOPEN => ErrorKind::Connection,
+            SQLITE_AUTH | SQLITE_PERM => ErrorKind::Authentication,
+            SQLITE_CONSTRAINT => ErrorKind::ConstraintViolation,
+            SQLITE_MISMATCH | SQLITE_TOOBIG | SQLITE_RANGE => ErrorKind::DataType,
+            _ => return Self::from(e.to_string()),
+        };
+        Self::with_kind(e.to_string(), kind)
     }
 }
@@ -6,8 +6,10 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::FutureExt;
 use futures_util::{StreamExt, TryStreamExt};
+use libsqlite3_sys::sqlite3_get_autocommit;
 use rbdc::db::{Connection, ExecResult, Row};
 use rbdc::error::Error;
+use rbdc::{normalize_server_version, QueryLog};
 use rbs::Value;
 use std::fmt::Write;
 
@@ -55,8 +57,9 @@ impl Connection for SqliteConnection {
         sql: &str,
         params: Vec<Value>,
     ) -> BoxFuture<Result<Vec<Box<dyn Row>>, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = sql.to_owned();
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
                     self.fetch_many(SqliteQuery {
@@ -88,12 +91,14 @@ impl Connection for SqliteConnection {
                 data.push(Box::new(x));
             }
             Ok(data)
-        })
+        });
+        QueryLog::wrap(log, f, |v| v.len() as u64)
     }
 
     fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
+        let log = QueryLog::start(sql, &params);
         let sql = sql.to_owned();
-        Box::pin(async move {
+        let f = Box::pin(async move {
             let many = {
                 if params.len() == 0 {
                     self.fetch_many(SqliteQuery {
@@ -123,17 +128,73 @@ impl Connection for SqliteConnection {
                 })
                 .boxed();
             let v: SqliteQueryResult = v.try_collect().boxed().await?;
-            return Ok(ExecResult {
-                rows_affected: v.rows_affected(),
-                last_insert_id: Value::U64(v.last_insert_rowid as u64),
-            });
-        })
+            return Ok(ExecResult::new(
+                v.rows_affected(),
+                Value::U64(v.last_insert_rowid as u64),
+            ));
+        });
+        QueryLog::wrap(log, f, |v| v.rows_affected)
     }
 
     fn close(&mut self) -> BoxFuture<Result<(), Error>> {
         Box::pin(async { self.do_close().await })
     }
 
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async move {
+            let mut rows = self.get_rows("SELECT sqlite_version()", vec![]).await?;
+            let version = match rows.first_mut() {
+                Some(row) => row.get(0)?.into_string(),
+                None => None,
+            };
+            Ok(version.map(|v| normalize_server_version(&v)))
+        })
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self
+                .get_rows(
+                    "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?",
+                    params,
+                )
+                .await?;
+            Ok(!rows.is_empty())
+        })
+    }
+
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        let sql = format!("EXPLAIN QUERY PLAN {}", sql);
+        Box::pin(async move {
+            let rows = self.get_rows(&sql, params).await?;
+            let mut nodes = Vec::with_capacity(rows.len());
+            for mut row in rows {
+                // id, parent, notused, detail; get() takes the value out, so read backwards
+                let detail = row.get(3)?.into_string().unwrap_or_default();
+                let parent = row.get(1)?.as_i64().unwrap_or_default();
+                let id = row.get(0)?.as_i64().unwrap_or_default();
+                nodes.push((id, parent, detail));
+            }
+            Ok(format_query_plan(&nodes))
+        })
+    }
+
+    /// rolls back an open transaction
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let in_transaction = {
+                let mut handle = self.lock_handle().await?;
+                // SAFETY: the worker thread is not using the handle while it is locked
+                unsafe { sqlite3_get_autocommit(handle.as_raw_handle().as_ptr()) == 0 }
+            };
+            if in_transaction {
+                self.exec("ROLLBACK", vec![]).await?;
+            }
+            Ok(())
+        })
+    }
+
     fn ping(&mut self) -> BoxFuture<Result<(), Error>> {
         Box::pin(async move {
             self.worker
@@ -143,3 +204,130 @@ impl Connection for SqliteConnection {
         })
     }
 }
+
+// `EXPLAIN QUERY PLAN` rows of (id, parent, detail) as a tree, like the sqlite3 shell
+fn format_query_plan(nodes: &[(i64, i64, String)]) -> String {
+    let mut depths: Vec<(i64, usize)> = Vec::with_capacity(nodes.len());
+    let mut lines = Vec::with_capacity(nodes.len() + 1);
+    lines.push("QUERY PLAN".to_string());
+    for (i, (id, parent, detail)) in nodes.iter().enumerate() {
+        let depth = depths
+            .iter()
+            .rev()
+            .find(|(id, _)| id == parent)
+            .map_or(0, |(_, depth)| depth + 1);
+        depths.push((*id, depth));
+        let last = !nodes[i + 1..].iter().any(|(_, p, _)| p == parent);
+        lines.push(format!(
+            "{}{}{}",
+            "   ".repeat(depth),
+            if last { "`--" } else { "|--" },
+            detail
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::options::connect::format_query_plan;
+    use crate::SqliteConnectOptions;
+    use rbdc::db::Connection;
+    use rbs::Value;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_server_version() {
+        let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let version = conn.server_version().await.unwrap().unwrap();
+        let parts: Vec<u32> = version.split('.').map(|v| v.parse().unwrap()).collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], 3);
+    }
+
+    #[tokio::test]
+    async fn test_reset() {
+        let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        conn.exec("CREATE TABLE t (a INTEGER)", vec![])
+            .await
+            .unwrap();
+        conn.reset().await.unwrap();
+        conn.begin().await.unwrap();
+        conn.exec("INSERT INTO t VALUES (1)", vec![]).await.unwrap();
+        conn.reset().await.unwrap();
+        // rolled back, so a new transaction can be started
+        conn.begin().await.unwrap();
+        let count = conn
+            .fetch_scalar("SELECT count(*) FROM t", vec![])
+            .await
+            .unwrap();
+        assert_eq!(count.as_i64(), Some(0));
+    }
+
+    #[test]
+    fn test_format_query_plan() {
+        let nodes = vec![
+            (2, 0, "SCAN a".to_string()),
+            (4, 0, "CORRELATED SCALAR SUBQUERY 1".to_string()),
+            (7, 4, "SEARCH b USING INDEX idx_b (x=?)".to_string()),
+        ];
+        assert_eq!(
+            format_query_plan(&nodes),
+            "QUERY PLAN\n|--SCAN a\n`--CORRELATED SCALAR SUBQUERY 1\n   `--SEARCH b USING INDEX idx_b (x=?)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_table_exists() {
+        let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        assert!(!conn.table_exists("t_exists").await.unwrap());
+        conn.exec("CREATE TABLE t_exists (id INTEGER PRIMARY KEY)", vec![])
+            .await
+            .unwrap();
+        assert!(conn.table_exists("t_exists").await.unwrap());
+        conn.exec("DROP TABLE t_exists", vec![]).await.unwrap();
+        assert!(!conn.table_exists("t_exists").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        conn.exec(
+            "CREATE TABLE t_explain (id INTEGER PRIMARY KEY, name TEXT)",
+            vec![],
+        )
+        .await
+        .unwrap();
+        conn.exec(
+            "CREATE INDEX idx_t_explain_name ON t_explain (name)",
+            vec![],
+        )
+        .await
+        .unwrap();
+        let plan = conn
+            .explain(
+                "SELECT * FROM t_explain WHERE name = ?",
+                vec![Value::String("a".to_string())],
+            )
+            .await
+            .unwrap();
+        assert!(plan.contains("t_explain"), "{}", plan);
+        assert!(plan.contains("idx_t_explain_name"), "{}", plan);
+    }
+}
@@ -19,6 +19,7 @@ pub use synchronous::SqliteSynchronous;
 
 use crate::connection::collation::Collation;
 use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use rbdc::common::DebugFn;
 use rbdc::db::{ConnectOptions, Connection};
 use rbdc::Error;
@@ -374,8 +375,86 @@ impl ConnectOptions for SqliteConnectOptions {
         })
     }
 
+    fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+        Ok(Box::new(self.clone()))
+    }
+
     fn set_uri(&mut self, uri: &str) -> Result<(), Error> {
         *self = SqliteConnectOptions::from_str(uri).map_err(|e| Error::from(e.to_string()))?;
         Ok(())
     }
+
+    fn to_url(&self) -> Result<String, Error> {
+        let mut params = vec![];
+        let mut url = if self.in_memory {
+            if !self.shared_cache {
+                params.push("cache=private");
+            }
+            "sqlite::memory:".to_string()
+        } else {
+            if self.read_only {
+                params.push("mode=ro");
+            }
+            if self.shared_cache {
+                params.push("cache=shared");
+            }
+            // `?`, `#` and `%` in the filename are percent encoded
+            const FILENAME: &AsciiSet = &CONTROLS.add(b'?').add(b'#').add(b'%');
+            let filename = self.filename.to_string_lossy();
+            format!("sqlite://{}", utf8_percent_encode(&filename, FILENAME))
+        };
+        if self.immutable {
+            params.push("immutable=true");
+        }
+        if !params.is_empty() {
+            url.push('?');
+            url.push_str(&params.join("&"));
+        }
+        Ok(url)
+    }
+
+    fn merge(&mut self, other: &dyn ConnectOptions) {
+        let other = match other.downcast_ref::<SqliteConnectOptions>() {
+            Some(v) => v,
+            None => return,
+        };
+        let default = SqliteConnectOptions::new();
+        if other.filename != default.filename || other.in_memory {
+            self.filename = other.filename.clone();
+            self.in_memory = other.in_memory;
+        }
+        if other.read_only {
+            self.read_only = true;
+        }
+        if other.create_if_missing != default.create_if_missing {
+            self.create_if_missing = other.create_if_missing;
+        }
+        if other.shared_cache {
+            self.shared_cache = true;
+        }
+        if other.statement_cache_capacity != default.statement_cache_capacity {
+            self.statement_cache_capacity = other.statement_cache_capacity;
+        }
+        if other.busy_timeout != default.busy_timeout {
+            self.busy_timeout = other.busy_timeout;
+        }
+        if other.immutable {
+            self.immutable = true;
+        }
+        for (key, value) in &other.pragmas {
+            if default.pragmas.get(key) != Some(value) {
+                self.pragmas.insert(key.clone(), value.clone());
+            }
+        }
+        if other.command_channel_size != default.command_channel_size {
+            self.command_channel_size = other.command_channel_size;
+        }
+        if other.row_channel_size != default.row_channel_size {
+            self.row_channel_size = other.row_channel_size;
+        }
+        self.collations.extend(other.collations.iter().cloned());
+        if other.serialized {
+            self.serialized = true;
+        }
+    }
 }
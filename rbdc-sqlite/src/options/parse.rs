@@ -165,3 +165,45 @@ fn test_parse_shared_in_memory() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_to_url() -> Result<(), Error> {
+    use rbdc::db::ConnectOptions;
+
+    let options = SqliteConnectOptions::new()
+        .filename("data/a?b#c%d.db")
+        .read_only(true)
+        .shared_cache(true)
+        .immutable(true);
+    let url = options.to_url()?;
+    assert_eq!(
+        url,
+        "sqlite://data/a%3Fb%23c%25d.db?mode=ro&cache=shared&immutable=true"
+    );
+    let parsed: SqliteConnectOptions = url.parse()?;
+    assert_eq!(parsed.filename, options.filename);
+    assert!(parsed.read_only && parsed.shared_cache && parsed.immutable);
+
+    let options: SqliteConnectOptions = "sqlite::memory:?cache=private".parse()?;
+    let parsed: SqliteConnectOptions = options.to_url()?.parse()?;
+    assert!(parsed.in_memory && !parsed.shared_cache);
+    Ok(())
+}
+
+#[test]
+fn test_merge() -> Result<(), Error> {
+    use rbdc::db::ConnectOptions;
+
+    let mut base: SqliteConnectOptions = "sqlite://base.db?mode=ro".parse()?;
+    let tenant = SqliteConnectOptions::new()
+        .filename("tenant_42.db")
+        .pragma("cache_size", "-4000");
+    base.merge(&tenant);
+    assert_eq!(base.filename.to_str(), Some("tenant_42.db"));
+    assert!(base.read_only);
+    assert_eq!(
+        base.pragmas.get("cache_size").map(|v| v.as_ref()),
+        Some("-4000")
+    );
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use crate::decode::Decode;
 use crate::statement::StatementHandle;
 use crate::{SqliteColumn, SqliteValue, SqliteValueRef};
-use rbdc::db::{MetaData, Row};
+use rbdc::db::{ColumnInfo, MetaData, Row};
 use rbdc::error::Error;
 use rbdc::ext::ustr::UStr;
 use rbs::Value;
@@ -70,6 +70,17 @@ impl MetaData for SqliteMetaData {
     fn column_type(&self, i: usize) -> String {
         self.columns[i].type_info.to_string()
     }
+
+    fn columns(&self) -> Vec<ColumnInfo> {
+        self.columns
+            .iter()
+            .map(|c| ColumnInfo {
+                name: c.name.to_string(),
+                column_type: c.type_info.to_string(),
+                nullable: None,
+            })
+            .collect()
+    }
 }
 
 impl Row for SqliteRow {
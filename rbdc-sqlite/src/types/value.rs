@@ -2,6 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::type_info::DataType;
 use crate::{SqliteArgumentValue, SqliteValue};
+use rbdc::timestamp::Timestamp;
 use rbdc::Error;
 use rbs::Value;
 
@@ -113,7 +114,10 @@ impl Encode for Value {
                     Ok(IsNull::No)
                 }
                 "Timestamp" => {
-                    v.as_i64().unwrap_or_default().encode(args)?;
+                    Timestamp::from_ext_value(&v)
+                        .unwrap_or_default()
+                        .0
+                        .encode(args)?;
                     Ok(IsNull::No)
                 }
                 "Decimal" => {
@@ -1,10 +1,13 @@
-use crate::Error;
+use crate::{explain_text, redact_password, Error, ErrorKind, ErrorKindExt};
 use futures_core::future::BoxFuture;
+use futures_util::FutureExt;
 use rbs::value::map::ValueMap;
 use rbs::Value;
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Deref, DerefMut};
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
 
 /// Represents database driver that can be shared between threads, and can therefore implement
 /// a connection pool
@@ -44,11 +47,19 @@ impl Driver for Box<dyn Driver> {
     }
 }
 
+/// a struct literal should end with `..Default::default()`, or use [`ExecResult::new`],
+/// so fields added later do not break it
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
 pub struct ExecResult {
     pub rows_affected: u64,
     /// If some databases do not support last_insert_id, the default value is Null
     pub last_insert_id: Value,
+    /// warning count reported by the server (mysql), 0 if the database does not report it
+    #[serde(default)]
+    pub warnings: u64,
+    /// notice/info messages raised by the server while executing (postgres notices, mysql `SHOW WARNINGS`, mssql info messages), empty if the database does not report them
+    #[serde(default)]
+    pub messages: Vec<String>,
 }
 
 impl Display for ExecResult {
@@ -61,24 +72,47 @@ impl Display for ExecResult {
                 std::fmt::Display::fmt(&self.inner, f)
             }
         }
-        f.debug_map()
-            .key(&"rows_affected")
+        let mut m = f.debug_map();
+        m.key(&"rows_affected")
             .value(&self.rows_affected)
             .key(&"last_insert_id")
             .value(&DisplayBox {
                 inner: &self.last_insert_id,
-            })
-            .finish()
+            });
+        if self.warnings != 0 {
+            m.key(&"warnings").value(&self.warnings);
+        }
+        if !self.messages.is_empty() {
+            m.key(&"messages").value(&self.messages);
+        }
+        m.finish()
     }
 }
 
-impl From<(u64, Value)> for ExecResult {
-    fn from(value: (u64, Value)) -> Self {
+impl ExecResult {
+    pub fn new(rows_affected: u64, last_insert_id: Value) -> Self {
         Self {
-            rows_affected: value.0,
-            last_insert_id: value.1,
+            rows_affected,
+            last_insert_id,
+            ..Default::default()
         }
     }
+
+    pub fn with_warnings(mut self, warnings: u64) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn with_messages(mut self, messages: Vec<String>) -> Self {
+        self.messages = messages;
+        self
+    }
+}
+
+impl From<(u64, Value)> for ExecResult {
+    fn from(value: (u64, Value)) -> Self {
+        Self::new(value.0, value.1)
+    }
 }
 
 /// Represents a connection to a database
@@ -117,6 +151,128 @@ pub trait Connection: Send {
     /// Execute a query that is expected to update some rows.
     fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>>;
 
+    /// Execute one statement once for every parameter set, such as a batch `INSERT`.
+    /// `rows_affected` is the sum of all executions and `last_insert_id` is taken from the last one.
+    ///
+    /// the default impl calls `exec` in a loop, drivers may override it with a native batch path
+    fn execute_many(
+        &mut self,
+        sql: &str,
+        params_sets: Vec<Vec<Value>>,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        let sql = sql.to_string();
+        Box::pin(async move {
+            let mut result = ExecResult::default();
+            for params in params_sets {
+                let v = self.exec(&sql, params).await?;
+                result.rows_affected += v.rows_affected;
+                result.last_insert_id = v.last_insert_id;
+                result.warnings += v.warnings;
+                result.messages.extend(v.messages);
+            }
+            Ok(result)
+        })
+    }
+
+    /// `exec` with a deadline, an [`ErrorKind::Timeout`] error when it runs longer than `timeout`.
+    ///
+    /// the default impl only stops waiting, the statement may still run on the server and the
+    /// connection may be left mid-response, drivers that can cancel a running statement
+    /// (such as postgres) override it
+    fn exec_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        let f = self.exec(sql, params);
+        Box::pin(async move {
+            crate::rt::timeout(timeout, f)
+                .await
+                .map_err(|_| statement_timeout(timeout))?
+        })
+    }
+
+    /// `get_rows` with a deadline, see [`Connection::exec_timeout`]
+    fn get_rows_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+        let f = self.get_rows(sql, params);
+        Box::pin(async move {
+            crate::rt::timeout(timeout, f)
+                .await
+                .map_err(|_| statement_timeout(timeout))?
+        })
+    }
+
+    /// the database server version, normalized to a semver-like `major.minor.patch` where possible
+    /// (see [`crate::normalize_server_version`]).
+    /// `None` when the driver does not support it
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// the first column of the first row, `Value::Null` when there is no row,
+    /// such as `select count(*) from t`
+    fn fetch_scalar(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> BoxFuture<'_, Result<Value, Error>> {
+        let sql = sql.to_string();
+        Box::pin(async move {
+            let mut rows = self.get_rows(&sql, params).await?;
+            match rows.first_mut() {
+                Some(row) if row.meta_data().column_len() > 0 => row.get(0),
+                _ => Ok(Value::Null),
+            }
+        })
+    }
+
+    /// whether `table` exists.
+    /// the default impl queries `information_schema.tables`
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        let params = vec![Value::String(table.to_string())];
+        Box::pin(async move {
+            let rows = self
+                .get_rows(
+                    "SELECT 1 FROM information_schema.tables WHERE table_name = ?",
+                    params,
+                )
+                .await?;
+            Ok(!rows.is_empty())
+        })
+    }
+
+    /// the query plan of `sql` as text.
+    /// the default impl runs `EXPLAIN <sql>` and joins the result rows with newlines
+    /// (see [`crate::explain_text`])
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        let sql = format!("EXPLAIN {}", sql);
+        Box::pin(async move {
+            let rows = self.get_rows(&sql, params).await?;
+            explain_text(rows)
+        })
+    }
+
+    /// take the underlying connection out of a pool guard, so it is not returned to the pool
+    /// when dropped and will not be recycled.
+    /// `None` when `self` is not a pooled connection (or was already detached),
+    /// the guard answers every later call with an error
+    fn detach(&mut self) -> Option<Box<dyn Connection>> {
+        None
+    }
+
+    /// clear the session state left by the last user before the connection is reused:
+    /// open transactions, temp tables, prepared statements and session variables where the
+    /// database supports it. The default impl does nothing
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async { Ok(()) })
+    }
+
     /// ping
     fn ping(&mut self) -> BoxFuture<Result<(), Error>>;
 
@@ -151,6 +307,50 @@ pub trait Connection: Send {
             Ok(())
         })
     }
+
+    /// run `f` inside `begin`/`commit`. When `f` returns `Err` the transaction is rolled back
+    /// and the error is returned, when it panics the transaction is rolled back and the panic
+    /// resumes. See [`crate::Transaction`] for a guard that works on `&mut dyn Connection`.
+    ///
+    /// ```rust,ignore
+    /// let id = conn
+    ///     .transaction(|conn| {
+    ///         Box::pin(async move {
+    ///             conn.exec("update account set balance = balance - 10 where id = 1", vec![]).await?;
+    ///             conn.exec("update account set balance = balance + 10 where id = 2", vec![]).await?;
+    ///             Ok(2)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    fn transaction<'a, T, F>(&'a mut self, f: F) -> BoxFuture<'a, Result<T, Error>>
+    where
+        Self: Sized,
+        T: Send + 'a,
+        F: for<'c> FnOnce(&'c mut Self) -> BoxFuture<'c, Result<T, Error>> + Send + 'a,
+    {
+        Box::pin(async move {
+            self.begin().await?;
+            match AssertUnwindSafe(f(self)).catch_unwind().await {
+                Ok(Ok(v)) => {
+                    self.commit().await?;
+                    Ok(v)
+                }
+                Ok(Err(e)) => {
+                    if let Err(rollback) = self.rollback().await {
+                        log::warn!("transaction rollback failed: {}", rollback);
+                    }
+                    Err(e)
+                }
+                Err(panic) => {
+                    if let Err(rollback) = self.rollback().await {
+                        log::warn!("transaction rollback failed: {}", rollback);
+                    }
+                    std::panic::resume_unwind(panic)
+                }
+            }
+        })
+    }
 }
 
 impl Connection for Box<dyn Connection> {
@@ -174,6 +374,60 @@ impl Connection for Box<dyn Connection> {
         self.deref_mut().exec(sql, params)
     }
 
+    fn execute_many(
+        &mut self,
+        sql: &str,
+        params_sets: Vec<Vec<Value>>,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        self.deref_mut().execute_many(sql, params_sets)
+    }
+
+    fn exec_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+        self.deref_mut().exec_timeout(sql, params, timeout)
+    }
+
+    fn get_rows_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+        self.deref_mut().get_rows_timeout(sql, params, timeout)
+    }
+
+    fn server_version(&mut self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        self.deref_mut().server_version()
+    }
+
+    fn fetch_scalar(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> BoxFuture<'_, Result<Value, Error>> {
+        self.deref_mut().fetch_scalar(sql, params)
+    }
+
+    fn table_exists(&mut self, table: &str) -> BoxFuture<'_, Result<bool, Error>> {
+        self.deref_mut().table_exists(table)
+    }
+
+    fn explain(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<'_, Result<String, Error>> {
+        self.deref_mut().explain(sql, params)
+    }
+
+    fn detach(&mut self) -> Option<Box<dyn Connection>> {
+        self.deref_mut().detach()
+    }
+
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.deref_mut().reset()
+    }
+
     fn ping(&mut self) -> BoxFuture<Result<(), Error>> {
         self.deref_mut().ping()
     }
@@ -193,6 +447,14 @@ impl Connection for Box<dyn Connection> {
     }
 }
 
+/// the error of [`Connection::exec_timeout`] and [`Connection::get_rows_timeout`]
+pub fn statement_timeout(timeout: Duration) -> Error {
+    Error::with_kind(
+        format!("statement timed out after {:?}", timeout),
+        ErrorKind::Timeout,
+    )
+}
+
 /// Result set from executing a query against a statement
 pub trait Row: 'static + Send + Debug {
     /// get meta data about this result set
@@ -200,6 +462,49 @@ pub trait Row: 'static + Send + Debug {
 
     /// get Value from index
     fn get(&mut self, i: usize) -> Result<Value, Error>;
+
+    /// get Value by column name. an exact match wins, otherwise the one column whose name
+    /// differs only in ASCII case is used, for backends folding names to upper/lower case.
+    /// several such columns are an "ambiguous column" error naming them
+    fn get_by_name(&mut self, name: &str) -> Result<Value, Error> {
+        match find_column(self.meta_data().as_ref(), name)? {
+            Some(i) => self.get(i),
+            None => Err(Error::with_kind(
+                format!("no such column: {}", name),
+                ErrorKind::Sql,
+            )),
+        }
+    }
+
+    /// like [`Row::get_by_name`], `Value::Null` when there is no such column
+    fn try_get_by_name(&mut self, name: &str) -> Result<Value, Error> {
+        match find_column(self.meta_data().as_ref(), name)? {
+            Some(i) => self.get(i),
+            None => Ok(Value::Null),
+        }
+    }
+}
+
+/// [`MetaData::resolve_column`], telling several columns matching `name` apart from none
+fn find_column(meta: &dyn MetaData, name: &str) -> Result<Option<usize>, Error> {
+    if let Some(i) = meta.column_ordinal(name) {
+        return Ok(Some(i));
+    }
+    let candidates: Vec<String> = (0..meta.column_len())
+        .map(|i| meta.column_name(i))
+        .filter(|v| v.eq_ignore_ascii_case(name))
+        .collect();
+    if candidates.len() > 1 {
+        return Err(Error::with_kind(
+            format!(
+                "ambiguous column: {}, candidates: {}",
+                name,
+                candidates.join(", ")
+            ),
+            ErrorKind::Sql,
+        ));
+    }
+    Ok(meta.column_ordinal_ignore_case(name))
 }
 
 /// Meta data for result set
@@ -207,6 +512,53 @@ pub trait MetaData: Debug {
     fn column_len(&self) -> usize;
     fn column_name(&self, i: usize) -> String;
     fn column_type(&self, i: usize) -> String;
+
+    /// the index of the column named `name`
+    fn column_ordinal(&self, name: &str) -> Option<usize> {
+        (0..self.column_len()).find(|i| self.column_name(*i) == name)
+    }
+
+    /// the index of the column named `name` ignoring ASCII case,
+    /// `None` when no column or several columns match
+    fn column_ordinal_ignore_case(&self, name: &str) -> Option<usize> {
+        let mut found = None;
+        for i in 0..self.column_len() {
+            if self.column_name(i).eq_ignore_ascii_case(name) {
+                if found.is_some() {
+                    return None;
+                }
+                found = Some(i);
+            }
+        }
+        found
+    }
+
+    /// [`MetaData::column_ordinal`], falling back to [`MetaData::column_ordinal_ignore_case`]
+    fn resolve_column(&self, name: &str) -> Option<usize> {
+        self.column_ordinal(name)
+            .or_else(|| self.column_ordinal_ignore_case(name))
+    }
+
+    /// every column in one call, in result set order
+    fn columns(&self) -> Vec<ColumnInfo> {
+        (0..self.column_len())
+            .map(|i| ColumnInfo {
+                name: self.column_name(i),
+                column_type: self.column_type(i),
+                nullable: None,
+            })
+            .collect()
+    }
+}
+
+/// a column of a result set, see [`MetaData::columns`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// the same as [`MetaData::column_type`]
+    pub column_type: String,
+    /// `None` when the driver does not report it
+    pub nullable: Option<bool>,
 }
 
 /// connect option
@@ -214,6 +566,14 @@ pub trait ConnectOptions: Any + Send + Sync + Debug + 'static {
     /// Establish a new database connection with the options specified by `self`.
     fn connect(&self) -> BoxFuture<Result<Box<dyn Connection>, Error>>;
 
+    /// a copy of `self`, to open several connections with the same options.
+    /// implement it as `Ok(Box::new(self.clone()))`, the default impl returns an error
+    fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+        Err(Error::from(
+            "clone_box is not implemented by this ConnectOptions",
+        ))
+    }
+
     ///set option
     ///
     /// for exmample:
@@ -251,11 +611,43 @@ pub trait ConnectOptions: Any + Send + Sync + Debug + 'static {
 
     ///set option from uri
     fn set_uri(&mut self, uri: &str) -> Result<(), Error>;
+
+    /// the options as an uri that `set_uri` accepts, the default impl returns an error
+    fn to_url(&self) -> Result<String, Error> {
+        Err(Error::from(
+            "to_url is not implemented by this ConnectOptions",
+        ))
+    }
+
+    /// [`ConnectOptions::to_url`] with the password replaced by `***`, safe to log
+    fn redact_uri(&self) -> String {
+        match self.to_url() {
+            Ok(v) => redact_password(&v),
+            Err(_) => "<redacted>".to_string(),
+        }
+    }
+
+    /// override `self` with the fields of `other` that are not default,
+    /// such as a per-tenant database name over a shared base config.
+    /// `other` of another options type is ignored, the default impl does nothing
+    fn merge(&mut self, _other: &dyn ConnectOptions) {}
+}
+
+/// `Debug` of [`ConnectOptions::redact_uri`], for logging options without the password
+pub struct RedactedDebug<'a>(pub &'a dyn ConnectOptions);
+
+impl Debug for RedactedDebug<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.redact_uri())
+    }
 }
 
 /// database driver ConnectOptions
 impl dyn ConnectOptions {
     pub fn downcast_ref<E: ConnectOptions>(&self) -> Option<&E> {
+        if Any::type_id(self) != TypeId::of::<E>() {
+            return None;
+        }
         let v = unsafe {
             //this is safe
             std::mem::transmute_copy::<&dyn ConnectOptions, &E>(&self)
@@ -264,6 +656,9 @@ impl dyn ConnectOptions {
     }
 
     pub fn downcast_ref_mut<E: ConnectOptions>(&mut self) -> Option<&mut E> {
+        if Any::type_id(self) != TypeId::of::<E>() {
+            return None;
+        }
         let v = unsafe {
             //this is safe
             std::mem::transmute_copy::<&mut dyn ConnectOptions, &mut E>(&self)
@@ -295,3 +690,402 @@ impl dyn ConnectOptions {
 pub trait Placeholder {
     fn exchange(&self, sql: &str) -> String;
 }
+
+#[cfg(test)]
+mod test {
+    use crate::db::{ColumnInfo, ConnectOptions, Connection, ExecResult, MetaData, Row};
+    use crate::{Error, ErrorKind, ErrorKindExt};
+    use futures_core::future::BoxFuture;
+    use futures_util::FutureExt;
+    use rbs::Value;
+    use std::panic::AssertUnwindSafe;
+    use std::time::Duration;
+
+    struct MockConn {
+        sqls: Vec<String>,
+    }
+
+    #[derive(Debug)]
+    struct MockRow(Vec<Value>);
+
+    impl Row for MockRow {
+        fn meta_data(&self) -> Box<dyn MetaData> {
+            Box::new(MockMetaData(self.0.len()))
+        }
+
+        fn get(&mut self, i: usize) -> Result<Value, Error> {
+            Ok(self.0[i].clone())
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockMetaData(usize);
+
+    impl MetaData for MockMetaData {
+        fn column_len(&self) -> usize {
+            self.0
+        }
+
+        fn column_name(&self, i: usize) -> String {
+            format!("c{}", i)
+        }
+
+        fn column_type(&self, _i: usize) -> String {
+            "TEXT".to_string()
+        }
+    }
+
+    impl Connection for MockConn {
+        fn get_rows(
+            &mut self,
+            sql: &str,
+            _params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+            self.sqls.push(sql.to_string());
+            let empty = sql.contains("1 = 0");
+            Box::pin(async move {
+                if empty {
+                    return Ok(vec![]);
+                }
+                Ok(vec![
+                    Box::new(MockRow(vec![
+                        Value::I32(1),
+                        Value::String("SIMPLE".to_string()),
+                        Value::String("t".to_string()),
+                    ])) as Box<dyn Row>,
+                    Box::new(MockRow(vec![
+                        Value::I32(2),
+                        Value::Null,
+                        Value::String("idx_t_a".to_string()),
+                    ])),
+                ])
+            })
+        }
+
+        fn exec(
+            &mut self,
+            sql: &str,
+            params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+            self.sqls.push(sql.to_string());
+            let slow = sql.contains("sleep");
+            Box::pin(async move {
+                if slow {
+                    crate::rt::sleep(Duration::from_secs(5)).await;
+                }
+                Ok(ExecResult::from((params.len() as u64, Value::Null)))
+            })
+        }
+
+        fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn close(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_many() {
+        let mut conn = MockConn { sqls: vec![] };
+        let sets = vec![
+            vec![Value::I32(1), Value::I32(2)],
+            vec![Value::I32(3), Value::I32(4)],
+            vec![Value::I32(5), Value::I32(6)],
+        ];
+        let r = conn
+            .execute_many("insert into t values (?,?)", sets)
+            .await
+            .unwrap();
+        assert_eq!(r.rows_affected, 6);
+        assert_eq!(conn.sqls.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let mut conn = MockConn { sqls: vec![] };
+        let v = conn
+            .transaction(|conn| {
+                Box::pin(async move {
+                    conn.exec("update t set a = 1", vec![]).await?;
+                    Ok(1)
+                })
+            })
+            .await;
+        assert_eq!(v.unwrap(), 1);
+        assert_eq!(conn.sqls, vec!["begin", "update t set a = 1", "commit"]);
+
+        let mut conn = MockConn { sqls: vec![] };
+        let v: Result<(), Error> = conn
+            .transaction(|conn| {
+                Box::pin(async move {
+                    conn.exec("update t set a = 1", vec![]).await?;
+                    Err(Error::from("failed"))
+                })
+            })
+            .await;
+        assert_eq!(v.unwrap_err().to_string(), "failed");
+        assert_eq!(conn.sqls, vec!["begin", "update t set a = 1", "rollback"]);
+
+        // works through a boxed connection
+        let mut conn: Box<dyn Connection> = Box::new(MockConn { sqls: vec![] });
+        let v = conn
+            .transaction(|conn| {
+                Box::pin(async move { conn.fetch_scalar("select 1", vec![]).await })
+            })
+            .await;
+        assert_eq!(v.unwrap(), Value::I32(1));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_panic() {
+        let mut conn = MockConn { sqls: vec![] };
+        let r = AssertUnwindSafe(conn.transaction(|_conn| -> BoxFuture<Result<(), Error>> {
+            Box::pin(async move { panic!("boom") })
+        }))
+        .catch_unwind()
+        .await;
+        assert!(r.is_err());
+        assert_eq!(conn.sqls, vec!["begin", "rollback"]);
+    }
+
+    #[tokio::test]
+    async fn test_exec_timeout() {
+        let mut conn = MockConn { sqls: vec![] };
+        let e = conn
+            .exec_timeout("select sleep(5)", vec![], Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Timeout);
+        assert!(
+            e.to_string().ends_with("statement timed out after 20ms"),
+            "{}",
+            e
+        );
+        let r = conn
+            .exec_timeout(
+                "update t set a = ?",
+                vec![Value::I32(1)],
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(r.rows_affected, 1);
+        let rows = conn
+            .get_rows_timeout("select 1", vec![], Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockOpt {
+        database: Option<String>,
+    }
+
+    impl ConnectOptions for MockOpt {
+        fn connect(&self) -> BoxFuture<'_, Result<Box<dyn Connection>, Error>> {
+            Box::pin(async { Ok(Box::new(MockConn { sqls: vec![] }) as Box<dyn Connection>) })
+        }
+
+        fn clone_box(&self) -> Result<Box<dyn ConnectOptions>, Error> {
+            Ok(Box::new(self.clone()))
+        }
+
+        fn set_uri(&mut self, _uri: &str) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn to_url(&self) -> Result<String, Error> {
+            Ok(String::new())
+        }
+
+        fn merge(&mut self, other: &dyn ConnectOptions) {
+            if let Some(other) = other.downcast_ref::<MockOpt>() {
+                if other.database.is_some() {
+                    self.database = other.database.clone();
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct OtherOpt;
+
+    impl ConnectOptions for OtherOpt {
+        fn connect(&self) -> BoxFuture<'_, Result<Box<dyn Connection>, Error>> {
+            Box::pin(async { Err(Error::from("unused")) })
+        }
+
+        fn set_uri(&mut self, _uri: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_to_url() {
+        assert!(OtherOpt.to_url().is_err());
+        assert_eq!(OtherOpt.redact_uri(), "<redacted>");
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut base = MockOpt {
+            database: Some("main".to_string()),
+        };
+        let other: Box<dyn ConnectOptions> = Box::new(OtherOpt);
+        assert!(other.downcast_ref::<MockOpt>().is_none());
+        base.merge(other.as_ref());
+        base.merge(&MockOpt::default());
+        assert_eq!(base.database.as_deref(), Some("main"));
+        base.merge(&MockOpt {
+            database: Some("tenant_42".to_string()),
+        });
+        assert_eq!(base.database.as_deref(), Some("tenant_42"));
+    }
+
+    #[derive(Debug)]
+    struct NamedRow(Vec<(&'static str, Value)>);
+
+    #[derive(Debug)]
+    struct NamedMetaData(Vec<&'static str>);
+
+    impl Row for NamedRow {
+        fn meta_data(&self) -> Box<dyn MetaData> {
+            Box::new(NamedMetaData(self.0.iter().map(|(k, _)| *k).collect()))
+        }
+
+        fn get(&mut self, i: usize) -> Result<Value, Error> {
+            Ok(self.0[i].1.clone())
+        }
+    }
+
+    impl MetaData for NamedMetaData {
+        fn column_len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn column_name(&self, i: usize) -> String {
+            self.0[i].to_string()
+        }
+
+        fn column_type(&self, _i: usize) -> String {
+            "TEXT".to_string()
+        }
+    }
+
+    #[test]
+    fn test_get_by_name() {
+        let mut row = NamedRow(vec![
+            ("ID", Value::I32(1)),
+            ("name", Value::String("a".to_string())),
+            ("Name", Value::String("b".to_string())),
+        ]);
+        assert_eq!(row.get_by_name("ID").unwrap(), Value::I32(1));
+        assert_eq!(row.get_by_name("id").unwrap(), Value::I32(1));
+        assert_eq!(row.get_by_name("Id").unwrap(), Value::I32(1));
+        // an exact match wins over the other case
+        assert_eq!(
+            row.get_by_name("Name").unwrap(),
+            Value::String("b".to_string())
+        );
+        // `NAME` matches two columns
+        let e = row.get_by_name("NAME").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "ambiguous column: NAME, candidates: name, Name"
+        );
+        assert_eq!(e.kind(), ErrorKind::Sql);
+        assert!(row.try_get_by_name("NAME").is_err());
+        let e = row.get_by_name("age").unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Sql);
+        assert_eq!(row.try_get_by_name("age").unwrap(), Value::Null);
+        assert_eq!(row.try_get_by_name("id").unwrap(), Value::I32(1));
+    }
+
+    #[tokio::test]
+    async fn test_clone_box() {
+        let opt: Box<dyn ConnectOptions> = Box::new(MockOpt {
+            database: Some("main".to_string()),
+        });
+        let copies: Vec<Box<dyn ConnectOptions>> =
+            (0..3).map(|_| opt.clone_box().unwrap()).collect();
+        for copy in copies {
+            let copy = copy.downcast_ref::<MockOpt>().unwrap();
+            assert_eq!(copy.database.as_deref(), Some("main"));
+            assert!(copy.connect().await.is_ok());
+        }
+        assert!(OtherOpt.clone_box().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_table_exists() {
+        let mut conn = MockConn { sqls: vec![] };
+        assert!(conn.table_exists("t").await.unwrap());
+        assert_eq!(
+            conn.sqls,
+            vec!["SELECT 1 FROM information_schema.tables WHERE table_name = ?"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_scalar() {
+        let mut conn = MockConn { sqls: vec![] };
+        let v = conn.fetch_scalar("select count(*) from t", vec![]).await;
+        assert_eq!(v.unwrap(), Value::I32(1));
+        let v = conn
+            .fetch_scalar("select a from t where 1 = 0", vec![])
+            .await;
+        assert_eq!(v.unwrap(), Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let mut conn = MockConn { sqls: vec![] };
+        let plan = conn
+            .explain("select * from t where a = ?", vec![Value::I32(1)])
+            .await
+            .unwrap();
+        assert_eq!(conn.sqls, vec!["EXPLAIN select * from t where a = ?"]);
+        assert_eq!(plan, "1\tSIMPLE\tt\n2\t\tidx_t_a");
+    }
+
+    #[test]
+    fn test_columns() {
+        let meta = MockMetaData(2);
+        assert_eq!(
+            meta.columns(),
+            vec![
+                ColumnInfo {
+                    name: "c0".to_string(),
+                    column_type: "TEXT".to_string(),
+                    nullable: None,
+                },
+                ColumnInfo {
+                    name: "c1".to_string(),
+                    column_type: "TEXT".to_string(),
+                    nullable: None,
+                },
+            ]
+        );
+        assert_eq!(meta.column_ordinal("c1"), Some(1));
+        assert_eq!(meta.column_ordinal("c2"), None);
+    }
+
+    #[test]
+    fn test_exec_result_display() {
+        let r = ExecResult::from((1, Value::U64(2)));
+        assert_eq!(
+            r.to_string(),
+            r#"{"rows_affected": 1, "last_insert_id": 2}"#
+        );
+        let r = ExecResult::new(1, Value::Null)
+            .with_warnings(1)
+            .with_messages(vec!["table \"t\" does not exist, skipping".to_string()]);
+        assert_eq!(
+            r.to_string(),
+            r#"{"rows_affected": 1, "last_insert_id": null, "warnings": 1, "messages": ["table \"t\" does not exist, skipping"]}"#
+        );
+    }
+}
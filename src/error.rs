@@ -1,4 +1,460 @@
+use serde::{de, ser};
+use std::fmt::{Display, Formatter};
+use std::num::{ParseFloatError, ParseIntError, TryFromIntError};
+use std::str::Utf8Error;
 
-pub use rbs::Error;
+/// The error of rbdc.
+///
+/// `E` is a message as [`rbs::Error`] carries it, its kind is read from the text
+/// (see [`ErrorKind::from_message`]). `Kind` is a message of a known [`ErrorKind`],
+/// such as a database error classified by its SQLSTATE. Both display only the message
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum Error {
+    E(String),
+    Kind(ErrorKind, String),
+}
 
-pub use rbs::err_protocol;
\ No newline at end of file
+impl Error {
+    pub fn append(self, arg: &str) -> Error {
+        match self {
+            Error::E(mut e) => {
+                e.push_str(arg);
+                Error::E(e)
+            }
+            Error::Kind(kind, mut e) => {
+                e.push_str(arg);
+                Error::Kind(kind, e)
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    pub fn protocol(err: impl Display) -> Self {
+        Error::from(format!("ProtocolError {}", err))
+    }
+
+    /// the message, without the kind
+    pub fn message(&self) -> &str {
+        match self {
+            Error::E(e) => e,
+            Error::Kind(_, e) => e,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.message(), f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::E(format!("{}", msg))
+    }
+}
+
+impl de::Error for Error {
+    #[cold]
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::E(format!("{}", msg))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(arg: std::io::Error) -> Self {
+        use std::io::ErrorKind as Io;
+        let kind = match arg.kind() {
+            Io::TimedOut => Some(ErrorKind::Timeout),
+            Io::ConnectionRefused
+            | Io::ConnectionReset
+            | Io::ConnectionAborted
+            | Io::NotConnected
+            | Io::BrokenPipe
+            | Io::UnexpectedEof => Some(ErrorKind::Connection),
+            _ => None,
+        };
+        match kind {
+            Some(kind) => Error::Kind(kind, arg.to_string()),
+            None => Error::E(arg.to_string()),
+        }
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::from(e.to_string())
+    }
+}
+
+impl From<&str> for Error {
+    fn from(arg: &str) -> Self {
+        Error::from(arg.to_string())
+    }
+}
+
+impl From<String> for Error {
+    fn from(arg: String) -> Self {
+        Error::E(arg)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(arg: ParseIntError) -> Self {
+        Error::from(arg.to_string())
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(arg: ParseFloatError) -> Self {
+        Error::from(arg.to_string())
+    }
+}
+
+impl From<TryFromIntError> for Error {
+    fn from(e: TryFromIntError) -> Self {
+        Error::from(e.to_string())
+    }
+}
+
+impl From<rbs::Error> for Error {
+    fn from(e: rbs::Error) -> Self {
+        let rbs::Error::E(msg) = e;
+        Error::E(msg)
+    }
+}
+
+impl From<Error> for rbs::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::E(msg) => rbs::Error::E(msg),
+            Error::Kind(_, msg) => rbs::Error::E(msg),
+        }
+    }
+}
+
+/// Format an error message as a `Protocol` error
+#[macro_export]
+macro_rules! err_protocol {
+    ($expr:expr) => {
+        $crate::Error::E($expr.into())
+    };
+
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::Error::E(format!($fmt, $($arg)*))
+    };
+}
+
+/// The category of an [`Error`].
+///
+/// the drivers give their database errors a kind, a plain message is classified by
+/// its text. read it through the [`ErrorKindExt`] trait:
+/// ```rust
+/// use rbdc::{Error, ErrorKind, ErrorKindExt};
+///
+/// let e = Error::with_kind("lock not available".to_string(), ErrorKind::Timeout);
+/// assert_eq!(e.kind(), ErrorKind::Timeout);
+/// assert_eq!(e.to_string(), "lock not available");
+/// let e = Error::from("duplicate key value violates unique constraint \"t_pkey\"");
+/// assert_eq!(e.kind(), ErrorKind::ConstraintViolation);
+/// let e = Error::from("1062 (23000): Duplicate entry '1' for key 'PRIMARY'");
+/// assert_eq!(e.kind(), ErrorKind::ConstraintViolation);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ErrorKind {
+    /// connect failed, or the connection was closed/reset
+    Connection,
+    /// wrong user/password or the user lacks the privilege to log in
+    Authentication,
+    /// unique, foreign key, not null or check constraint failed
+    ConstraintViolation,
+    /// a value can not be encoded/decoded, or is out of range for the column type
+    DataType,
+    /// the database rejected the sql, such as a syntax error or unknown table
+    Sql,
+    /// waited too long for a connection, a lock or the statement
+    Timeout,
+    Unknown,
+}
+
+impl ErrorKind {
+    #[cfg(test)]
+    const ALL: [ErrorKind; 7] = [
+        ErrorKind::Connection,
+        ErrorKind::Authentication,
+        ErrorKind::ConstraintViolation,
+        ErrorKind::DataType,
+        ErrorKind::Sql,
+        ErrorKind::Timeout,
+        ErrorKind::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Connection => "connection",
+            ErrorKind::Authentication => "authentication",
+            ErrorKind::ConstraintViolation => "constraint_violation",
+            ErrorKind::DataType => "data_type",
+            ErrorKind::Sql => "sql",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Unknown => "unknown",
+        }
+    }
+
+    /// classify a five character SQLSTATE, such as `23505` (unique_violation)
+    pub fn from_sql_state(code: &str) -> ErrorKind {
+        match code {
+            // query_canceled (statement_timeout), ODBC timeout expired
+            "57014" | "HYT00" | "HYT01" => return ErrorKind::Timeout,
+            _ => {}
+        }
+        match code.get(0..2) {
+            Some("08") => ErrorKind::Connection,
+            Some("28") => ErrorKind::Authentication,
+            Some("23") => ErrorKind::ConstraintViolation,
+            Some("22") => ErrorKind::DataType,
+            Some("42") => ErrorKind::Sql,
+            _ => ErrorKind::Unknown,
+        }
+    }
+
+    /// classify an error message: the SQLSTATE in it (`... (SQLSTATE 23505)` or mysql's
+    /// `1062 (23000): ...`), then an OS I/O error, then well-known message patterns
+    pub fn from_message(msg: &str) -> ErrorKind {
+        if let Some(kind) = find_sql_state(msg)
+            .map(ErrorKind::from_sql_state)
+            .filter(|v| *v != ErrorKind::Unknown)
+        {
+            return kind;
+        }
+        let msg = msg.to_ascii_lowercase();
+        // an `std::io::Error` of the OS, such as `Connection refused (os error 111)`
+        if msg.contains("(os error ") {
+            return if msg.contains("timed out") {
+                ErrorKind::Timeout
+            } else {
+                ErrorKind::Connection
+            };
+        }
+        for (kind, patterns) in MESSAGE_PATTERNS {
+            if patterns.iter().any(|p| msg.contains(p)) {
+                return kind;
+            }
+        }
+        ErrorKind::Unknown
+    }
+}
+
+// lower case phrases of the drivers and the servers, for messages made without a kind.
+// checked in order
+const MESSAGE_PATTERNS: [(ErrorKind, &[&str]); 6] = [
+    (
+        ErrorKind::Timeout,
+        &["timed out", "timeout", "deadline elapsed"],
+    ),
+    (
+        ErrorKind::Authentication,
+        &[
+            "authentication failed",
+            "access denied",
+            "login failed",
+            "channel binding is required",
+        ],
+    ),
+    (
+        ErrorKind::ConstraintViolation,
+        &[
+            "violates unique constraint",
+            "violates foreign key constraint",
+            "violates not-null constraint",
+            "violates check constraint",
+            "duplicate key",
+            "duplicate entry",
+            "constraint failed",
+            "foreign key constraint fails",
+        ],
+    ),
+    (
+        ErrorKind::Connection,
+        &[
+            "connection refused",
+            "connection reset",
+            "connection closed",
+            "broken pipe",
+        ],
+    ),
+    (
+        ErrorKind::Sql,
+        &[
+            "syntax error",
+            "no such table",
+            "no such column",
+            "unknown column",
+            "ambiguous column",
+        ],
+    ),
+    (
+        ErrorKind::DataType,
+        &[
+            "out of range",
+            "invalid input syntax",
+            "datatype mismatch",
+            "unexpected null",
+        ],
+    ),
+];
+
+// `SQLSTATE xxxxx`, or `(xxxxx): ` as mysql writes it
+fn find_sql_state(msg: &str) -> Option<&str> {
+    let is_code = |v: &str| {
+        v.len() == 5
+            && v.bytes()
+                .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+    };
+    if let Some(v) = msg
+        .find("SQLSTATE ")
+        .and_then(|i| msg.get(i + 9..i + 14))
+        .filter(|v| is_code(v))
+    {
+        return Some(v);
+    }
+    msg.match_indices('(').find_map(|(i, _)| {
+        let v = msg.get(i + 1..i + 6)?;
+        (is_code(v) && msg[i + 6..].starts_with("): ")).then_some(v)
+    })
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// kind support for [`Error`]
+pub trait ErrorKindExt {
+    /// the kind this error was made with, or else the kind of its message
+    /// (see [`ErrorKind::from_message`])
+    fn kind(&self) -> ErrorKind;
+
+    /// new error with a known kind, the message is kept as is.
+    /// `ErrorKind::Unknown` leaves the kind to the message
+    fn with_kind(msg: String, kind: ErrorKind) -> Self;
+}
+
+impl ErrorKindExt for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::E(msg) => ErrorKind::from_message(msg),
+            Error::Kind(kind, _) => *kind,
+        }
+    }
+
+    fn with_kind(msg: String, kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::Unknown => Error::E(msg),
+            kind => Error::Kind(kind, msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Error, ErrorKind, ErrorKindExt};
+
+    #[test]
+    fn test_with_kind() {
+        for kind in ErrorKind::ALL {
+            let e = Error::with_kind(format!("test_with_kind {}", kind), kind);
+            assert_eq!(e.kind(), kind);
+            assert_eq!(e.clone().kind(), kind);
+            assert_eq!(e.to_string(), format!("test_with_kind {}", kind));
+        }
+        // the kind wins over the text
+        let e = Error::with_kind("statement timed out".to_string(), ErrorKind::Sql);
+        assert_eq!(e.kind(), ErrorKind::Sql);
+        assert_eq!(e.to_string(), "statement timed out");
+        let e = Error::with_kind("x".to_string(), ErrorKind::Connection).append(" y");
+        assert_eq!(e.kind(), ErrorKind::Connection);
+        assert_eq!(e.to_string(), "x y");
+        // `Unknown` leaves the kind to the text
+        let e = Error::with_kind("statement timed out".to_string(), ErrorKind::Unknown);
+        assert_eq!(e.kind(), ErrorKind::Timeout);
+        // the kind is lost in an `rbs::Error`, and read from the text again
+        let e: Error = rbs::Error::from(Error::with_kind("x".to_string(), ErrorKind::Sql)).into();
+        assert_eq!(e.kind(), ErrorKind::Unknown);
+    }
+
+    #[test]
+    fn test_kind_from_io_error() {
+        let e = Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        assert_eq!(e.kind(), ErrorKind::Connection);
+        let e = Error::from(std::io::Error::from(std::io::ErrorKind::TimedOut));
+        assert_eq!(e.kind(), ErrorKind::Timeout);
+        let e = Error::from(std::io::Error::other("no such table: t"));
+        assert_eq!(e.kind(), ErrorKind::Sql);
+    }
+
+    #[test]
+    fn test_kind_from_message() {
+        let cases = [
+            ("Connection refused (os error 111)", ErrorKind::Connection),
+            ("Connection timed out (os error 110)", ErrorKind::Timeout),
+            (
+                "db:Notice { .. } (SQLSTATE 23505)",
+                ErrorKind::ConstraintViolation,
+            ),
+            ("db:Notice { .. } (SQLSTATE 42P01)", ErrorKind::Sql),
+            (
+                "1045 (28000): Access denied for user 'root'",
+                ErrorKind::Authentication,
+            ),
+            ("1146 (42S02): Table 'test.t' doesn't exist", ErrorKind::Sql),
+            // a general SQLSTATE falls back to the text
+            (
+                "1205 (HY000): Lock wait timeout exceeded",
+                ErrorKind::Timeout,
+            ),
+            (
+                "duplicate key value violates unique constraint \"t_pkey\"",
+                ErrorKind::ConstraintViolation,
+            ),
+            (
+                "UNIQUE constraint failed: t.id",
+                ErrorKind::ConstraintViolation,
+            ),
+            (
+                "password authentication failed for user \"u\"",
+                ErrorKind::Authentication,
+            ),
+            ("statement timeout exceeded", ErrorKind::Timeout),
+            ("no such table: t", ErrorKind::Sql),
+            ("value out of range for type integer", ErrorKind::DataType),
+            ("conn is drop", ErrorKind::Unknown),
+            ("something else", ErrorKind::Unknown),
+            ("(abc): not a code", ErrorKind::Unknown),
+        ];
+        for (msg, kind) in cases {
+            assert_eq!(Error::from(msg).kind(), kind, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn test_kind_from_sql_state() {
+        assert_eq!(ErrorKind::from_sql_state("08006"), ErrorKind::Connection);
+        assert_eq!(
+            ErrorKind::from_sql_state("28P01"),
+            ErrorKind::Authentication
+        );
+        assert_eq!(
+            ErrorKind::from_sql_state("23505"),
+            ErrorKind::ConstraintViolation
+        );
+        assert_eq!(ErrorKind::from_sql_state("22003"), ErrorKind::DataType);
+        assert_eq!(ErrorKind::from_sql_state("42601"), ErrorKind::Sql);
+        assert_eq!(ErrorKind::from_sql_state("57014"), ErrorKind::Timeout);
+        assert_eq!(ErrorKind::from_sql_state("XX000"), ErrorKind::Unknown);
+    }
+}
@@ -2,7 +2,7 @@ use std::str::from_utf8;
 
 use bytes::{Buf, Bytes};
 use memchr::memchr;
-use rbs::err_protocol;
+use crate::err_protocol;
 use crate::{Error};
 
 pub trait BufExt: Buf {
@@ -35,7 +35,7 @@ impl<S: AsyncWrite + Unpin> Future for WriteAndFlush<'_, S> {
 
         Pin::new(stream)
             .poll_flush(cx)
-            .map_err(|e| Error::from(e.to_string()))
+            .map_err(Error::from)
     }
 }
 
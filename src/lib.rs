@@ -7,10 +7,14 @@ pub mod ext;
 pub mod io;
 pub mod net;
 pub mod pool;
+pub mod query_log;
 pub mod rt;
+pub mod transaction;
 pub mod types;
 pub mod util;
 pub use error::*;
+pub use query_log::*;
+pub use transaction::Transaction;
 pub use util::*;
 #[cfg(test)]
 mod test {
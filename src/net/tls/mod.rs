@@ -10,7 +10,7 @@ use crate::rt::{AsyncRead, AsyncWrite, TlsStream};
 
 use crate::Error;
 use std::mem::replace;
-use rbs::err_protocol;
+use crate::err_protocol;
 
 /// X.509 Certificate input, either a file path or a PEM encoded inline certificate(s).
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -75,6 +75,29 @@ where
         matches!(self, Self::Tls(_))
     }
 
+    /// DER encoded end-entity certificate of the server, `None` when not (yet) TLS
+    pub fn peer_certificate(&self) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "tls-rustls")]
+            MaybeTlsStream::Tls(s) => s
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .map(|cert| cert.as_ref().to_vec()),
+
+            #[cfg(feature = "tls-native-tls")]
+            MaybeTlsStream::Tls(s) => s
+                .get_ref()
+                .peer_certificate()
+                .ok()
+                .flatten()
+                .and_then(|cert| cert.to_der().ok()),
+
+            _ => None,
+        }
+    }
+
     pub async fn upgrade(
         &mut self,
         host: &str,
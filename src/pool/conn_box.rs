@@ -18,6 +18,14 @@ impl Debug for ConnectionBox {
 }
 
 unsafe impl Sync for ConnectionBox {}
+
+impl ConnectionBox {
+    /// take the connection out, it is no longer closed on drop
+    pub fn into_inner(mut self) -> Option<Box<dyn Connection>> {
+        self.conn.take()
+    }
+}
+
 impl Deref for ConnectionBox {
     type Target = Box<dyn Connection>;
 
@@ -1,14 +1,27 @@
-use crate::db::{ConnectOptions, Driver};
+use crate::db::{ConnectOptions, Driver, RedactedDebug};
 use crate::pool::conn_box::ConnectionBox;
 use crate::Error;
+use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::ops::Deref;
 use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct ConnManager {
     pub driver: Arc<Box<dyn Driver>>,
     pub option: Arc<Box<dyn ConnectOptions>>,
+    /// [`ConnManager::check`] calls [`Connection::reset`](crate::db::Connection::reset)
+    /// instead of `ping`, see [`ConnManager::reset_on_check`]. `false` in every constructor
+    pub reset_on_check: bool,
+}
+
+impl Debug for ConnManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnManager")
+            .field("driver", &self.driver)
+            .field("option", &RedactedDebug(self.option.deref().deref()))
+            .finish()
+    }
 }
 
 impl ConnManager {
@@ -27,12 +40,14 @@ impl ConnManager {
         Ok(Self {
             driver: Arc::new(Box::new(driver)),
             option: Arc::new(option),
+            reset_on_check: false,
         })
     }
     pub fn new_option<D: Driver + 'static, Option: ConnectOptions>(driver: D, option: Option) -> Self {
         Self {
             driver: Arc::new(Box::new(driver)),
             option: Arc::new(Box::new(option)),
+            reset_on_check: false,
         }
     }
 
@@ -40,6 +55,7 @@ impl ConnManager {
         Self {
             driver: Arc::new(driver),
             option: Arc::new(option),
+            reset_on_check: false,
         }
     }
 
@@ -47,14 +63,34 @@ impl ConnManager {
         Self {
             driver: driver,
             option: option,
+            reset_on_check: false,
         }
     }
 
+    /// reset the session state of a pooled connection (see [`Connection::reset`](crate::db::Connection::reset))
+    /// every time it is checked, so state left by the last user (an open transaction,
+    /// temp tables, `SET` variables) never leaks to the next one.
+    /// The reset is a round trip, like the `ping` it replaces. The default is `false`
+    pub fn reset_on_check(mut self, enable: bool) -> Self {
+        self.reset_on_check = enable;
+        self
+    }
+
+    /// whether [`ConnManager::check`] resets the connection, see [`ConnManager::reset_on_check`]
+    pub fn is_reset_on_check(&self) -> bool {
+        self.reset_on_check
+    }
+
     pub fn driver_type(&self) -> &str {
         self.driver.name()
     }
 
     pub async fn connect(&self) -> Result<ConnectionBox, Error> {
+        log::debug!(
+            "{} connect {:?}",
+            self.driver.name(),
+            RedactedDebug(self.option.deref().deref())
+        );
         Ok(ConnectionBox {
             conn: Some(self.driver.connect_opt(self.option.deref().deref()).await?),
             manager_proxy: self.clone(),
@@ -63,6 +99,9 @@ impl ConnManager {
     }
 
     pub async fn check(&self, conn: &mut ConnectionBox) -> Result<(), Error> {
+        if self.reset_on_check {
+            return conn.reset().await;
+        }
         return match conn.ping().await {
             Ok(_) => Ok(()),
             Err(e) => Err(e),
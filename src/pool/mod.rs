@@ -9,6 +9,41 @@ use rbs::Value;
 use std::fmt::Debug;
 use std::time::Duration;
 
+/// pool statistics, see [`Pool::pool_state`]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct PoolState {
+    /// max open limit
+    pub max_open: u64,
+    /// connections = in_use + idle
+    pub connections: u64,
+    /// connections taken from the pool
+    pub in_use: u64,
+    /// connections waiting in the pool
+    pub idle: u64,
+    /// callers waiting for a connection
+    pub waits: u64,
+    /// connections being opened
+    pub connecting: u64,
+    /// connections being checked before use
+    pub checking: u64,
+}
+
+impl PoolState {
+    /// read the fields of the map returned by [`Pool::state`], missing fields are `0`
+    pub fn from_value(v: &Value) -> Self {
+        let get = |name: &str| v[name].as_u64().unwrap_or_default();
+        PoolState {
+            max_open: get("max_open"),
+            connections: get("connections"),
+            in_use: get("in_use"),
+            idle: get("idle"),
+            waits: get("waits"),
+            connecting: get("connecting"),
+            checking: get("checking"),
+        }
+    }
+}
+
 #[async_trait]
 pub trait Pool: Sync + Send + Debug {
     /// create an Pool,use ConnManager
@@ -22,6 +57,37 @@ pub trait Pool: Sync + Send + Debug {
     /// get timeout from pool
     async fn get_timeout(&self, d: Duration) -> Result<Box<dyn Connection>, Error>;
 
+    /// get timeout from pool, `label` names the caller (such as `"report_job"`)
+    /// and is included in the error when the pool is saturated.
+    /// the default impl ignores the label
+    async fn get_with_timeout_and_label(
+        &self,
+        d: Duration,
+        _label: &str,
+    ) -> Result<Box<dyn Connection>, Error> {
+        self.get_timeout(d).await
+    }
+
+    /// get an connection without waiting, `None` when none is available right now.
+    /// the default impl calls `get_timeout(Duration::ZERO)`
+    async fn try_get(&self) -> Option<Box<dyn Connection>> {
+        self.get_timeout(Duration::ZERO).await.ok()
+    }
+
+    /// open up to `n` connections (capped at `max_open` when the pool reports it) and return
+    /// them to the pool as idle, so the first requests after startup do not pay for connecting.
+    /// the default impl takes them one after another and releases them together, a pool
+    /// that does not report `max_open` must be able to hand out `n` connections at once
+    async fn warmup(&self, n: u64) -> Result<(), Error> {
+        let max_open = self.pool_state().await.max_open;
+        let n = if max_open == 0 { n } else { n.min(max_open) };
+        let mut conns = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            conns.push(self.get().await?);
+        }
+        Ok(())
+    }
+
     async fn set_timeout(&self, _timeout: Option<Duration>) {}
 
     async fn set_conn_max_lifetime(&self, _max_lifetime: Option<Duration>) {}
@@ -35,6 +101,109 @@ pub trait Pool: Sync + Send + Debug {
         Value::Null
     }
 
+    /// typed [`Pool::state`].
+    /// the default impl reads the map returned by `state()`
+    async fn pool_state(&self) -> PoolState {
+        PoolState::from_value(&self.state().await)
+    }
+
     /// get driver_type from manager: ConnManager
     fn driver_type(&self) -> &str;
 }
+
+#[cfg(test)]
+mod test {
+    use crate::db::{Connection, ExecResult, Row};
+    use crate::pool::conn_manager::ConnManager;
+    use crate::pool::Pool;
+    use crate::Error;
+    use futures_core::future::BoxFuture;
+    use rbs::Value;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// a pool that does not report its state, counting the connections it opens
+    #[derive(Debug, Default)]
+    struct CountingPool {
+        opened: Arc<AtomicU64>,
+        in_use: Arc<AtomicU64>,
+        max_in_use: Arc<AtomicU64>,
+    }
+
+    #[derive(Debug)]
+    struct CountingConn {
+        in_use: Arc<AtomicU64>,
+    }
+
+    impl Drop for CountingConn {
+        fn drop(&mut self) {
+            self.in_use.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    impl Connection for CountingConn {
+        fn get_rows(
+            &mut self,
+            _sql: &str,
+            _params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+            Box::pin(async { Ok(vec![]) })
+        }
+
+        fn exec(
+            &mut self,
+            _sql: &str,
+            _params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+            Box::pin(async { Ok(ExecResult::default()) })
+        }
+
+        fn close(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Pool for CountingPool {
+        fn new(_manager: ConnManager) -> Result<Self, Error> {
+            Ok(Self::default())
+        }
+
+        async fn get(&self) -> Result<Box<dyn Connection>, Error> {
+            // every connection is a new one, as if released connections were closed
+            self.opened.fetch_add(1, Ordering::SeqCst);
+            let in_use = self.in_use.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_use.fetch_max(in_use, Ordering::SeqCst);
+            Ok(Box::new(CountingConn {
+                in_use: self.in_use.clone(),
+            }))
+        }
+
+        async fn get_timeout(&self, _d: Duration) -> Result<Box<dyn Connection>, Error> {
+            self.get().await
+        }
+
+        async fn set_max_idle_conns(&self, _n: u64) {}
+
+        async fn set_max_open_conns(&self, _n: u64) {}
+
+        fn driver_type(&self) -> &str {
+            "counting"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_without_max_open() {
+        let pool = CountingPool::default();
+        pool.warmup(5).await.unwrap();
+        assert_eq!(pool.opened.load(Ordering::SeqCst), 5);
+        // all 5 are held together, so a pool that keeps idle connections keeps 5
+        assert_eq!(pool.max_in_use.load(Ordering::SeqCst), 5);
+        assert_eq!(pool.in_use.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::Error;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::{stream, StreamExt};
+use rbs::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Called after every `get_rows`/`exec` of the bundled drivers with
+/// `(sql, params, elapsed, result)`, where result is the number of rows returned (`get_rows`)
+/// or affected (`exec`)
+pub type QueryLogger = dyn Fn(&str, &[Value], Duration, &Result<u64, Error>) + Send + Sync;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LOGGER: RwLock<Option<Arc<QueryLogger>>> = RwLock::new(None);
+
+/// set (or remove with `None`) the global query logger
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// rbdc::set_query_logger(Some(Arc::new(|sql, params, elapsed, result| {
+///     println!("{} {:?} {:?} {:?}", sql, params, elapsed, result);
+/// })));
+/// rbdc::set_query_logger(None);
+/// ```
+pub fn set_query_logger(logger: Option<Arc<QueryLogger>>) {
+    let mut v = LOGGER.write().unwrap_or_else(|e| e.into_inner());
+    ENABLED.store(logger.is_some(), Ordering::Release);
+    *v = logger;
+}
+
+/// the current global query logger
+pub fn query_logger() -> Option<Arc<QueryLogger>> {
+    if !ENABLED.load(Ordering::Acquire) {
+        return None;
+    }
+    LOGGER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// One query being timed for the query logger, used by drivers:
+/// ```rust,ignore
+/// fn exec(&mut self, sql: &str, params: Vec<Value>) -> BoxFuture<Result<ExecResult, Error>> {
+///     let log = QueryLog::start(sql, &params);
+///     let f = Box::pin(async move { ... });
+///     QueryLog::wrap(log, f, |v| v.rows_affected)
+/// }
+/// ```
+pub struct QueryLog {
+    logger: Arc<QueryLogger>,
+    sql: String,
+    params: Vec<Value>,
+    start: Instant,
+}
+
+impl QueryLog {
+    /// `None` when no logger is set, so sql and params are only copied while logging
+    pub fn start(sql: &str, params: &[Value]) -> Option<QueryLog> {
+        let logger = query_logger()?;
+        Some(QueryLog {
+            logger,
+            sql: sql.to_string(),
+            params: params.to_vec(),
+            start: Instant::now(),
+        })
+    }
+
+    pub fn finish(self, result: Result<u64, &Error>) {
+        let result = result.map_err(|e| e.clone());
+        (self.logger)(&self.sql, &self.params, self.start.elapsed(), &result);
+    }
+
+    /// report `f` to `log` once it completes, `f` is returned as is when `log` is `None`
+    pub fn wrap<'a, T: Send + 'a>(
+        log: Option<QueryLog>,
+        f: BoxFuture<'a, Result<T, Error>>,
+        count: fn(&T) -> u64,
+    ) -> BoxFuture<'a, Result<T, Error>> {
+        match log {
+            None => f,
+            Some(log) => Box::pin(async move {
+                let result = f.await;
+                log.finish(result.as_ref().map(count));
+                result
+            }),
+        }
+    }
+
+    /// report `s` to `log` with the number of items once it ends, or with the first error.
+    /// a stream dropped before that is not reported
+    pub fn wrap_stream<'a, T: Send + 'a>(
+        log: Option<QueryLog>,
+        s: BoxStream<'a, Result<T, Error>>,
+    ) -> BoxStream<'a, Result<T, Error>> {
+        match log {
+            None => s,
+            Some(log) => Box::pin(stream::unfold(
+                (s, Some(log), 0u64),
+                |(mut s, mut log, mut n)| async move {
+                    let item = s.next().await;
+                    match &item {
+                        Some(Ok(_)) => n += 1,
+                        Some(Err(e)) => {
+                            if let Some(log) = log.take() {
+                                log.finish(Err(e));
+                            }
+                        }
+                        None => {
+                            if let Some(log) = log.take() {
+                                log.finish(Ok(n));
+                            }
+                        }
+                    }
+                    Some((item?, (s, log, n)))
+                },
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{set_query_logger, Error, QueryLog};
+    use futures_util::{stream, StreamExt};
+    use rbs::Value;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_query_log() {
+        assert!(QueryLog::start("select 1", &[]).is_none());
+        let logs = Arc::new(Mutex::new(vec![]));
+        let l = logs.clone();
+        set_query_logger(Some(Arc::new(move |sql, params, _elapsed, result| {
+            l.lock()
+                .unwrap()
+                .push((sql.to_string(), params.to_vec(), result.clone().ok()));
+        })));
+        let log = QueryLog::start("select ?", &[Value::I32(1)]).unwrap();
+        log.finish(Ok(1));
+        let log = QueryLog::start("selec", &[]).unwrap();
+        log.finish(Err(&Error::from("syntax error")));
+        let rows = stream::iter(vec![Ok(1), Ok(2)]).boxed();
+        let rows = QueryLog::wrap_stream(QueryLog::start("select a", &[]), rows);
+        let rows: Vec<Result<i32, Error>> = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(rows.collect());
+        assert_eq!(rows.len(), 2);
+        set_query_logger(None);
+        assert!(QueryLog::start("select 1", &[]).is_none());
+        assert_eq!(
+            *logs.lock().unwrap(),
+            vec![
+                ("select ?".to_string(), vec![Value::I32(1)], Some(1)),
+                ("selec".to_string(), vec![], None),
+                ("select a".to_string(), vec![], Some(2)),
+            ]
+        );
+    }
+}
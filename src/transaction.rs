@@ -0,0 +1,152 @@
+use crate::db::Connection;
+use crate::Error;
+use futures_core::future::BoxFuture;
+use std::ops::{Deref, DerefMut};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// A transaction on a borrowed connection, rolled back unless [`Transaction::commit`] is called.
+///
+/// ```rust,ignore
+/// let mut tx = Transaction::begin(&mut conn).await?;
+/// tx.exec("update t set a = ?", vec![Value::I32(1)]).await?; // `?` here rolls back
+/// tx.commit().await?;
+/// ```
+///
+/// dropping an unfinished transaction rolls back with `block_in_place`, which needs the
+/// multi-thread tokio runtime. On any other runtime the rollback is skipped with a warning
+/// and the connection stays inside the transaction, so prefer an explicit [`Transaction::rollback`]
+pub struct Transaction<'a> {
+    // `None` once committed or rolled back
+    conn: Option<&'a mut dyn Connection>,
+}
+
+impl<'a> Transaction<'a> {
+    pub async fn begin(conn: &'a mut dyn Connection) -> Result<Transaction<'a>, Error> {
+        conn.begin().await?;
+        Ok(Transaction { conn: Some(conn) })
+    }
+
+    pub async fn commit(mut self) -> Result<(), Error> {
+        match self.conn.take() {
+            Some(conn) => conn.commit().await,
+            None => Ok(()),
+        }
+    }
+
+    pub fn rollback(mut self) -> BoxFuture<'a, Result<(), Error>> {
+        let conn = self.conn.take();
+        Box::pin(async move {
+            match conn {
+                Some(conn) => conn.rollback().await,
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+impl<'a> Deref for Transaction<'a> {
+    type Target = dyn Connection + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn.as_deref().unwrap()
+    }
+}
+
+impl<'a> DerefMut for Transaction<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn.as_deref_mut().unwrap()
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                let r = tokio::task::block_in_place(|| handle.block_on(conn.rollback()));
+                if let Err(e) = r {
+                    log::warn!("rollback of dropped transaction failed: {}", e);
+                }
+            }
+            _ => {
+                log::warn!(
+                    "transaction dropped outside a multi-thread tokio runtime, rollback skipped"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{Connection, ExecResult, Row};
+    use crate::transaction::Transaction;
+    use crate::Error;
+    use futures_core::future::BoxFuture;
+    use rbs::Value;
+
+    #[derive(Default)]
+    struct LogConn {
+        sqls: Vec<String>,
+    }
+
+    impl Connection for LogConn {
+        fn get_rows(
+            &mut self,
+            _sql: &str,
+            _params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<Vec<Box<dyn Row>>, Error>> {
+            Box::pin(async { Ok(vec![]) })
+        }
+
+        fn exec(
+            &mut self,
+            sql: &str,
+            _params: Vec<Value>,
+        ) -> BoxFuture<'_, Result<ExecResult, Error>> {
+            self.sqls.push(sql.to_string());
+            Box::pin(async { Ok(ExecResult::default()) })
+        }
+
+        fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn close(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    async fn update(conn: &mut LogConn, fail: bool) -> Result<(), Error> {
+        let mut tx = Transaction::begin(conn).await?;
+        tx.exec("update t set a = 1", vec![]).await?;
+        if fail {
+            return Err(Error::from("fail"));
+        }
+        tx.commit().await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_commit() {
+        let mut conn = LogConn::default();
+        update(&mut conn, false).await.unwrap();
+        assert_eq!(conn.sqls, ["begin", "update t set a = 1", "commit"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rollback_on_drop() {
+        let mut conn = LogConn::default();
+        assert!(update(&mut conn, true).await.is_err());
+        assert_eq!(conn.sqls, ["begin", "update t set a = 1", "rollback"]);
+    }
+
+    #[tokio::test]
+    async fn test_rollback() {
+        let mut conn = LogConn::default();
+        let tx = Transaction::begin(&mut conn).await.unwrap();
+        tx.rollback().await.unwrap();
+        assert_eq!(conn.sqls, ["begin", "rollback"]);
+    }
+}
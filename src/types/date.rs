@@ -29,8 +29,9 @@ impl From<Date> for Value {
 impl FromStr for Date {
     type Err = Error;
 
+    /// accepts `2024-01-02`, any time part after a space or `T` is ignored
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Date(fastdate::Date::from_str(s).map_err(|e|Error::from(e.to_string()))?))
+        Ok(Date(fastdate::Date::from_str(s.trim()).map_err(|e|Error::from(e.to_string()))?))
     }
 }
 
@@ -46,6 +47,35 @@ impl From<DateTime> for Date {
     }
 }
 
+impl TryFrom<Value> for Date {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Ext(_, v) => Date::try_from(*v),
+            Value::String(v) => Date::from_str(&v),
+            _ => Err(Error::from(format!("warn type decode Date:{}", value))),
+        }
+    }
+}
+
+impl Date {
+    /// days elapsed since `other`, negative when `other` is later than self
+    pub fn days_since(&self, other: &Date) -> i64 {
+        self.unix_days() - other.unix_days()
+    }
+
+    /// add (or sub when negative) n days
+    pub fn add_days(&self, n: i64) -> Date {
+        let dt = DateTime::from(self.0.clone()).add_sub_sec(n * 86400);
+        Date(dt.into())
+    }
+
+    fn unix_days(&self) -> i64 {
+        DateTime::from(self.0.clone()).unix_timestamp().div_euclid(86400)
+    }
+}
+
 impl Default for Date {
     fn default() -> Self {
         Date(fastdate::Date {
@@ -55,3 +85,66 @@ impl Default for Date {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::Date;
+    use rbs::Value;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_from_str_formats() {
+        for s in [
+            "2024-01-02",
+            "2024-01-02 15:04",
+            "2024-01-02 15:04:05",
+            "2024-01-02T15:04:05",
+            "2024-01-02 15:04:05.123",
+            "2024-01-02T15:04:05.1234567Z",
+            " 2024-01-02 ",
+        ] {
+            assert_eq!(Date::from_str(s).unwrap().to_string(), "2024-01-02", "{}", s);
+        }
+        assert!(Date::from_str("2024/01").is_err());
+    }
+
+    #[test]
+    fn test_leap_year() {
+        let d = Date::from_str("2024-02-29").unwrap();
+        assert_eq!(d.0.year, 2024);
+        assert_eq!(d.0.mon, 2);
+        assert_eq!(d.0.day, 29);
+        assert_eq!(d.to_string(), "2024-02-29");
+        assert_eq!(d.add_days(1).to_string(), "2024-03-01");
+    }
+
+    #[test]
+    fn test_days_since() {
+        let a = Date::from_str("2024-03-01").unwrap();
+        let b = Date::from_str("2024-02-01").unwrap();
+        assert_eq!(a.days_since(&b), 29);
+        assert_eq!(b.days_since(&a), -29);
+        assert_eq!(Date::default().days_since(&Date::default()), 0);
+        assert_eq!(a.add_days(-29), b);
+        assert_eq!(
+            Date::from_str("1969-12-31")
+                .unwrap()
+                .days_since(&Date::default()),
+            -1
+        );
+    }
+
+    #[test]
+    fn test_ser_de() {
+        let d = Date::from_str("2024-02-29").unwrap();
+        let v = rbs::value!(&d);
+        assert_eq!(
+            v,
+            Value::Ext("Date", Box::new(Value::String("2024-02-29".to_string())))
+        );
+        let r: Date = rbs::from_value(v.clone()).unwrap();
+        assert_eq!(r, d);
+        assert_eq!(Date::try_from(v).unwrap(), d);
+        assert_eq!(Date::default().to_string(), "1970-01-01");
+    }
+}
@@ -82,6 +82,35 @@ impl DateTime {
         Self(self.0.set_offset(offset_sec))
     }
 
+    /// the same instant at `offset_sec` seconds east of UTC
+    /// ```rust
+    /// use std::str::FromStr;
+    /// let dt = rbdc::DateTime::from_str("2024-01-01T10:00:00+08:00").unwrap();
+    /// assert_eq!(dt.to_offset(0).hour(), 2);
+    /// assert_eq!(dt.to_offset(-3600).hour(), 1);
+    /// ```
+    pub fn to_offset(&self, offset_sec: i32) -> DateTime {
+        Self(self.0.clone().set_offset(offset_sec))
+    }
+
+    /// same as [`DateTime::to_offset`]
+    pub fn to_timezone(&self, offset_sec: i32) -> DateTime {
+        self.to_offset(offset_sec)
+    }
+
+    /// convert to UTC, keeping the same instant
+    pub fn to_utc(&self) -> DateTime {
+        self.to_offset(0)
+    }
+
+    /// move the instant by `sec` seconds (negative goes back), the offset is kept.
+    ///
+    /// `to_offset(off).add_seconds(-off)` keeps the wall clock and changes the instant,
+    /// such as reading a zone-less UTC value as local time
+    pub fn add_seconds(self, sec: i64) -> Self {
+        Self(self.0.add_sub_sec(sec))
+    }
+
     pub fn add(self, d: Duration) -> Self {
         Self(self.0.add(d))
     }
@@ -94,6 +123,27 @@ impl DateTime {
         Self(self.0.add_sub_sec(sec))
     }
 
+    /// absolute duration between self and other
+    pub fn duration_since(&self, other: &DateTime) -> Duration {
+        let nano = self.unix_timestamp_nano() - other.unix_timestamp_nano();
+        Duration::from_nanos(nano.unsigned_abs() as u64)
+    }
+
+    /// return a new DateTime add duration
+    pub fn add_duration(&self, d: Duration) -> DateTime {
+        Self(self.0.clone().add(d))
+    }
+
+    /// return a new DateTime sub duration
+    pub fn sub_duration(&self, d: Duration) -> DateTime {
+        Self(self.0.clone().sub(d))
+    }
+
+    /// signed count of whole days from other to self
+    pub fn days_since(&self, other: &DateTime) -> i64 {
+        (self.unix_timestamp_millis() - other.unix_timestamp_millis()) / 86_400_000
+    }
+
     pub fn before(&self, other: &DateTime) -> bool {
         self.0.before(&other.0)
     }
@@ -145,6 +195,52 @@ impl DateTime {
         self.0.format(fmt)
     }
 
+    /// format by strftime style pattern.
+    /// support token = ["%Y","%m","%d","%H","%M","%S","%f","%Z","%%"],
+    /// `%f` is micro seconds(6 digits), `%Z` is offset like `+08:00` or `Z`
+    /// ```
+    ///   let dt = rbdc::DateTime::from_timestamp(0);
+    ///   assert_eq!(dt.strftime("%d/%m/%Y").unwrap(), "01/01/1970");
+    /// ```
+    pub fn strftime(&self, pattern: &str) -> Result<String, Error> {
+        let mut s = String::with_capacity(pattern.len() + 16);
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                s.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => s.push_str(&format!("{:04}", self.year())),
+                Some('m') => s.push_str(&format!("{:02}", self.mon())),
+                Some('d') => s.push_str(&format!("{:02}", self.day())),
+                Some('H') => s.push_str(&format!("{:02}", self.hour())),
+                Some('M') => s.push_str(&format!("{:02}", self.minute())),
+                Some('S') => s.push_str(&format!("{:02}", self.sec())),
+                Some('f') => s.push_str(&format!("{:06}", self.micro())),
+                Some('Z') => {
+                    let offset = self.offset();
+                    if offset == 0 {
+                        s.push('Z');
+                    } else {
+                        let sign = if offset < 0 { '-' } else { '+' };
+                        let offset = offset.abs();
+                        s.push_str(&format!(
+                            "{}{:02}:{:02}",
+                            sign,
+                            offset / 3600,
+                            offset % 3600 / 60
+                        ));
+                    }
+                }
+                Some('%') => s.push('%'),
+                Some(v) => return Err(Error::from(format!("unsupported format token '%{}'", v))),
+                None => return Err(Error::from("unsupported format token '%' at end")),
+            }
+        }
+        Ok(s)
+    }
+
     /// parse an string by format.
     /// format str must be:
     /// ```rust
@@ -321,12 +417,40 @@ impl From<(Date, Time, i32)> for DateTime {
 impl FromStr for DateTime {
     type Err = Error;
 
+    /// accepts `2024-01-02`, `2024-01-02 15:04`, `2024-01-02 15:04:05`
+    /// and `2024-01-02T15:04:05.123456`, optionally followed by `Z` or an offset
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(DateTime(
-            fastdate::DateTime::from_str(s)
-                .map_err(|e| crate::error::Error::from(e.to_string()))?,
-        ))
+        let s = s.trim();
+        let v = match fastdate::DateTime::from_str(s) {
+            Ok(v) => v,
+            Err(e) => match with_seconds(s, 11) {
+                Some(s) => fastdate::DateTime::from_str(&s),
+                None => Err(e),
+            }
+            .map_err(|e| crate::error::Error::from(e.to_string()))?,
+        };
+        Ok(DateTime(v))
+    }
+}
+
+/// `hh:mm` starting at `at` without seconds -> insert `:00`
+pub(crate) fn with_seconds(s: &str, at: usize) -> Option<String> {
+    let b = s.as_bytes();
+    let hh_mm = b.get(at..at + 5)?;
+    if !(hh_mm[0].is_ascii_digit()
+        && hh_mm[1].is_ascii_digit()
+        && hh_mm[2] == b':'
+        && hh_mm[3].is_ascii_digit()
+        && hh_mm[4].is_ascii_digit())
+        || b.get(at + 5) == Some(&b':')
+    {
+        return None;
     }
+    let mut v = String::with_capacity(s.len() + 3);
+    v.push_str(&s[..at + 5]);
+    v.push_str(":00");
+    v.push_str(&s[at + 5..]);
+    Some(v)
 }
 
 impl From<DateTime> for Value {
@@ -363,6 +487,50 @@ impl Default for DateTime {
 mod test {
     use crate::datetime::DateTime;
     use std::str::FromStr;
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_str_formats() {
+        let cases = [
+            ("2024-01-02", "2024-01-02T00:00:00Z"),
+            ("2024-01-02 15:04", "2024-01-02T15:04:00Z"),
+            ("2024-01-02T15:04", "2024-01-02T15:04:00Z"),
+            ("2024-01-02 15:04:05", "2024-01-02T15:04:05Z"),
+            ("2024-01-02T15:04:05", "2024-01-02T15:04:05Z"),
+            ("2024-01-02 15:04:05.123", "2024-01-02T15:04:05.123Z"),
+            (
+                "2024-01-02T15:04:05.1234567",
+                "2024-01-02T15:04:05.1234567Z",
+            ),
+            ("2024-01-02T15:04:05Z", "2024-01-02T15:04:05Z"),
+            ("2024-01-02 15:04+08:00", "2024-01-02T15:04:00+08:00"),
+            (" 2024-01-02 15:04:05 ", "2024-01-02T15:04:05Z"),
+        ];
+        for (s, expect) in cases {
+            assert_eq!(DateTime::from_str(s).unwrap().to_string(), expect, "{}", s);
+        }
+        assert!(DateTime::from_str("2024-01-02 1504").is_err());
+    }
+
+    #[test]
+    fn test_offset_helpers() {
+        let dt = DateTime::from_str("2500-06-30T23:30:00Z").unwrap();
+        let east = dt.to_offset(8 * 3600);
+        assert_eq!(east.to_string(), "2500-07-01T07:30:00+08:00");
+        assert_eq!(east.unix_timestamp(), dt.unix_timestamp());
+        assert_eq!(east.to_utc(), dt);
+        assert_eq!(
+            dt.clone().add_seconds(3600).to_string(),
+            "2500-07-01T00:30:00Z"
+        );
+        assert_eq!(
+            dt.clone().add_seconds(-86400).to_string(),
+            "2500-06-29T23:30:00Z"
+        );
+        // keep the wall clock, change the zone
+        let local = dt.to_offset(-5 * 3600).add_seconds(5 * 3600);
+        assert_eq!(local.to_string(), "2500-06-30T23:30:00-05:00");
+    }
 
     #[test]
     fn test_ser_de() {
@@ -394,7 +562,7 @@ mod test {
     #[test]
     fn test_de3() {
         let dt = vec![DateTime::from_str("2023-10-21T00:15:00.9233333+08:00").unwrap()];
-        let v = rbs::to_value!(&dt);
+        let v = rbs::value!(&dt);
         let new_dt: Vec<DateTime> = rbs::from_value(v).unwrap();
         assert_eq!(new_dt, dt);
     }
@@ -402,7 +570,7 @@ mod test {
     #[test]
     fn test_de4() {
         let dt = DateTime::from_str("2023-10-21T00:15:00.9233333+08:00").unwrap();
-        let v = rbs::to_value!(&dt.unix_timestamp_millis());
+        let v = rbs::value!(&dt.unix_timestamp_millis());
         let new_dt: DateTime = rbs::from_value(v).unwrap();
         assert_eq!(
             new_dt,
@@ -428,6 +596,58 @@ mod test {
         assert_eq!(dt.to_string(), "1970-01-01T00:00:00Z");
     }
 
+    #[test]
+    fn test_to_timezone() {
+        let dt = DateTime::from_str("2024-01-01T10:30:00+08:00").unwrap();
+        let utc = dt.to_utc();
+        assert_eq!(utc.hour(), dt.hour() - 8);
+        assert_eq!(utc.offset(), 0);
+        assert_eq!(utc.unix_timestamp_nano(), dt.unix_timestamp_nano());
+        assert_eq!(utc.to_string(), "2024-01-01T02:30:00Z");
+
+        let ny = dt.to_timezone(-5 * 3600);
+        assert_eq!(ny.day(), 31);
+        assert_eq!(ny.hour(), 21);
+        assert_eq!(ny.offset(), -5 * 3600);
+    }
+
+    #[test]
+    fn test_add_sub_duration() {
+        let dt = DateTime::from_str("2024-02-28T23:30:00.123456+08:00").unwrap();
+        let d = Duration::from_secs(3600);
+        assert_eq!(dt.add_duration(d).sub_duration(d), dt);
+        assert_eq!(dt.add_duration(d).day(), 29);
+        assert_eq!(dt.add_duration(d).duration_since(&dt), d);
+        assert_eq!(dt.duration_since(&dt.add_duration(d)), d);
+    }
+
+    #[test]
+    fn test_days_since() {
+        let a = DateTime::from_str("2024-01-01T00:00:00Z").unwrap();
+        let b = DateTime::from_str("2024-03-01T12:00:00Z").unwrap();
+        assert_eq!(b.days_since(&a), 60);
+        assert_eq!(a.days_since(&b), -60);
+        assert_eq!(a.days_since(&a), 0);
+    }
+
+    #[test]
+    fn test_strftime() {
+        let dt = DateTime::from_str("2024-03-05T07:08:09.123456+08:00").unwrap();
+        assert_eq!(dt.strftime("%Y-%m-%d").unwrap(), "2024-03-05");
+        assert_eq!(dt.strftime("%d/%m/%Y").unwrap(), "05/03/2024");
+        assert_eq!(
+            dt.strftime("%Y-%m-%dT%H:%M:%S%Z").unwrap(),
+            "2024-03-05T07:08:09+08:00"
+        );
+        assert_eq!(
+            dt.strftime("%H:%M:%S.%f 100%%").unwrap(),
+            "07:08:09.123456 100%"
+        );
+        assert_eq!(dt.to_utc().strftime("%H%Z").unwrap(), "23Z");
+        assert!(dt.strftime("%Q").is_err());
+        assert!(dt.strftime("%Y%").is_err());
+    }
+
     #[test]
     fn test_format() {
         let dt = DateTime::default();
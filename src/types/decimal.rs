@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 use crate::Error;
-use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive, Zero};
 use rbs::Value;
 use serde::Deserializer;
 use std::cmp::Ordering;
@@ -115,6 +115,56 @@ impl Decimal {
     pub fn abs(&self) -> Self {
         Decimal::from(self.0.abs())
     }
+
+    /// is zero
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Checked division. Computes `self / rhs`, returning `None` if `rhs` is zero
+    pub fn checked_div(&self, rhs: &Decimal) -> Option<Decimal> {
+        if rhs.0.is_zero() {
+            return None;
+        }
+        Some(Decimal(self.0.clone() / rhs.0.clone()))
+    }
+
+    /// Checked remainder. Computes `self % rhs`, returning `None` if `rhs` is zero
+    pub fn checked_rem(&self, rhs: &Decimal) -> Option<Decimal> {
+        if rhs.0.is_zero() {
+            return None;
+        }
+        Some(Decimal(self.0.clone() % rhs.0.clone()))
+    }
+
+    /// Square root, returning `None` if `self` is negative
+    pub fn checked_sqrt(&self) -> Option<Decimal> {
+        self.0.sqrt().map(Decimal)
+    }
+
+    /// Return a new Decimal with trailing zeros stripped
+    ///```rust
+    /// use rbdc::Decimal;
+    /// let n: Decimal = "1.1000".parse().unwrap();
+    /// assert_eq!(n.normalize().to_string(), "1.1");
+    /// ```
+    pub fn normalize(&self) -> Decimal {
+        Decimal(self.0.normalized())
+    }
+
+    /// true when there is no fractional part, e.g. `1.000`
+    pub fn is_integer(&self) -> bool {
+        self.normalize().fractional_digit_count() <= 0
+    }
+
+    /// to i64, returning `None` when the value has a fractional part or overflows
+    pub fn to_i64_checked(&self) -> Option<i64> {
+        if !self.is_integer() {
+            return None;
+        }
+        self.0.to_i64()
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for Decimal {
@@ -451,6 +501,69 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checked_div_zero() {
+        let v1 = Decimal::new("1").unwrap();
+        assert_eq!(v1.checked_div(&Decimal::new("0").unwrap()), None);
+        assert_eq!(v1.checked_div(&Decimal::new("0.000").unwrap()), None);
+        assert_eq!(v1.checked_rem(&Decimal::new("0").unwrap()), None);
+        assert!(Decimal::new("0.00").unwrap().is_zero());
+        assert!(!v1.is_zero());
+    }
+
+    #[test]
+    fn test_checked_div_negative() {
+        let v1 = Decimal::new("-1").unwrap();
+        let v2 = Decimal::new("4").unwrap();
+        assert_eq!(v1.checked_div(&v2), Some(Decimal::new("-0.25").unwrap()));
+    }
+
+    #[test]
+    fn test_checked_rem() {
+        let pi = Decimal::new("3.14159").unwrap();
+        let sqrt2 = Decimal::new("1.41421").unwrap();
+        assert_eq!(
+            pi.checked_rem(&sqrt2),
+            Some(Decimal::new("0.31317").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_checked_sqrt() {
+        let v = Decimal::new("2.25").unwrap();
+        assert_eq!(v.checked_sqrt(), Some(Decimal::new("1.5").unwrap()));
+        assert_eq!(Decimal::new("-1").unwrap().checked_sqrt(), None);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let v = "1.100".parse::<Decimal>().unwrap().normalize();
+        assert_eq!(v.to_string(), "1.1");
+        assert_eq!(
+            Decimal::new("1.1000").unwrap().normalize(),
+            Decimal::new("1.1").unwrap().normalize()
+        );
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(Decimal::new("12").unwrap().is_integer());
+        assert!(Decimal::new("12.000").unwrap().is_integer());
+        assert!(!Decimal::new("12.01").unwrap().is_integer());
+    }
+
+    #[test]
+    fn test_to_i64_checked() {
+        assert_eq!(Decimal::new("-12.00").unwrap().to_i64_checked(), Some(-12));
+        assert_eq!(Decimal::new("12.5").unwrap().to_i64_checked(), None);
+        assert_eq!(
+            Decimal::new("99999999999999999999")
+                .unwrap()
+                .to_i64_checked(),
+            None
+        );
+    }
+
     #[test]
     fn test_fractional_digit_count() {
         let v = Decimal::new("1.123456").unwrap();
@@ -1,3 +1,4 @@
+use crate::types::datetime::with_seconds;
 use crate::{DateTime, Error};
 use rbs::Value;
 use std::fmt::{Debug, Display, Formatter};
@@ -28,8 +29,24 @@ impl From<Time> for Value {
 impl FromStr for Time {
     type Err = Error;
 
+    /// accepts `15:04`, `15:04:05` and `15:04:05.1234567`,
+    /// or a datetime such as `2024-01-02T15:04:05` whose time part is taken
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Time(fastdate::Time::from_str(s).map_err(|e|Error::from(e.to_string()))?))
+        let mut s = s.trim();
+        let b = s.as_bytes();
+        if b.len() > 11 && b[4] == b'-' && (b[10] == b' ' || b[10] == b'T') {
+            s = &s[11..];
+        }
+        let s = s.strip_suffix('Z').unwrap_or(s);
+        let v = match fastdate::Time::from_str(s) {
+            Ok(v) => v,
+            Err(e) => match with_seconds(s, 0) {
+                Some(s) => fastdate::Time::from_str(&s),
+                None => Err(e),
+            }
+            .map_err(|e| Error::from(e.to_string()))?,
+        };
+        Ok(Time(v))
     }
 }
 
@@ -45,6 +62,52 @@ impl From<DateTime> for Time {
     }
 }
 
+impl TryFrom<Value> for Time {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Ext(_, v) => Time::try_from(*v),
+            Value::String(v) => Time::from_str(&v),
+            _ => Err(Error::from(format!("warn type decode Time:{}", value))),
+        }
+    }
+}
+
+impl Time {
+    /// hour 0-23, minute 0-59, second 0-59
+    pub fn from_hms(h: u8, m: u8, s: u8) -> Result<Time, Error> {
+        if h > 23 || m > 59 || s > 59 {
+            return Err(Error::from(format!(
+                "Time out of range: {:02}:{:02}:{:02}",
+                h, m, s
+            )));
+        }
+        Ok(Time(fastdate::Time {
+            nano: 0,
+            sec: s,
+            minute: m,
+            hour: h,
+        }))
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.0.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.0.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.0.sec
+    }
+
+    pub fn nanosecond(&self) -> u32 {
+        self.0.nano
+    }
+}
+
 impl Default for Time {
     fn default() -> Self {
         Time(fastdate::Time {
@@ -55,3 +118,61 @@ impl Default for Time {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::Time;
+    use rbs::Value;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_from_str_formats() {
+        let cases = [
+            ("15:04", "15:04:00"),
+            ("15:04:05", "15:04:05"),
+            ("15:04:05.123", "15:04:05.123"),
+            ("15:04:05.1234567", "15:04:05.1234567"),
+            ("15:04:05Z", "15:04:05"),
+            ("2024-01-02 15:04:05", "15:04:05"),
+            ("2024-01-02T15:04:05.5", "15:04:05.5"),
+            (" 15:04:05 ", "15:04:05"),
+        ];
+        for (s, expect) in cases {
+            assert_eq!(Time::from_str(s).unwrap().to_string(), expect, "{}", s);
+        }
+        assert!(Time::from_str("1504").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let t = Time::from_str("23:59:59.999999").unwrap();
+        assert_eq!(t.hour(), 23);
+        assert_eq!(t.minute(), 59);
+        assert_eq!(t.second(), 59);
+        assert_eq!(t.nanosecond(), 999_999_000);
+        let t = Time::from_str("00:00:00").unwrap();
+        assert_eq!(t, Time::default());
+        assert_eq!(t.nanosecond(), 0);
+    }
+
+    #[test]
+    fn test_from_hms() {
+        assert_eq!(
+            Time::from_hms(12, 30, 5).unwrap(),
+            Time::from_str("12:30:05").unwrap()
+        );
+        assert!(Time::from_hms(24, 0, 0).is_err());
+        assert!(Time::from_hms(0, 60, 0).is_err());
+        assert!(Time::from_hms(0, 0, 60).is_err());
+    }
+
+    #[test]
+    fn test_ser_de() {
+        let t = Time::from_str("08:15:30.000001").unwrap();
+        let v = rbs::value!(&t);
+        let r: Time = rbs::from_value(v.clone()).unwrap();
+        assert_eq!(r, t);
+        assert_eq!(Time::try_from(v).unwrap(), t);
+        assert_eq!(Time::try_from(Value::from(t.clone())).unwrap(), t);
+    }
+}
@@ -4,19 +4,62 @@ use serde::Deserializer;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
-/// Timestamp(timestamp_millis:u64)
+/// Timestamp(timestamp_micros:i64)
 #[derive(serde::Serialize, Clone, Eq, PartialEq, Hash)]
 #[serde(rename = "Timestamp")]
 pub struct Timestamp(pub i64);
 
+/// values below this are treated as the old millisecond format when decoding.
+/// 10^14 micros is 1973-03-03, 10^14 millis is year 5138
+const MICROS_THRESHOLD: i64 = 100_000_000_000_000;
+
 impl Timestamp {
     #[deprecated(note = "please use utc()")]
     pub fn now() -> Self {
-        Self(fastdate::DateTime::utc().unix_timestamp_millis())
+        Self(fastdate::DateTime::utc().unix_timestamp_micros())
     }
     /// utc time
     pub fn utc() -> Self {
-        Self(fastdate::DateTime::utc().unix_timestamp_millis())
+        Self(fastdate::DateTime::utc().unix_timestamp_micros())
+    }
+
+    pub fn from_unix_seconds(secs: i64) -> Self {
+        Self(secs * 1_000_000)
+    }
+
+    pub fn from_unix_millis(ms: i64) -> Self {
+        Self(ms * 1000)
+    }
+
+    pub fn from_unix_micros(micros: i64) -> Self {
+        Self(micros)
+    }
+
+    pub fn unix_timestamp_micros(&self) -> i64 {
+        self.0
+    }
+
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        self.0.div_euclid(1000)
+    }
+
+    /// the instant of a `Value::Ext("Timestamp", ..)` payload: `I64` is micros,
+    /// `U64` is millis, the payload mysql decoded `TIMESTAMP` to before micros.
+    /// drivers encode with it, the unit is never guessed
+    pub fn from_ext_value(v: &Value) -> Option<Self> {
+        match v {
+            Value::U64(v) => Some(Self::from_unix_millis(*v as i64)),
+            v => v.as_i64().map(Self),
+        }
+    }
+
+    /// decode legacy data that carries no unit, the old millisecond format is detected by magnitude
+    fn from_stored(v: i64) -> Self {
+        if v.abs() < MICROS_THRESHOLD {
+            Self::from_unix_millis(v)
+        } else {
+            Self(v)
+        }
     }
 }
 
@@ -26,9 +69,13 @@ impl<'de> serde::Deserialize<'de> for Timestamp {
         D: Deserializer<'de>,
     {
         use serde::de::Error;
-        match Value::deserialize(deserializer)?.as_i64() {
-            None => Err(Error::custom("warn type decode Json")),
-            Some(v) => Ok(Self(v)),
+        match Value::deserialize(deserializer)? {
+            // the micros of `From<Timestamp> for Value`
+            Value::I64(v) => Ok(Self(v)),
+            v => match v.as_i64() {
+                None => Err(Error::custom("warn type decode Json")),
+                Some(v) => Ok(Self::from_stored(v)),
+            },
         }
     }
 }
@@ -61,7 +108,7 @@ impl FromStr for Timestamp {
 
 impl From<Timestamp> for fastdate::DateTime {
     fn from(value: Timestamp) -> Self {
-        fastdate::DateTime::from_timestamp_millis(value.0 as i64)
+        fastdate::DateTime::from_timestamp_micros(value.0)
     }
 }
 
@@ -73,13 +120,13 @@ impl Default for Timestamp {
 
 impl From<DateTime> for Timestamp {
     fn from(value: DateTime) -> Self {
-        Self(value.unix_timestamp_millis())
+        Self(value.unix_timestamp_micros())
     }
 }
 
 impl Into<DateTime> for Timestamp {
     fn into(self) -> DateTime {
-        DateTime::from_timestamp_millis(self.0)
+        DateTime::from_timestamp_micros(self.0)
     }
 }
 
@@ -107,14 +154,83 @@ mod test {
 
     #[test]
     fn test_decode_timestamp_u64() {
-        assert_eq!(Timestamp(1), rbs::from_value(Value::U64(1)).unwrap());
+        assert_eq!(Timestamp(1000), rbs::from_value(Value::U64(1)).unwrap());
     }
 
     #[test]
     fn test_decode_timestamp_ext() {
         assert_eq!(
-            Timestamp(1),
+            Timestamp(1000),
             rbs::from_value(Value::Ext("Timestamp", Box::new(Value::U64(1)))).unwrap()
         );
     }
+
+    #[test]
+    fn test_decode_timestamp_millis_micros() {
+        // legacy data without the `I64` micros payload, such as json numbers
+        let millis = 1_713_491_896_123u64;
+        let t: Timestamp = rbs::from_value(Value::U64(millis)).unwrap();
+        assert_eq!(t, Timestamp::from_unix_millis(millis as i64));
+        let t: Timestamp = serde_json::from_str(&millis.to_string()).unwrap();
+        assert_eq!(t, Timestamp::from_unix_millis(millis as i64));
+        let micros = 1_713_491_896_123_456u64;
+        let t: Timestamp = rbs::from_value(Value::U64(micros)).unwrap();
+        assert_eq!(t, Timestamp(micros as i64));
+        // the `I64` payload is micros as is
+        let t: Timestamp = rbs::from_value(Value::I64(millis as i64)).unwrap();
+        assert_eq!(t, Timestamp(millis as i64));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let t = Timestamp(1_713_491_896_123_456);
+        assert_eq!(t.to_string().parse::<Timestamp>().unwrap(), t);
+        let t = Timestamp::from_unix_seconds(31_536_000);
+        assert_eq!(t.to_string().parse::<Timestamp>().unwrap(), t);
+    }
+
+    #[test]
+    fn test_before_1973_round_trip() {
+        // 1971-01-01, below the millisecond threshold of legacy data
+        let t = Timestamp::from_unix_seconds(31_536_000);
+        let v: Value = t.clone().into();
+        assert_eq!(Timestamp::from_ext_value(&v).unwrap(), t);
+        assert_eq!(rbs::from_value::<Timestamp>(v).unwrap(), t);
+        assert_eq!(rbs::from_value::<Timestamp>(rbs::to_value(&t).unwrap()).unwrap(), t);
+        let dt: DateTime = t.into();
+        assert_eq!(dt.year(), 1971);
+    }
+
+    #[test]
+    fn test_from_ext_value() {
+        let millis = 1_713_491_896_123i64;
+        assert_eq!(
+            Timestamp::from_ext_value(&Value::U64(millis as u64)).unwrap(),
+            Timestamp::from_unix_millis(millis)
+        );
+        assert_eq!(
+            Timestamp::from_ext_value(&Value::I64(millis)).unwrap(),
+            Timestamp(millis)
+        );
+    }
+
+    #[test]
+    fn test_from_unix() {
+        assert_eq!(Timestamp::from_unix_seconds(2).0, 2_000_000);
+        assert_eq!(Timestamp::from_unix_millis(-2).0, -2_000);
+        assert_eq!(Timestamp(-1).unix_timestamp_millis(), -1);
+    }
+
+    #[test]
+    fn test_micros_round_trip() {
+        let t = Timestamp(1_713_491_896_123_456);
+        let v: Value = t.clone().into();
+        assert_eq!(v, Value::Ext("Timestamp", Box::new(Value::I64(t.0))));
+        assert_eq!(rbs::from_value::<Timestamp>(v).unwrap(), t);
+        let new_t: Timestamp = serde_json::from_value(serde_json::to_value(&t).unwrap()).unwrap();
+        assert_eq!(new_t, t);
+        let dt: DateTime = t.clone().into();
+        assert_eq!(dt.micro(), 123456);
+        assert_eq!(Timestamp::from(dt), t);
+    }
 }
@@ -1,12 +1,23 @@
 use crate::Error;
 use rbs::Value;
-use serde::Deserializer;
+use serde::{Deserializer, Serializer};
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
-#[derive(serde::Serialize, Clone, Eq, PartialEq, Hash)]
-#[serde(rename = "Uuid")]
-pub struct Uuid(pub String);
+/// Uuid(bytes:[u8;16])
+///
+/// serialized as `Value::Ext("Uuid", Value::String("hyphenated uuid"))`
+#[derive(Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Uuid(pub [u8; 16]);
+
+impl serde::Serialize for Uuid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("Uuid", &self.to_string())
+    }
+}
 
 impl<'de> serde::Deserialize<'de> for Uuid {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -16,26 +27,39 @@ impl<'de> serde::Deserialize<'de> for Uuid {
         use serde::de::Error;
         match Value::deserialize(deserializer)?.into_string() {
             None => Err(D::Error::custom("warn type decode Uuid")),
-            Some(v) => Ok(Self(v)),
+            Some(v) => Uuid::from_str(&v).map_err(|e| D::Error::custom(e.to_string())),
         }
     }
 }
 
 impl Display for Uuid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        Display::fmt(&uuid::Uuid::from_bytes(self.0).hyphenated(), f)
     }
 }
 
 impl Debug for Uuid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Uuid({})", self.0)
+        write!(f, "Uuid({})", self)
     }
 }
 
 impl From<Uuid> for Value {
     fn from(arg: Uuid) -> Self {
-        Value::Ext("Uuid", Box::new(Value::String(arg.0)))
+        Value::Ext("Uuid", Box::new(Value::String(arg.to_string())))
+    }
+}
+
+impl TryFrom<Value> for Uuid {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Ext(_, v) => Uuid::try_from(*v),
+            Value::String(v) => Uuid::from_str(&v),
+            Value::Binary(v) => Uuid::from_slice(&v),
+            _ => Err(Error::from(format!("warn type decode Uuid:{}", value))),
+        }
     }
 }
 
@@ -43,27 +67,76 @@ impl FromStr for Uuid {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Uuid(s.to_string()))
+        let v = uuid::Uuid::from_str(s).map_err(|e| Error::from(format!("Uuid:{}", e)))?;
+        Ok(Uuid(v.into_bytes()))
+    }
+}
+
+impl From<uuid::Uuid> for Uuid {
+    fn from(arg: uuid::Uuid) -> Self {
+        Uuid(arg.into_bytes())
+    }
+}
+
+impl From<Uuid> for uuid::Uuid {
+    fn from(arg: Uuid) -> Self {
+        uuid::Uuid::from_bytes(arg.0)
     }
 }
 
 impl Uuid {
     ///new for uuid v4
     pub fn new() -> Self {
-        Self(uuid::Uuid::new_v4().to_string())
+        Self::new_v4()
+    }
+
+    ///new random uuid v4
+    pub fn new_v4() -> Self {
+        Self(uuid::Uuid::new_v4().into_bytes())
+    }
+
+    ///new time ordered uuid v7, sorts by creation time so it suits primary keys
+    pub fn new_v7() -> Self {
+        Self(uuid::Uuid::now_v7().into_bytes())
+    }
+
+    /// parse the hyphenated, simple, braced or urn form
+    pub fn parse_str(s: &str) -> Result<Self, Error> {
+        Self::from_str(s)
+    }
+
+    /// '00000000-0000-0000-0000-000000000000'
+    pub const fn nil() -> Self {
+        Self([0; 16])
+    }
+
+    /// from 16 raw bytes
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let v = uuid::Uuid::from_slice(bytes).map_err(|e| Error::from(format!("Uuid:{}", e)))?;
+        Ok(Uuid(v.into_bytes()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.0 == [0; 16]
     }
 }
 
 /// '00000000-0000-0000-0000-000000000000'
 impl Default for Uuid {
     fn default() -> Self {
-        Uuid(uuid::Uuid::default().to_string())
+        Self::nil()
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::Uuid;
+    use rbs::Value;
+    use std::str::FromStr;
 
     #[test]
     fn test_default() {
@@ -71,4 +144,44 @@ mod test {
         println!("{}", u);
         assert_eq!(u.to_string(), "00000000-0000-0000-0000-000000000000");
     }
+
+    #[test]
+    fn test_nil() {
+        assert_eq!(Uuid::nil().0, [0u8; 16]);
+        assert!(Uuid::nil().is_nil());
+        assert!(!Uuid::new_v4().is_nil());
+    }
+
+    #[test]
+    fn test_new_v7() {
+        let a = Uuid::new_v7();
+        let b = Uuid::new_v7();
+        assert_eq!(uuid::Uuid::from(a).get_version_num(), 7);
+        assert!(a < b);
+        assert_eq!(Uuid::parse_str(&a.to_string()).unwrap(), a);
+        assert!(Uuid::parse_str("not a uuid").is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let u = Uuid::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        assert_eq!(u.0[0], 0x67);
+        assert_eq!(u.0[15], 0xc8);
+        assert_eq!(u.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert!(Uuid::from_str("not a uuid").is_err());
+    }
+
+    #[test]
+    fn test_ser_de() {
+        let u = Uuid::new_v4();
+        let v = rbs::value!(&u);
+        assert_eq!(
+            v,
+            Value::Ext("Uuid", Box::new(Value::String(u.to_string())))
+        );
+        let r: Uuid = rbs::from_value(v.clone()).unwrap();
+        assert_eq!(r, u);
+        assert_eq!(Uuid::try_from(v).unwrap(), u);
+        assert_eq!(Value::from(u), rbs::value!(&u));
+    }
 }
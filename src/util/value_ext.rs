@@ -0,0 +1,111 @@
+use rbs::Value;
+
+/// coercions of a [`Value`] that return `None` instead of a lossy or wrong result
+pub trait ValueExt {
+    /// `I32`/`I64`/`U32`/`U64`, or a `F32`/`F64` without fraction, that fits an `i64`
+    fn try_as_i64(&self) -> Option<i64>;
+
+    /// any numeric variant, integers above 2^53 lose precision
+    fn try_as_f64(&self) -> Option<f64>;
+
+    /// `String`, or an `Ext` wrapping a `String` such as `Ext("Decimal", "1.5")`
+    fn try_as_str(&self) -> Option<&str>;
+
+    /// `I32`/`I64`/`U32`/`U64`/`F32`/`F64`
+    fn is_numeric(&self) -> bool;
+}
+
+impl ValueExt for Value {
+    fn try_as_i64(&self) -> Option<i64> {
+        match self {
+            Value::I32(v) => Some(*v as i64),
+            Value::I64(v) => Some(*v),
+            Value::U32(v) => Some(*v as i64),
+            Value::U64(v) => i64::try_from(*v).ok(),
+            Value::F32(v) => f64_to_i64(*v as f64),
+            Value::F64(v) => f64_to_i64(*v),
+            _ => None,
+        }
+    }
+
+    fn try_as_f64(&self) -> Option<f64> {
+        match self {
+            Value::I32(v) => Some(*v as f64),
+            Value::I64(v) => Some(*v as f64),
+            Value::U32(v) => Some(*v as f64),
+            Value::U64(v) => Some(*v as f64),
+            Value::F32(v) => Some(*v as f64),
+            Value::F64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn try_as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(v) => Some(v),
+            Value::Ext(_, v) => v.try_as_str(),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Value::I32(_)
+                | Value::I64(_)
+                | Value::U32(_)
+                | Value::U64(_)
+                | Value::F32(_)
+                | Value::F64(_)
+        )
+    }
+}
+
+// `i64::MAX as f64` rounds up to 2^63, which is out of range
+fn f64_to_i64(v: f64) -> Option<i64> {
+    if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 {
+        Some(v as i64)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::value_ext::ValueExt;
+    use rbs::Value;
+
+    #[test]
+    fn test_try_as_i64() {
+        assert_eq!(Value::I32(42).try_as_i64(), Some(42));
+        assert_eq!(Value::U32(u32::MAX).try_as_i64(), Some(u32::MAX as i64));
+        assert_eq!(Value::U64(i64::MAX as u64).try_as_i64(), Some(i64::MAX));
+        assert_eq!(Value::U64(u64::MAX).try_as_i64(), None);
+        assert_eq!(Value::F64(-3.0).try_as_i64(), Some(-3));
+        assert_eq!(Value::F64(1.5).try_as_i64(), None);
+        assert_eq!(Value::F64(f64::NAN).try_as_i64(), None);
+        assert_eq!(Value::F64(9.3e18).try_as_i64(), None);
+        assert_eq!(Value::F64(i64::MIN as f64).try_as_i64(), Some(i64::MIN));
+        assert_eq!(Value::String("1".to_string()).try_as_i64(), None);
+    }
+
+    #[test]
+    fn test_try_as_f64() {
+        assert_eq!(Value::I64(-2).try_as_f64(), Some(-2.0));
+        assert_eq!(Value::U64(u64::MAX).try_as_f64(), Some(u64::MAX as f64));
+        assert_eq!(Value::F32(0.5).try_as_f64(), Some(0.5));
+        assert_eq!(Value::Bool(true).try_as_f64(), None);
+        assert!(Value::U32(1).is_numeric());
+        assert!(!Value::Null.is_numeric());
+        assert!(!Value::String("1".to_string()).is_numeric());
+    }
+
+    #[test]
+    fn test_try_as_str() {
+        assert_eq!(Value::String("a".to_string()).try_as_str(), Some("a"));
+        let v = Value::String("1.5".to_string()).into_ext("Decimal");
+        assert_eq!(v.try_as_str(), Some("1.5"));
+        assert_eq!(Value::Binary(vec![]).into_ext("Uuid").try_as_str(), None);
+        assert_eq!(Value::I32(1).try_as_str(), None);
+    }
+}